@@ -1,11 +1,12 @@
-use crate::config::{Cli, Config};
-use crate::display::formatter::WeatherFormatter;
+use crate::cache::{CachedWeather, cache_weather, get_cached_weather};
+use crate::config::{Cli, Config, Shown};
+use crate::display::formatter::{LocationWeather, WeatherFormatter};
 use crate::errors::RustormyError;
 use crate::live::run as run_live;
 use crate::models::{Provider, Weather};
-use crate::weather::{GetWeather, GetWeatherProvider, enrich};
+use crate::weather::{GetWeather, GetWeatherProvider, enrich, enrich_hourly, http};
+use chrono::{DateTime, Local, Utc};
 use reqwest::blocking::Client;
-use std::time::Duration;
 
 pub struct App {
     client: Client,
@@ -18,13 +19,23 @@ impl App {
     pub fn new() -> Result<App, RustormyError> {
         let mut config = Config::new(Cli::new())?;
         if !config.live_mode() {
-            crate::logging::init(config.verbose(), config.format().use_colors);
+            crate::logging::init(config.verbose(), config.format().colors.for_stderr());
         }
-        let client = Client::builder()
-            .user_agent(concat!("rustormy/", env!("CARGO_PKG_VERSION")))
-            .timeout(Duration::from_secs(config.connect_timeout()))
-            .build()?;
-        let provider = GetWeatherProvider::new(config.take_next_provider().unwrap_or_default());
+        for name in config.unknown_env_vars() {
+            crate::logging::warn_always(&format!(
+                "Ignoring {name}: it sets no configuration option"
+            ));
+        }
+        http::init_mode(config.http_mode().clone());
+        http::init_cache(config.use_http_cache());
+        let client = http::build_client(&config)?;
+        // Each of several locations starts with the providers of its own configuration
+        let first_provider = if config.locations().is_empty() {
+            config.take_next_provider()
+        } else {
+            None
+        };
+        let provider = GetWeatherProvider::new(first_provider.unwrap_or_default());
         let formatter = WeatherFormatter::new(&config);
         Ok(Self {
             client,
@@ -34,6 +45,19 @@ impl App {
         })
     }
 
+    /// App fetching one of the configured `locations`
+    fn for_location(&self, location: &Shown) -> App {
+        let mut config = self.config.for_location(location);
+        let provider = GetWeatherProvider::new(config.take_next_provider().unwrap_or_default());
+        let formatter = WeatherFormatter::new(&config);
+        Self {
+            client: self.client.clone(),
+            config,
+            provider,
+            formatter,
+        }
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -42,19 +66,44 @@ impl App {
         &self.formatter
     }
 
+    pub fn formatter_mut(&mut self) -> &mut WeatherFormatter {
+        &mut self.formatter
+    }
+
     pub fn fetch_with_fallback(&mut self) -> Result<Weather, RustormyError> {
+        let mut tried = Vec::new();
         loop {
+            let p: Provider = (&self.provider).into();
+            if let Some(CachedWeather {
+                fetched_at,
+                mut weather,
+            }) = self.fresh_cached_weather(p)
+            {
+                // Charts turned on since the reading was cached need its hourly forecast. The
+                // rest is not fetched again before the reading expires, so that frequent runs
+                // don't use up the quotas of OpenUV and the other extra requests.
+                if weather.hourly.is_empty() {
+                    enrich_hourly(&mut weather, &self.client, &self.config);
+                    if !weather.hourly.is_empty() {
+                        self.store_cached_weather(p, &weather, fetched_at);
+                    }
+                }
+                return Ok(weather);
+            }
+            tried.push(p);
             match self.provider.get_weather(&self.client, &self.config) {
                 Ok(mut weather) => {
                     enrich(&mut weather, &self.client, &self.config);
+                    self.store_cached_weather(p, &weather, Utc::now());
                     return Ok(weather);
                 }
                 Err(error) => match error {
-                    RustormyError::ApiReturnedError(_) | RustormyError::HttpRequestFailed(_) => {
-                        let p: Provider = (&self.provider).into();
+                    RustormyError::ApiReturnedError(_)
+                    | RustormyError::HttpRequestFailed(_)
+                    | RustormyError::NoRecordedResponse(_) => {
                         crate::warn!("Provider {p:?} failed: {error}");
                         let Some(next) = self.config.take_next_provider() else {
-                            return Err(error);
+                            return self.stale_cached_weather(&tried).ok_or(error);
                         };
                         self.provider = GetWeatherProvider::new(next);
                     }
@@ -64,7 +113,99 @@ impl App {
         }
     }
 
+    fn fresh_cached_weather(&self, provider: Provider) -> Option<CachedWeather> {
+        if !self.config.use_weather_cache() {
+            return None;
+        }
+        match get_cached_weather(provider, &self.config) {
+            Ok(Some(entry)) if entry.is_fresh(self.config.weather_cache_ttl(), Utc::now()) => {
+                crate::info!("weather cache hit ({provider:?})");
+                Some(entry)
+            }
+            Ok(_) => {
+                crate::info!("weather cache miss ({provider:?})");
+                None
+            }
+            Err(error) => {
+                crate::warn!("Failed to read weather cache: {error}");
+                None
+            }
+        }
+    }
+
+    /// Cache a reading fetched at `fetched_at`, which a completed cached reading keeps so it
+    /// still expires on time
+    fn store_cached_weather(
+        &self,
+        provider: Provider,
+        weather: &Weather,
+        fetched_at: DateTime<Utc>,
+    ) {
+        if !self.config.use_weather_cache() && !self.config.serve_stale_weather() {
+            return;
+        }
+        if let Err(error) = cache_weather(provider, &self.config, weather, fetched_at) {
+            crate::warn!("Failed to write weather cache: {error}");
+        }
+    }
+
+    /// Pick the most recent cached reading among the providers that just failed
+    fn stale_cached_weather(&self, tried: &[Provider]) -> Option<Weather> {
+        if !self.config.serve_stale_weather() {
+            return None;
+        }
+        let entry = tried
+            .iter()
+            .filter_map(|&p| get_cached_weather(p, &self.config).ok().flatten())
+            .max_by_key(|entry| entry.fetched_at)?;
+        crate::warn!(
+            "All providers failed, showing cached weather from {}",
+            entry
+                .fetched_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+        );
+        Some(Weather {
+            stale_since: Some(entry.fetched_at),
+            ..entry.weather
+        })
+    }
+
+    /// Fetch every configured location at the same time and show them together
+    fn run_locations(&self) {
+        let mut apps: Vec<(&Shown, App)> = self
+            .config
+            .locations()
+            .iter()
+            .map(|location| (location, self.for_location(location)))
+            .collect();
+        let results: Vec<LocationWeather> = std::thread::scope(|scope| {
+            let handles: Vec<_> = apps
+                .iter_mut()
+                .map(|(location, app)| {
+                    let format = app.config().format().clone();
+                    (location, format, scope.spawn(|| app.fetch_with_fallback()))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|(location, format, handle)| LocationWeather {
+                    name: location.name().to_string(),
+                    format,
+                    weather: handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic)),
+                })
+                .collect()
+        });
+        self.formatter.display_many(&results);
+    }
+
     pub fn run(&mut self) {
+        if !self.config.locations().is_empty() {
+            self.run_locations();
+            return;
+        }
         if self.config.live_mode() {
             if let Err(error) = run_live(self) {
                 self.formatter.display_error(&error);
@@ -78,3 +219,72 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Location, WeatherConditionIcon};
+    use std::sync::atomic::Ordering;
+
+    const HOURLY: &str = r#"{"hourly":{"time":[1792296000,1792299600],"temperature_2m":[12.0,13.0],"precipitation_probability":[10,20],"precipitation":[0.0,0.1]}}"#;
+
+    #[test]
+    fn test_cached_weather_is_completed_once() {
+        let (url, requests) = http::serve(&[HOURLY], std::time::Duration::ZERO);
+        let config: Config = toml::from_str(&format!(
+            r#"
+            lat = 12.34
+            lon = 56.78
+            use_weather_cache = true
+            api_keys = {{ open_uv = "test" }}
+            base_urls = {{ open_meteo = "{url}", open_meteo_air_quality = "{url}", open_uv = "{url}" }}
+            format = {{ chart = true, fields = ["temperature", "aqi"] }}
+            "#
+        ))
+        .unwrap();
+        // Cached without the UV index, air quality and hourly forecast
+        let weather = Weather {
+            temperature: 12.5,
+            feels_like: 11.0,
+            humidity: 70,
+            dew_point: 7.2,
+            precipitation: 0.0,
+            pressure: 1015,
+            wind_speed: 3.0,
+            wind_direction: 90,
+            uv_index: None,
+            aqi: None,
+            is_day: Some(true),
+            description: "Clear sky".to_string(),
+            icon: WeatherConditionIcon::Clear,
+            location: Location::new("Test City".to_string(), 12.34, 56.78),
+            stale_since: None,
+            expires: None,
+            provider: None,
+            hourly: Vec::new(),
+        };
+        cache_weather(Provider::OpenMeteo, &config, &weather, Utc::now()).unwrap();
+        let app = || App {
+            client: http::build_client(&config).unwrap(),
+            config: config.clone(),
+            provider: GetWeatherProvider::new(Provider::OpenMeteo),
+            formatter: WeatherFormatter::new(&config),
+        };
+
+        let first = app().fetch_with_fallback().unwrap();
+        assert_eq!(first.hourly.len(), 2);
+        assert_eq!(
+            requests.load(Ordering::SeqCst),
+            1,
+            "only the hourly forecast"
+        );
+
+        let second = app().fetch_with_fallback().unwrap();
+        assert_eq!(second.hourly.len(), 2);
+        assert_eq!(
+            requests.load(Ordering::SeqCst),
+            1,
+            "no request within the TTL"
+        );
+    }
+}
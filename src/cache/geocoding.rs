@@ -0,0 +1,359 @@
+use super::get_cache_dir;
+use crate::errors::RustormyError;
+use crate::models::{Language, Location};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+pub(super) const INDEX_FILE_NAME: &str = "geocoding.json";
+/// Per-city files written by versions before the indexed store
+const LEGACY_FILE_PREFIX: &str = "geocoding_";
+
+/// Held while the index is read and written back, so locations looked up at the same time
+/// don't overwrite each other's entries
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_index() -> MutexGuard<'static, ()> {
+    INDEX_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A single geocoding result stored in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeocodingEntry {
+    /// City name as it was typed by the user
+    pub query: String,
+    pub language: Language,
+    pub location: Location,
+    pub cached_at: DateTime<Utc>,
+    /// `None` means the entry never expires
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl GeocodingEntry {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// All cached geocoding results, keyed by language and normalized city name
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct GeocodingIndex {
+    entries: BTreeMap<String, GeocodingEntry>,
+}
+
+/// Normalize a city name so that "  new   York" and "New York" share an entry
+fn normalize_city(city: &str) -> String {
+    city.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn index_key(city: &str, language: Language) -> String {
+    format!("{}:{}", language.code(), normalize_city(city))
+}
+
+impl GeocodingIndex {
+    pub(super) fn load(path: &Path) -> Result<Self, RustormyError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    /// Write the index to a temporary file first, so a crash never leaves a truncated index behind.
+    /// The file is named after the process, so that other processes saving at the same time don't
+    /// write to it. Per-city files of older versions are removed once the index has replaced them.
+    fn save(&self, path: &Path) -> Result<(), RustormyError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        serde_json::to_writer(File::create(&tmp_path)?, self)?;
+        fs::rename(tmp_path, path)?;
+        if let Some(parent) = path.parent() {
+            remove_legacy_files(parent)?;
+        }
+        Ok(())
+    }
+
+    fn get(&self, city: &str, language: Language, now: DateTime<Utc>) -> Option<&Location> {
+        self.entries
+            .get(&index_key(city, language))
+            .filter(|entry| !entry.is_expired(now))
+            .map(|entry| &entry.location)
+    }
+
+    fn insert(
+        &mut self,
+        city: &str,
+        language: Language,
+        location: &Location,
+        ttl_days: u64,
+        now: DateTime<Utc>,
+    ) {
+        let expires_at = (ttl_days > 0).then(|| {
+            // Capped at 100 years so the timestamp never overflows
+            now + Duration::days(i64::try_from(ttl_days.min(36_500)).unwrap_or(36_500))
+        });
+        self.entries.insert(
+            index_key(city, language),
+            GeocodingEntry {
+                query: city.trim().to_string(),
+                language,
+                location: location.clone(),
+                cached_at: now,
+                expires_at,
+            },
+        );
+    }
+
+    /// Remove the entries for a city in every language, returning how many were removed
+    fn remove(&mut self, city: &str) -> usize {
+        let city = normalize_city(city);
+        let before = self.entries.len();
+        self.entries
+            .retain(|_, entry| normalize_city(&entry.query) != city);
+        before - self.entries.len()
+    }
+
+    /// Remove expired entries, returning how many were removed
+    fn prune(&mut self, now: DateTime<Utc>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| !entry.is_expired(now));
+        before - self.entries.len()
+    }
+
+    pub(super) fn entries(&self) -> impl Iterator<Item = &GeocodingEntry> {
+        self.entries.values()
+    }
+}
+
+fn remove_legacy_files(dir: &Path) -> Result<(), RustormyError> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_legacy = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(LEGACY_FILE_PREFIX));
+        if is_legacy {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn load_index() -> Result<(GeocodingIndex, std::path::PathBuf), RustormyError> {
+    let path = get_cache_dir()?.join(INDEX_FILE_NAME);
+    Ok((GeocodingIndex::load(&path)?, path))
+}
+
+/// Retrieve a cached location if it exists and has not expired
+/// Returns Ok(Some(Location)) if found, Ok(None) if not found, or Err on error
+pub fn get_cached_location(
+    city: &str,
+    language: Language,
+) -> Result<Option<Location>, RustormyError> {
+    let (index, _) = load_index()?;
+    if let Some(location) = index.get(city, language, Utc::now()) {
+        crate::info!("cache hit \"{city}\"");
+        Ok(Some(location.clone()))
+    } else {
+        crate::info!("cache miss \"{city}\"");
+        Ok(None)
+    }
+}
+
+/// Cache a location, expiring after `ttl_days` days (`0` means never)
+pub fn cache_location(
+    city: &str,
+    language: Language,
+    location: &Location,
+    ttl_days: u64,
+) -> Result<(), RustormyError> {
+    let _guard = lock_index();
+    let (mut index, path) = load_index()?;
+    index.insert(city, language, location, ttl_days, Utc::now());
+    index.save(&path)
+}
+
+/// List every cached geocoding entry, including expired ones
+pub fn list_locations() -> Result<Vec<GeocodingEntry>, RustormyError> {
+    let (index, _) = load_index()?;
+    Ok(index.entries().cloned().collect())
+}
+
+/// Remove cached entries for a city in every language
+pub fn remove_location(city: &str) -> Result<usize, RustormyError> {
+    let _guard = lock_index();
+    let (mut index, path) = load_index()?;
+    let removed = index.remove(city);
+    if removed > 0 {
+        index.save(&path)?;
+    }
+    Ok(removed)
+}
+
+/// Remove expired geocoding entries
+pub fn prune_locations() -> Result<usize, RustormyError> {
+    let _guard = lock_index();
+    let (mut index, path) = load_index()?;
+    let removed = index.prune(Utc::now());
+    if removed > 0 {
+        index.save(&path)?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::clear_cache;
+
+    fn location(name: &str) -> Location {
+        Location::new(name.to_string(), 12.34, 56.78)
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_cache_location_and_retrieve() {
+        let city = "Test City";
+        let location = location(city);
+
+        // Cache the location
+        cache_location(city, Language::English, &location, 30).expect("Failed to cache location");
+        // Retrieve the cached location
+        let cached_location =
+            get_cached_location(city, Language::English).expect("Failed to get cached location");
+        assert!(cached_location.is_some());
+        let cached_location = cached_location.unwrap();
+        assert_eq!(cached_location.name, location.name);
+        assert_eq!(cached_location.latitude, location.latitude);
+        assert_eq!(cached_location.longitude, location.longitude);
+
+        // Check for a non-cached city
+        let non_cached = get_cached_location("Nonexistent City", Language::English)
+            .expect("Failed to get cached location");
+        assert!(non_cached.is_none());
+
+        // Check for a different language cache miss
+        let lang_miss =
+            get_cached_location(city, Language::Spanish).expect("Failed to get cached location");
+        assert!(lang_miss.is_none());
+
+        // Clean up the test cache file
+        clear_cache().expect("Failed to clear cached location");
+    }
+
+    #[test]
+    fn test_normalized_keys() {
+        let now = Utc::now();
+        let mut index = GeocodingIndex::default();
+        index.insert(
+            "  New   York ",
+            Language::English,
+            &location("New York"),
+            30,
+            now,
+        );
+
+        assert!(index.get("new york", Language::English, now).is_some());
+        assert!(index.get("NEW YORK", Language::English, now).is_some());
+        assert!(index.get("New York", Language::Russian, now).is_none());
+        assert_eq!(index.entries().next().unwrap().query, "New   York");
+    }
+
+    #[test]
+    fn test_city_names_with_slashes_share_one_index() {
+        let now = Utc::now();
+        let mut index = GeocodingIndex::default();
+        index.insert("Frankfurt/Main", Language::English, &location("F"), 30, now);
+        assert!(
+            index
+                .get("frankfurt/main", Language::English, now)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_expired_entries_are_ignored_and_pruned() {
+        let now = Utc::now();
+        let mut index = GeocodingIndex::default();
+        index.insert(
+            "Old",
+            Language::English,
+            &location("Old"),
+            1,
+            now - Duration::days(2),
+        );
+        index.insert("Fresh", Language::English, &location("Fresh"), 1, now);
+        index.insert(
+            "Forever",
+            Language::English,
+            &location("Forever"),
+            0,
+            now - Duration::days(9999),
+        );
+
+        assert!(index.get("Old", Language::English, now).is_none());
+        assert!(index.get("Fresh", Language::English, now).is_some());
+        assert!(index.get("Forever", Language::English, now).is_some());
+
+        assert_eq!(index.prune(now), 1);
+        assert_eq!(index.entries().count(), 2);
+    }
+
+    #[test]
+    fn test_remove_city_in_all_languages() {
+        let now = Utc::now();
+        let mut index = GeocodingIndex::default();
+        index.insert("Lisbon", Language::English, &location("Lisbon"), 30, now);
+        index.insert("lisbon", Language::Spanish, &location("Lisboa"), 30, now);
+        index.insert("Porto", Language::English, &location("Porto"), 30, now);
+
+        assert_eq!(index.remove("LISBON"), 2);
+        assert_eq!(index.remove("Lisbon"), 0);
+        assert_eq!(index.entries().count(), 1);
+    }
+
+    #[test]
+    fn test_index_roundtrip_and_legacy_cleanup() {
+        let dir = std::env::temp_dir().join("rustormy_test_geocoding_index");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let legacy_file = dir.join("geocoding_Test_City_en.json");
+        fs::write(&legacy_file, "{}").unwrap();
+        let path = dir.join(INDEX_FILE_NAME);
+
+        let mut index = GeocodingIndex::load(&path).unwrap();
+        assert!(legacy_file.exists(), "loading should not remove files");
+        index.insert(
+            "Lisbon",
+            Language::English,
+            &location("Lisbon"),
+            30,
+            Utc::now(),
+        );
+        index.save(&path).unwrap();
+        assert!(
+            !legacy_file.exists(),
+            "legacy cache files should be removed once the index is saved"
+        );
+
+        let loaded = GeocodingIndex::load(&path).unwrap();
+        assert!(
+            loaded
+                .get("lisbon", Language::English, Utc::now())
+                .is_some()
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
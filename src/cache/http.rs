@@ -0,0 +1,113 @@
+use super::{get_cache_dir, stable_hash};
+use crate::errors::RustormyError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+
+pub(super) const HTTP_FILE_PREFIX: &str = "http_";
+
+/// A response body stored together with the validators needed for conditional requests
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub url: String,
+    /// Raw `Last-Modified` header, sent back verbatim as `If-Modified-Since`
+    pub last_modified: Option<String>,
+    /// Parsed `Expires` header; the server should not be asked again before this time
+    pub expires: Option<DateTime<Utc>>,
+    pub body: String,
+}
+
+impl CachedResponse {
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires > now)
+    }
+}
+
+fn http_cache_file_name(url: &str) -> String {
+    format!("{HTTP_FILE_PREFIX}{:016x}.json", stable_hash(url))
+}
+
+fn read_cached_response(path: &Path, url: &str) -> Result<Option<CachedResponse>, RustormyError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let entry: CachedResponse = serde_json::from_reader(File::open(path)?)?;
+    // Guard against hash collisions
+    Ok((entry.url == url).then_some(entry))
+}
+
+fn write_cached_response(path: &Path, entry: &CachedResponse) -> Result<(), RustormyError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    serde_json::to_writer(File::create(path)?, entry)?;
+    Ok(())
+}
+
+/// Retrieve the last response cached for this URL, regardless of its expiry
+pub fn get_cached_response(url: &str) -> Result<Option<CachedResponse>, RustormyError> {
+    let path = get_cache_dir()?.join(http_cache_file_name(url));
+    read_cached_response(&path, url)
+}
+
+/// Cache a response body together with its `Last-Modified` and `Expires` headers
+pub fn cache_response(entry: &CachedResponse) -> Result<(), RustormyError> {
+    let path = get_cache_dir()?.join(http_cache_file_name(&entry.url));
+    write_cached_response(&path, entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const URL: &str = "https://api.met.no/weatherapi/locationforecast/2.0/compact?lat=1&lon=2";
+
+    fn entry(expires: Option<DateTime<Utc>>) -> CachedResponse {
+        CachedResponse {
+            url: URL.to_string(),
+            last_modified: Some("Tue, 16 Jun 2026 10:00:00 GMT".to_string()),
+            expires,
+            body: r#"{"ok":true}"#.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_file_name_is_stable() {
+        let name = http_cache_file_name(URL);
+        assert_eq!(name, http_cache_file_name(URL));
+        assert!(name.starts_with(HTTP_FILE_PREFIX));
+        assert_ne!(name, http_cache_file_name("https://api.met.no/other"));
+    }
+
+    #[test]
+    fn test_freshness() {
+        let now = Utc::now();
+        assert!(entry(Some(now + Duration::minutes(5))).is_fresh(now));
+        assert!(!entry(Some(now - Duration::minutes(5))).is_fresh(now));
+        assert!(!entry(None).is_fresh(now));
+    }
+
+    #[test]
+    fn test_roundtrip_checks_url() {
+        let path = std::env::temp_dir().join("rustormy_test_http_cache_roundtrip.json");
+        let _ = std::fs::remove_file(&path);
+        assert!(read_cached_response(&path, URL).unwrap().is_none());
+
+        write_cached_response(&path, &entry(None)).unwrap();
+        let cached = read_cached_response(&path, URL).unwrap().unwrap();
+        assert_eq!(cached.body, r#"{"ok":true}"#);
+        assert_eq!(
+            cached.last_modified.as_deref(),
+            Some("Tue, 16 Jun 2026 10:00:00 GMT")
+        );
+        assert!(
+            read_cached_response(&path, "https://other")
+                .unwrap()
+                .is_none()
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
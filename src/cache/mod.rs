@@ -0,0 +1,86 @@
+mod geocoding;
+mod http;
+mod weather;
+
+use crate::errors::RustormyError;
+use chrono::Utc;
+#[cfg(not(test))]
+use directories::ProjectDirs;
+use std::path::PathBuf;
+
+pub use geocoding::{
+    cache_location, get_cached_location, list_locations, prune_locations, remove_location,
+};
+pub use http::{CachedResponse, cache_response, get_cached_response};
+pub use weather::{CachedWeather, cache_weather, get_cached_weather};
+
+#[cfg(not(test))]
+fn get_cache_dir() -> Result<PathBuf, RustormyError> {
+    let proj_dirs = ProjectDirs::from("", "", "rustormy")
+        .ok_or_else(|| RustormyError::CacheFindError("Could not determine cache directory"))?;
+
+    Ok(proj_dirs.cache_dir().to_path_buf())
+}
+
+#[cfg(test)]
+#[allow(clippy::unnecessary_wraps)]
+fn get_cache_dir() -> Result<PathBuf, RustormyError> {
+    Ok(std::env::temp_dir().join("rustormy_test_cache"))
+}
+
+/// FNV-1a hash, stable across builds and platforms unlike `DefaultHasher`
+pub fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub fn clear_cache() -> Result<(), RustormyError> {
+    let cache_dir = get_cache_dir()?;
+
+    if cache_dir.exists() {
+        std::fs::remove_dir_all(cache_dir)?;
+    }
+    Ok(())
+}
+
+/// Summary of what is currently stored in the cache directory
+#[derive(Debug)]
+pub struct CacheStats {
+    pub dir: PathBuf,
+    pub geocoding_entries: usize,
+    pub expired_geocoding_entries: usize,
+    pub weather_entries: usize,
+    pub http_entries: usize,
+    pub total_bytes: u64,
+}
+
+pub fn stats() -> Result<CacheStats, RustormyError> {
+    let dir = get_cache_dir()?;
+    let now = Utc::now();
+    let index = geocoding::GeocodingIndex::load(&dir.join(geocoding::INDEX_FILE_NAME))?;
+
+    let mut stats = CacheStats {
+        dir: dir.clone(),
+        geocoding_entries: index.entries().count(),
+        expired_geocoding_entries: index.entries().filter(|e| e.is_expired(now)).count(),
+        weather_entries: 0,
+        http_entries: 0,
+        total_bytes: 0,
+    };
+
+    if dir.exists() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            stats.total_bytes += entry.metadata()?.len();
+            let name = entry.file_name();
+            let name = name.to_str().unwrap_or_default();
+            if name.starts_with(weather::WEATHER_FILE_PREFIX) {
+                stats.weather_entries += 1;
+            } else if name.starts_with(http::HTTP_FILE_PREFIX) {
+                stats.http_entries += 1;
+            }
+        }
+    }
+    Ok(stats)
+}
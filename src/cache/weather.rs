@@ -0,0 +1,188 @@
+use super::get_cache_dir;
+use crate::config::Config;
+use crate::errors::RustormyError;
+use crate::models::{Provider, Weather};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+
+pub(super) const WEATHER_FILE_PREFIX: &str = "weather_";
+
+/// A weather reading stored in the cache together with the time it was fetched
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedWeather {
+    pub fetched_at: DateTime<Utc>,
+    pub weather: Weather,
+}
+
+impl CachedWeather {
+    pub fn is_fresh(&self, ttl: u64, now: DateTime<Utc>) -> bool {
+        let age = now.signed_duration_since(self.fetched_at);
+        age >= chrono::Duration::zero()
+            && age.num_seconds() < i64::try_from(ttl).unwrap_or(i64::MAX)
+    }
+}
+
+/// Build the weather cache file name from provider, location, units and language.
+/// Coordinates are rounded to two decimals (~1 km) so nearby lookups share an entry.
+fn weather_cache_file_name(provider: Provider, config: &Config) -> String {
+    let location = if let Some((lat, lon)) = config.coordinates() {
+        format!("{lat:.2}_{lon:.2}")
+    } else {
+        config
+            .city()
+            .unwrap_or_default()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect()
+    };
+    format!(
+        "{WEATHER_FILE_PREFIX}{provider:?}_{location}_{:?}_{}.json",
+        config.units(),
+        config.language().code()
+    )
+    .to_lowercase()
+}
+
+fn read_cached_weather(path: &Path) -> Result<Option<CachedWeather>, RustormyError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_reader(File::open(path)?)?))
+}
+
+fn write_cached_weather(
+    path: &Path,
+    weather: &Weather,
+    fetched_at: DateTime<Utc>,
+) -> Result<(), RustormyError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let entry = CachedWeather {
+        fetched_at,
+        weather: Weather {
+            stale_since: None,
+            expires: None,
+            ..weather.clone()
+        },
+    };
+    serde_json::to_writer(File::create(path)?, &entry)?;
+    Ok(())
+}
+
+/// Retrieve the last weather reading cached for this provider and request, regardless of its age
+pub fn get_cached_weather(
+    provider: Provider,
+    config: &Config,
+) -> Result<Option<CachedWeather>, RustormyError> {
+    let path = get_cache_dir()?.join(weather_cache_file_name(provider, config));
+    read_cached_weather(&path)
+}
+
+/// Cache a weather reading fetched from a provider at `fetched_at`
+pub fn cache_weather(
+    provider: Provider,
+    config: &Config,
+    weather: &Weather,
+    fetched_at: DateTime<Utc>,
+) -> Result<(), RustormyError> {
+    let path = get_cache_dir()?.join(weather_cache_file_name(provider, config));
+    write_cached_weather(&path, weather, fetched_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Location, WeatherConditionIcon};
+
+    fn sample_weather() -> Weather {
+        Weather {
+            temperature: 12.5,
+            feels_like: 10.0,
+            humidity: 80,
+            dew_point: 9.1,
+            precipitation: 0.2,
+            pressure: 1008,
+            wind_speed: 4.0,
+            wind_direction: 225,
+            uv_index: Some(1.5),
+            aqi: None,
+            is_day: Some(true),
+            description: "Light rain".to_string(),
+            icon: WeatherConditionIcon::LightShowers,
+            location: Location::new("London".to_string(), 51.5074, -0.1278),
+            stale_since: None,
+            expires: None,
+            provider: None,
+            hourly: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_weather_cache_roundtrip() {
+        let path = std::env::temp_dir().join("rustormy_test_weather_cache_roundtrip.json");
+        let _ = std::fs::remove_file(&path);
+        assert!(read_cached_weather(&path).unwrap().is_none());
+
+        write_cached_weather(&path, &sample_weather(), Utc::now())
+            .expect("Failed to cache weather");
+        let entry = read_cached_weather(&path)
+            .unwrap()
+            .expect("Expected cached weather");
+        assert_eq!(entry.weather.location.name, "London");
+        assert_eq!(entry.weather.description, "Light rain");
+        assert_eq!(entry.weather.uv_index, Some(1.5));
+        assert!(entry.weather.stale_since.is_none());
+        assert!(entry.is_fresh(60, Utc::now()));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_cached_weather_freshness() {
+        let now = Utc::now();
+        let entry = CachedWeather {
+            fetched_at: now - chrono::Duration::seconds(120),
+            weather: sample_weather(),
+        };
+        assert!(entry.is_fresh(600, now));
+        assert!(!entry.is_fresh(60, now));
+        assert!(!entry.is_fresh(0, now));
+    }
+
+    #[test]
+    fn test_weather_cache_file_name() {
+        use crate::config::Cli;
+        use clap::Parser;
+
+        let by_coords = Config::new(Cli::parse_from([
+            "rustormy",
+            "-y",
+            "51.50741",
+            "-x=-0.12784",
+        ]))
+        .unwrap();
+        assert_eq!(
+            weather_cache_file_name(Provider::OpenMeteo, &by_coords),
+            "weather_openmeteo_51.51_-0.13_metric_en.json"
+        );
+
+        let by_city = Config::new(Cli::parse_from([
+            "rustormy",
+            "-c",
+            "New York/NY",
+            "-g",
+            "es",
+            "-u",
+            "imperial",
+        ]))
+        .unwrap();
+        assert_eq!(
+            weather_cache_file_name(Provider::Yr, &by_city),
+            "weather_yr_new_york_ny_imperial_es.json"
+        );
+    }
+}
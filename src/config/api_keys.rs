@@ -1,8 +1,13 @@
 use crate::errors::RustormyError;
 use crate::models::Provider;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::Command;
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+/// API keys of the providers. Each may be the key itself or a reference to it: `env:VARIABLE`,
+/// `file:PATH` or `cmd:COMMAND` (its output), resolved by [`ApiKeys::resolve`].
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct ApiKeys {
     #[serde(default)]
     pub open_weather_map: String,
@@ -16,22 +21,229 @@ pub struct ApiKeys {
     pub tomorrow_io: String,
     #[serde(default)]
     pub open_uv: String,
+    /// Why references couldn't be resolved, by key name
+    #[serde(skip)]
+    pub(super) unresolved: BTreeMap<&'static str, String>,
+}
+
+/// Output of a shell command, without surrounding whitespace
+fn command_output(command: &str) -> Result<String, String> {
+    #[cfg(windows)]
+    let output = Command::new("cmd").args(["/C", command]).output();
+    #[cfg(not(windows))]
+    let output = Command::new("sh").args(["-c", command]).output();
+
+    let output = output.map_err(|error| format!("failed to run the command: {error}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.lines().next() {
+            Some(line) => format!("the command failed ({}): {line}", output.status),
+            None => format!("the command failed ({})", output.status),
+        });
+    }
+    String::from_utf8(output.stdout)
+        .map(|stdout| stdout.trim().to_string())
+        .map_err(|_| "the command output is not valid UTF-8".to_string())
+}
+
+/// The secret `value` refers to, `None` if it is not a reference
+fn resolve_secret(
+    value: &str,
+    env: &impl Fn(&str) -> Option<String>,
+) -> Option<Result<String, String>> {
+    let secret = if let Some(name) = value.strip_prefix("env:") {
+        env(name).ok_or_else(|| format!("environment variable {name} is not set"))
+    } else if let Some(path) = value.strip_prefix("file:") {
+        std::fs::read_to_string(path)
+            .map(|content| content.trim().to_string())
+            .map_err(|error| format!("failed to read {path}: {error}"))
+    } else if let Some(command) = value.strip_prefix("cmd:") {
+        command_output(command)
+    } else {
+        return None;
+    };
+    Some(secret.and_then(|secret| {
+        if secret.is_empty() {
+            Err("the secret is empty".to_string())
+        } else {
+            Ok(secret)
+        }
+    }))
 }
 
 impl ApiKeys {
+    /// Replace the references of the keys of `providers` and `open_uv` with the secrets they
+    /// point to, leaving the others alone so that no command runs for a provider that is not
+    /// used. Keys that can't be resolved are left empty, and the reason is reported when they
+    /// are needed.
+    pub fn resolve(
+        &mut self,
+        providers: impl IntoIterator<Item = Provider>,
+        env: impl Fn(&str) -> Option<String>,
+    ) {
+        let used: Vec<&str> = providers
+            .into_iter()
+            .filter_map(|provider| Some(self.provider_key(provider)?.0))
+            .chain(["open_uv"])
+            .collect();
+        let keys = [
+            ("open_weather_map", &mut self.open_weather_map),
+            ("world_weather_online", &mut self.world_weather_online),
+            ("weather_api", &mut self.weather_api),
+            ("weather_bit", &mut self.weather_bit),
+            ("tomorrow_io", &mut self.tomorrow_io),
+            ("open_uv", &mut self.open_uv),
+        ];
+        for (name, key) in keys {
+            if !used.contains(&name) {
+                continue;
+            }
+            match resolve_secret(key, &env) {
+                Some(Ok(secret)) => *key = secret,
+                Some(Err(error)) => {
+                    self.unresolved
+                        .insert(name, format!("`{name} = \"{key}\"`: {error}"));
+                    key.clear();
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Why the reference of the key `name` couldn't be resolved, if it couldn't
+    pub fn resolve_error(&self, name: &str) -> Option<&str> {
+        self.unresolved.get(name).map(String::as_str)
+    }
+
+    /// Name and value of the key `provider` needs, `None` if it needs none
+    fn provider_key(&self, provider: Provider) -> Option<(&'static str, &str)> {
+        Some(match provider {
+            Provider::OpenMeteo | Provider::Yr => return None,
+            Provider::OpenWeatherMap => ("open_weather_map", &self.open_weather_map),
+            Provider::WorldWeatherOnline => ("world_weather_online", &self.world_weather_online),
+            Provider::WeatherApi => ("weather_api", &self.weather_api),
+            Provider::WeatherBit => ("weather_bit", &self.weather_bit),
+            Provider::TomorrowIo => ("tomorrow_io", &self.tomorrow_io),
+        })
+    }
+
     pub fn validate(&self, provider: Provider) -> Result<(), RustormyError> {
-        let has_api_key = match provider {
-            Provider::OpenMeteo | Provider::Yr => return Ok(()),
-            Provider::OpenWeatherMap => !self.open_weather_map.is_empty(),
-            Provider::WorldWeatherOnline => !self.world_weather_online.is_empty(),
-            Provider::WeatherApi => !self.weather_api.is_empty(),
-            Provider::WeatherBit => !self.weather_bit.is_empty(),
-            Provider::TomorrowIo => !self.tomorrow_io.is_empty(),
+        let Some((name, key)) = self.provider_key(provider) else {
+            return Ok(());
         };
-        if has_api_key {
-            Ok(())
-        } else {
-            Err(RustormyError::MissingApiKey(provider))
+        if !key.is_empty() {
+            return Ok(());
+        }
+        Err(RustormyError::MissingApiKey {
+            provider,
+            reason: self.resolve_error(name).map_or_else(
+                || format!("set `{name}` in [api_keys]"),
+                ToString::to_string,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_PROVIDERS: [Provider; 5] = [
+        Provider::OpenWeatherMap,
+        Provider::WorldWeatherOnline,
+        Provider::WeatherApi,
+        Provider::WeatherBit,
+        Provider::TomorrowIo,
+    ];
+
+    fn env(name: &str) -> Option<String> {
+        (name == "OWM_KEY").then(|| "owm-secret".to_string())
+    }
+
+    #[test]
+    fn test_resolve_references() {
+        let path = std::env::temp_dir().join("rustormy_test_api_key");
+        std::fs::write(&path, "wa-secret\n").unwrap();
+        let mut keys = ApiKeys {
+            open_weather_map: "env:OWM_KEY".to_string(),
+            weather_api: format!("file:{}", path.display()),
+            weather_bit: "plain-key".to_string(),
+            ..Default::default()
+        };
+        #[cfg(not(windows))]
+        {
+            keys.tomorrow_io = "cmd:echo ti-secret".to_string();
         }
+        keys.resolve(ALL_PROVIDERS, env);
+
+        assert_eq!(keys.open_weather_map, "owm-secret");
+        assert_eq!(keys.weather_api, "wa-secret");
+        assert_eq!(keys.weather_bit, "plain-key");
+        #[cfg(not(windows))]
+        assert_eq!(keys.tomorrow_io, "ti-secret");
+        assert!(keys.validate(Provider::OpenWeatherMap).is_ok());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_unresolved_reference_is_reported() {
+        let mut keys = ApiKeys {
+            open_weather_map: "env:OTHER_KEY".to_string(),
+            weather_api: "file:/nonexistent/rustormy/key".to_string(),
+            ..Default::default()
+        };
+        keys.resolve(ALL_PROVIDERS, env);
+
+        assert_eq!(keys.open_weather_map, "");
+        assert_eq!(
+            keys.validate(Provider::OpenWeatherMap)
+                .unwrap_err()
+                .to_string(),
+            "Missing API key for selected weather provider OpenWeatherMap: \
+             `open_weather_map = \"env:OTHER_KEY\"`: environment variable OTHER_KEY is not set"
+        );
+        let error = keys.validate(Provider::WeatherApi).unwrap_err().to_string();
+        assert!(
+            error.contains("`weather_api = \"file:/nonexistent/rustormy/key\"`: failed to read"),
+            "{error}"
+        );
+        assert_eq!(
+            keys.validate(Provider::WeatherBit).unwrap_err().to_string(),
+            "Missing API key for selected weather provider WeatherBit: set `weather_bit` in [api_keys]"
+        );
+    }
+
+    #[test]
+    fn test_only_keys_of_used_providers_are_resolved() {
+        let mut keys = ApiKeys {
+            open_weather_map: "env:OWM_KEY".to_string(),
+            weather_api: "cmd:exit 1".to_string(),
+            open_uv: "env:OWM_KEY".to_string(),
+            ..Default::default()
+        };
+        keys.resolve([Provider::OpenMeteo, Provider::OpenWeatherMap], env);
+
+        assert_eq!(keys.open_weather_map, "owm-secret");
+        assert_eq!(keys.open_uv, "owm-secret");
+        assert_eq!(keys.weather_api, "cmd:exit 1");
+        assert_eq!(keys.resolve_error("weather_api"), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_failed_command_is_reported() {
+        let mut keys = ApiKeys {
+            tomorrow_io: "cmd:echo no such entry >&2; exit 3".to_string(),
+            open_uv: "cmd:true".to_string(),
+            ..Default::default()
+        };
+        keys.resolve(ALL_PROVIDERS, env);
+
+        let error = keys.validate(Provider::TomorrowIo).unwrap_err().to_string();
+        assert!(error.contains(": no such entry"), "{error}");
+        assert_eq!(
+            keys.resolve_error("open_uv"),
+            Some("`open_uv = \"cmd:true\"`: the secret is empty")
+        );
     }
 }
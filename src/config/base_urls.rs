@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Base URL overrides for internal mirrors of the provider APIs.
+/// Only the scheme, host and optional path prefix are replaced, endpoint paths stay the same.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaseUrls {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_meteo: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_meteo_geocoding: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_meteo_air_quality: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_weather_map: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world_weather_online: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weather_api: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weather_bit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tomorrow_io: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_uv: Option<String>,
+}
+
+impl BaseUrls {
+    /// Join an endpoint path onto the overridden base URL, or onto `default_base` if not overridden
+    pub fn endpoint(base_url: Option<&str>, default_base: &str, path: &str) -> String {
+        let base = base_url.unwrap_or(default_base).trim_end_matches('/');
+        format!("{base}{path}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(None, "https://api.open-meteo.com/v1/forecast")]
+    #[test_case(Some("http://mirror.local"), "http://mirror.local/v1/forecast")]
+    #[test_case(Some("http://mirror.local/om/"), "http://mirror.local/om/v1/forecast")]
+    fn test_endpoint(base_url: Option<&str>, expected: &str) {
+        assert_eq!(
+            BaseUrls::endpoint(base_url, "https://api.open-meteo.com", "/v1/forecast"),
+            expected
+        );
+    }
+}
@@ -1,14 +1,18 @@
-use crate::cache::clear_cache;
-use crate::models::{Language, OutputFormat, Provider, TextMode, Units};
-use clap::{ArgAction, Parser};
+use crate::cache::{self, clear_cache};
+use crate::errors::RustormyError;
+use crate::models::{
+    ColorMode, IconStyle, Language, Layout, OutputFormat, Provider, TextMode, ThemeName, Units,
+};
+use clap::{ArgAction, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// City name (required if lat/lon not provided)
+    /// City name (required if lat/lon not provided), repeat to show several locations
     #[arg(short = 'c', long)]
-    pub city: Option<String>,
+    pub city: Vec<String>,
 
     /// Latitude (required if city not provided)
     #[arg(short = 'y', long, allow_negative_numbers = true)]
@@ -18,6 +22,15 @@ pub struct Cli {
     #[arg(short = 'x', long, allow_negative_numbers = true)]
     pub lon: Option<f64>,
 
+    /// Named location from the config file, repeat to show several locations
+    #[arg(
+        short = 'L',
+        long = "at",
+        value_name = "NAME",
+        conflicts_with_all = ["city", "lat", "lon"]
+    )]
+    pub at: Vec<String>,
+
     /// Weather data provider
     #[arg(short = 'p', long, value_enum)]
     pub provider: Option<Provider>,
@@ -38,9 +51,32 @@ pub struct Cli {
     #[arg(long="name", action = ArgAction::SetTrue, alias="show-city-name")]
     pub show_city_name: bool,
 
-    /// Use colors in output
-    #[arg(long="colors", action = ArgAction::SetTrue, alias="use-colors")]
-    pub use_colors: bool,
+    /// When to use colors in output (`--colors` alone means `always`)
+    #[arg(
+        long,
+        value_enum,
+        value_name = "WHEN",
+        num_args = 0..=1,
+        default_missing_value = "always",
+        alias = "use-colors"
+    )]
+    pub colors: Option<ColorMode>,
+
+    /// Icon style
+    #[arg(long, value_enum)]
+    pub icons: Option<IconStyle>,
+
+    /// ASCII art icon pack: `default`, `big`, `minimal` or the name of a pack in the config directory
+    #[arg(long, value_name = "NAME")]
+    pub icon_pack: Option<String>,
+
+    /// Color theme
+    #[arg(long, value_enum)]
+    pub theme: Option<ThemeName>,
+
+    /// Color temperature, wind and UV index by their value
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub gradients: bool,
 
     /// Use degrees for wind direction in output
     #[arg(long="degrees", action = ArgAction::SetTrue)]
@@ -58,10 +94,30 @@ pub struct Cli {
     #[arg(short = 'm', long = "text-mode", value_enum)]
     pub text_mode: Option<TextMode>,
 
+    /// Output template for text output, e.g. "%l: %c %t %w" (overrides text mode)
+    #[arg(short = 't', long)]
+    pub template: Option<String>,
+
+    /// Arrangement of several locations in text output
+    #[arg(long, value_enum)]
+    pub layout: Option<Layout>,
+
     /// Align labels to the right in text output
     #[arg(long="align-right", action = ArgAction::SetTrue)]
     pub align_right: bool,
 
+    /// Show temperature and precipitation charts of the coming hours below text output
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub chart: bool,
+
+    /// Show sparklines of the coming hours in one-line and status bar output
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub sparkline: bool,
+
+    /// Omit the header line of CSV output, e.g. when appending to an existing file
+    #[arg(long = "no-header", action = ArgAction::SetTrue)]
+    pub no_header: bool,
+
     /// Live mode - continuously update weather data every 5 minutes (or specified interval)
     #[arg(short = 'l', long = "live", action = ArgAction::SetTrue, alias="live-mode")]
     pub live_mode: bool,
@@ -79,17 +135,55 @@ pub struct Cli {
     #[arg(long="no-footer", requires = "live_mode", action = ArgAction::SetTrue)]
     pub no_footer: bool,
 
-    /// Disable caching of geocoding results
+    /// Disable caching of geocoding, weather and HTTP responses
     #[arg(long, action = ArgAction::SetTrue)]
     pub no_cache: bool,
 
-    /// Clear cached geocoding results and exit
+    /// Clear cached geocoding and weather results and exit
     #[arg(long, action = ArgAction::SetTrue)]
     pub clear_cache: bool,
 
+    /// Save every HTTP response to this directory (API keys are redacted)
+    #[arg(long, value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Serve HTTP responses from a directory written by `--record` instead of the network
+    #[arg(long, value_name = "DIR")]
+    pub replay: Option<PathBuf>,
+
+    /// Apply the settings of `[profiles.<NAME>]` from the config file (default: `RUSTORMY_PROFILE`)
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
     /// Increase verbosity level (can be used multiple times)
     #[arg(short = 'v', long, action = ArgAction::Count)]
     pub verbose: u8,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Inspect and manage the local cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// List cached geocoding results
+    List,
+    /// Remove cached geocoding results for a city (in every language)
+    Remove { city: String },
+    /// Remove expired geocoding results
+    Prune,
+    /// Show cache location, entry counts and size on disk
+    Stats,
+    /// Clear cached geocoding and weather results
+    Clear,
 }
 
 impl Cli {
@@ -105,6 +199,66 @@ impl Cli {
             std::process::exit(0);
         }
 
+        if let Some(Command::Cache { action }) = &cli.command {
+            if let Err(e) = run_cache_command(action) {
+                eprintln!("Cache command failed: {e}");
+                std::process::exit(1);
+            }
+            std::process::exit(0);
+        }
+
         cli
     }
 }
+
+fn run_cache_command(action: &CacheCommand) -> Result<(), RustormyError> {
+    match action {
+        CacheCommand::List => {
+            let now = chrono::Utc::now();
+            let entries = cache::list_locations()?;
+            if entries.is_empty() {
+                println!("Geocoding cache is empty.");
+            }
+            for entry in entries {
+                println!(
+                    "{} [{}] → {} ({:.4}, {:.4}), cached {}{}",
+                    entry.query,
+                    entry.language.code(),
+                    entry.location.name,
+                    entry.location.latitude,
+                    entry.location.longitude,
+                    entry.cached_at.format("%Y-%m-%d"),
+                    if entry.is_expired(now) {
+                        " (expired)"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+        CacheCommand::Remove { city } => {
+            let removed = cache::remove_location(city)?;
+            println!("Removed {removed} cached entries for \"{city}\".");
+        }
+        CacheCommand::Prune => {
+            let removed = cache::prune_locations()?;
+            println!("Removed {removed} expired entries.");
+        }
+        CacheCommand::Stats => {
+            let stats = cache::stats()?;
+            println!("Directory:         {}", stats.dir.display());
+            println!(
+                "Geocoding entries: {} ({} expired)",
+                stats.geocoding_entries, stats.expired_geocoding_entries
+            );
+            println!("Weather entries:   {}", stats.weather_entries);
+            println!("HTTP responses:    {}", stats.http_entries);
+            println!("Total size:        {} bytes", stats.total_bytes);
+        }
+        CacheCommand::Clear => {
+            clear_cache()?;
+            println!("Cache cleared successfully.");
+        }
+    }
+    Ok(())
+}
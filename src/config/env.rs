@@ -0,0 +1,118 @@
+use crate::config::profiles::PROFILE_ENV;
+use toml::{Table, Value};
+
+/// Prefix of the environment variables that override options of the config file
+const ENV_PREFIX: &str = "RUSTORMY_";
+
+/// Separator of nested keys in variable names, e.g. `RUSTORMY_FORMAT__TEXT_MODE`
+const KEY_SEPARATOR: &str = "__";
+
+/// Keys of the option overridden by the environment variable `name`, `None` if it overrides
+/// none, e.g. `["format", "text_mode"]` for `RUSTORMY_FORMAT__TEXT_MODE`
+pub fn option_path(name: &str) -> Option<Vec<String>> {
+    if name == PROFILE_ENV {
+        return None;
+    }
+    let path: Vec<String> = name
+        .strip_prefix(ENV_PREFIX)?
+        .split(KEY_SEPARATOR)
+        .map(str::to_lowercase)
+        .collect();
+    (!path.iter().any(String::is_empty)).then_some(path)
+}
+
+/// Values a variable may stand for, most specific first: the value read as TOML, e.g. a number
+/// or a list, and the value as a string
+pub fn candidate_values(value: &str) -> Vec<Value> {
+    let raw = Value::String(value.to_string());
+    let parsed = toml::from_str::<Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .filter(|parsed| *parsed != raw);
+    parsed.into_iter().chain([raw]).collect()
+}
+
+/// Whether a configuration could not be read because a variable names no option
+pub fn is_unknown_option(reason: &str) -> bool {
+    reason.starts_with("unknown field")
+}
+
+/// Set the option at `path` in `table`, creating the tables on the way
+pub fn set_option(table: &mut Table, path: &[String], value: Value) {
+    let Some((key, parents)) = path.split_last() else {
+        return;
+    };
+    let mut table = table;
+    for parent in parents {
+        let entry = table
+            .entry(parent.clone())
+            .or_insert_with(|| Value::Table(Table::new()));
+        if !entry.is_table() {
+            *entry = Value::Table(Table::new());
+        }
+        let Some(inner) = entry.as_table_mut() else {
+            return;
+        };
+        table = inner;
+    }
+    table.insert(key.clone(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("RUSTORMY_CITY", Some(&["city"]) ; "top level")]
+    #[test_case("RUSTORMY_LIVE_MODE_INTERVAL", Some(&["live_mode_interval"]) ; "with underscores")]
+    #[test_case("RUSTORMY_FORMAT__TEXT_MODE", Some(&["format", "text_mode"]) ; "nested")]
+    #[test_case("RUSTORMY_API_KEYS__OPEN_WEATHER_MAP", Some(&["api_keys", "open_weather_map"]) ; "api key")]
+    #[test_case("RUSTORMY_PROFILE", None ; "profile")]
+    #[test_case("RUSTORMY_FORMAT__", None ; "empty key")]
+    #[test_case("HOME", None ; "other variable")]
+    fn test_option_path(name: &str, expected: Option<&[&str]>) {
+        assert_eq!(
+            option_path(name),
+            expected.map(|path| path.iter().map(ToString::to_string).collect())
+        );
+    }
+
+    #[test_case("600", &[Value::Integer(600), Value::String("600".to_string())] ; "number")]
+    #[test_case("London", &[Value::String("London".to_string())] ; "bare string")]
+    #[test_case(
+        "\"London\"",
+        &[Value::String("London".to_string()), Value::String("\"London\"".to_string())] ;
+        "quoted string"
+    )]
+    #[test_case(
+        "[\"om\", \"yr\"]",
+        &[
+            Value::Array(vec![Value::String("om".to_string()), Value::String("yr".to_string())]),
+            Value::String("[\"om\", \"yr\"]".to_string()),
+        ] ;
+        "list"
+    )]
+    fn test_candidate_values(value: &str, expected: &[Value]) {
+        assert_eq!(candidate_values(value), expected);
+    }
+
+    #[test_case("unknown field `citty`, expected one of `city`, `lat`", true ; "unknown")]
+    #[test_case("invalid type: string \"often\", expected u64", false ; "invalid value")]
+    fn test_is_unknown_option(reason: &str, expected: bool) {
+        assert_eq!(is_unknown_option(reason), expected);
+    }
+
+    #[test]
+    fn test_set_option() {
+        let mut table: Table = toml::from_str("verbose = 1\nformat = 2").unwrap();
+        set_option(
+            &mut table,
+            &["format".to_string(), "text_mode".to_string()],
+            Value::String("compact".to_string()),
+        );
+        set_option(&mut table, &["verbose".to_string()], Value::Integer(3));
+        let expected: Table =
+            toml::from_str("verbose = 3\n[format]\ntext_mode = \"compact\"").unwrap();
+        assert_eq!(table, expected);
+    }
+}
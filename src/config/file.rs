@@ -1,14 +1,22 @@
+use crate::config::env::{candidate_values, is_unknown_option, option_path, set_option};
 use crate::config::legacy::LegacyConfig;
-use crate::config::{ApiKeys, Cli};
+use crate::config::profiles::{merge_table, normalize_aliases, profile_name};
+use crate::config::{ApiKeys, BaseUrls, Cli, Locations, NamedLocation, Shown};
+use crate::display::icon_pack::IconPack;
+use crate::display::template::{Field, Template};
+use crate::environment;
 use crate::errors::RustormyError;
-use crate::models::{ColorTheme, Language, OutputFormat, Provider, TextMode, Units};
+use crate::models::{
+    ColorMode, ColorTheme, ColorThemeOverrides, IconStyle, Language, Layout, OutputFormat,
+    Provider, TextField, TextMode, ThemeName, Units,
+};
+use crate::weather::http::HttpMode;
 #[cfg(not(test))]
 use directories::ProjectDirs;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
-#[cfg(not(test))]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const CONFIG_FILE_HEADER: &str = "# Rustormy Configuration File
 # This file is in TOML format. See https://toml.io/ for details
@@ -17,14 +25,20 @@ const CONFIG_FILE_HEADER: &str = "# Rustormy Configuration File
 ";
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct FormatterConfig {
     #[serde(default)]
     pub output_format: OutputFormat,
     #[serde(default)]
     pub text_mode: TextMode,
-    #[serde(default)]
-    pub use_colors: bool,
+    /// When to color text output, `auto`, `always` or `never`
+    #[serde(
+        default,
+        alias = "use_colors",
+        deserialize_with = "deserialize_color_mode"
+    )]
+    pub colors: ColorMode,
     #[serde(default)]
     pub show_city_name: bool,
     #[serde(default)]
@@ -35,12 +49,181 @@ pub struct FormatterConfig {
     pub units: Units,
     #[serde(default)]
     pub language: Language,
+    /// Built-in color theme
+    #[serde(default)]
+    pub theme: ThemeName,
+    /// Colors replacing those of the theme, ANSI color names or `#rrggbb`
+    #[serde(default, skip_serializing_if = "ColorThemeOverrides::is_empty")]
+    pub color_theme: ColorThemeOverrides,
+    /// Color temperature, wind and UV index by their value instead of the theme colors
+    #[serde(default)]
+    pub gradients: bool,
+    /// Icons: `ascii` art, `emoji`, `nerdfont` glyphs or `none`
+    #[serde(default)]
+    pub icons: IconStyle,
+    /// ASCII art of `icons = "ascii"`: `default`, `big`, `minimal` or a pack in the `icons` directory
+    #[serde(default = "default_icon_pack")]
+    pub icon_pack: String,
+    /// Output template for text output, e.g. `"%l: %c %t %w"` (overrides `text_mode`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Lines shown in full and compact text modes, in order
+    #[serde(default = "default_fields")]
+    pub fields: Vec<TextField>,
+    /// Fit full and compact text output to the terminal width
+    #[serde(default = "default_responsive")]
+    pub responsive: bool,
+    /// Show temperature and precipitation charts of the coming hours below text output
+    #[serde(default)]
+    pub chart: bool,
+    /// Hours shown in charts, 1 to 48
+    #[serde(default = "default_chart_hours")]
+    pub chart_hours: u8,
+    /// Show sparklines of the coming hours in one-line and status bar output
     #[serde(default)]
-    pub color_theme: ColorTheme,
+    pub sparkline: bool,
+    /// Hours shown in sparklines, 1 to 48
+    #[serde(default = "default_sparkline_hours")]
+    pub sparkline_hours: u8,
+    /// Print the header line before CSV rows
+    #[serde(default = "default_csv_header")]
+    pub csv_header: bool,
+    /// Arrangement of several locations: `stacked`, `columns` or `table`
+    #[serde(default)]
+    pub layout: Layout,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        Self {
+            output_format: OutputFormat::default(),
+            text_mode: TextMode::default(),
+            colors: ColorMode::default(),
+            show_city_name: false,
+            align_right: false,
+            wind_in_degrees: false,
+            units: Units::default(),
+            language: Language::default(),
+            theme: ThemeName::default(),
+            color_theme: ColorThemeOverrides::default(),
+            gradients: false,
+            icons: IconStyle::default(),
+            icon_pack: default_icon_pack(),
+            template: None,
+            fields: default_fields(),
+            responsive: true,
+            chart: false,
+            chart_hours: default_chart_hours(),
+            sparkline: false,
+            sparkline_hours: default_sparkline_hours(),
+            csv_header: true,
+            layout: Layout::default(),
+        }
+    }
+}
+
+impl FormatterConfig {
+    /// Whether text written to stdout is colored
+    pub fn use_colors(&self) -> bool {
+        self.colors.for_stdout()
+    }
+
+    /// The named theme with the colors of `[format.color_theme]` applied
+    pub fn color_theme(&self) -> ColorTheme {
+        // Config files written before themes existed contain every color of the simple theme,
+        // except for the sunrise and sunset colors added later
+        let legacy = ColorThemeOverrides {
+            sunrise: None,
+            sunset: None,
+            ..ColorThemeOverrides::from(ColorTheme::simple())
+        };
+        if self.color_theme == legacy {
+            return ColorTheme::named(self.theme);
+        }
+        ColorTheme::named(self.theme).with_overrides(&self.color_theme)
+    }
+
+    /// Hours of hourly forecast shown by charts and sparklines, `None` if there are none
+    pub fn forecast_hours(&self) -> Option<u8> {
+        let template_trends = self
+            .template
+            .as_deref()
+            .and_then(|template| Template::parse(template).ok())
+            .is_some_and(|template| {
+                template.contains_any(&[Field::TemperatureTrend, Field::PrecipitationTrend])
+            });
+        let sparkline = (self.sparkline || template_trends).then_some(self.sparkline_hours);
+        let chart = self.chart.then_some(self.chart_hours);
+        sparkline.max(chart)
+    }
+}
+
+/// Color mode, or the `use_colors` bool of older config files
+fn deserialize_color_mode<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ColorMode, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ColorModeOrBool {
+        Mode(ColorMode),
+        Bool(bool),
+    }
+    Ok(match ColorModeOrBool::deserialize(deserializer)? {
+        ColorModeOrBool::Mode(mode) => mode,
+        ColorModeOrBool::Bool(true) => ColorMode::Always,
+        ColorModeOrBool::Bool(false) => ColorMode::Never,
+    })
+}
+
+/// Directory of `config.toml` and the `icons` directory of icon packs
+#[cfg(not(test))]
+pub fn config_dir() -> Result<PathBuf, RustormyError> {
+    let proj_dirs = ProjectDirs::from("", "", "rustormy")
+        .ok_or_else(|| RustormyError::ConfigNotFound("Could not determine config directory"))?;
+
+    Ok(proj_dirs.config_dir().to_path_buf())
+}
+
+#[cfg(test)]
+#[allow(clippy::unnecessary_wraps)]
+pub fn config_dir() -> Result<PathBuf, RustormyError> {
+    Ok(std::env::temp_dir().join("rustormy_test_config"))
+}
+
+fn default_icon_pack() -> String {
+    "default".to_string()
+}
+
+fn default_responsive() -> bool {
+    true
+}
+
+fn default_chart_hours() -> u8 {
+    24
+}
+
+fn default_sparkline_hours() -> u8 {
+    12
+}
+
+fn default_csv_header() -> bool {
+    true
+}
+
+fn default_fields() -> Vec<TextField> {
+    vec![
+        TextField::Location,
+        TextField::Condition,
+        TextField::Temperature,
+        TextField::Wind,
+        TextField::Precipitation,
+        TextField::Pressure,
+        TextField::Humidity,
+    ]
 }
 
 #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
     /// List of providers to try in order (if the first fails, try the next, etc.)
@@ -63,6 +246,23 @@ pub struct Config {
     #[serde(default)]
     lon: Option<f64>,
 
+    /// Cities shown together instead of `city` or `lat`/`lon`, e.g. `["London", "Lisbon", "Tokyo"]`,
+    /// or locations saved under a name as `[locations.<name>]` tables, selected with `--at <name>`
+    #[serde(default, skip_serializing_if = "Locations::is_empty")]
+    locations: Locations,
+
+    /// Name of the location in `[locations]` used when none is given on the command line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_location: Option<String>,
+
+    /// Names of the locations in `[locations]` selected with `--at` or `default_location`
+    #[serde(skip)]
+    at: Vec<String>,
+
+    /// Cities or named locations shown together (set from `locations` or the command line)
+    #[serde(skip)]
+    shown: Vec<Shown>,
+
     /// Configuration for output formatting
     #[serde(default)]
     format: FormatterConfig,
@@ -84,13 +284,68 @@ pub struct Config {
     #[serde(default)]
     use_geocoding_cache: bool,
 
+    /// Number of days a cached geocoding result stays valid (default: 30, `0` = never expire)
+    #[serde(default = "default_geocoding_cache_ttl_days")]
+    geocoding_cache_ttl_days: u64,
+
+    /// Use weather cache (`true` or `false`)
+    /// (if enabled, weather results are reused for `weather_cache_ttl` seconds instead of calling the API again)
+    #[serde(default)]
+    use_weather_cache: bool,
+
+    /// Weather cache time-to-live in seconds (default: 600)
+    #[serde(default = "default_weather_cache_ttl")]
+    weather_cache_ttl: u64, // in seconds, default to 600 (10 minutes)
+
+    /// Show the last cached weather, marked as stale, when every provider fails (`true` or `false`)
+    #[serde(default)]
+    serve_stale_weather: bool,
+
     /// Verbosity level of output (0 = errors, 1 = warnings, 2 = info, 3 = debug)
     #[serde(default)]
     verbose: u8,
 
-    /// API HTTP client timeout in seconds
+    /// Timeout for establishing a connection to the API in seconds
     #[serde(default = "default_connect_timeout")]
     connect_timeout: u64, // in seconds, default to 10
+
+    /// Timeout for reading the API response in seconds
+    #[serde(default = "default_read_timeout")]
+    read_timeout: u64, // in seconds, default to 30
+
+    /// Proxy URL for all requests (`http://`, `https://`, `socks5://` or `socks5h://`)
+    /// (if not set, `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY` environment variables are used)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proxy: Option<String>,
+
+    /// Comma-separated hosts that bypass `proxy` (if not set, `NO_PROXY` environment variable is used)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    no_proxy: Option<String>,
+
+    /// Extra PEM files with root CA certificates to trust in addition to the system ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ca_certificates: Vec<PathBuf>,
+
+    /// Base URL overrides for provider APIs (e.g. internal mirrors)
+    #[serde(default)]
+    base_urls: BaseUrls,
+
+    /// Settings applied on top of the others with `--profile <name>`, as `[profiles.<name>]` tables
+    /// of any of the options above, e.g. `[profiles.bar.format]` with `text_mode = "one_line"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    profiles: BTreeMap<String, toml::Table>,
+
+    /// Record or replay HTTP responses (set from `--record` / `--replay` only)
+    #[serde(skip)]
+    http_mode: HttpMode,
+
+    /// Reuse HTTP responses cached on disk until they expire (off with `--no-cache` only)
+    #[serde(skip, default = "default_use_http_cache")]
+    use_http_cache: bool,
+
+    /// `RUSTORMY_*` environment variables that set no option and were skipped
+    #[serde(skip)]
+    unknown_env_vars: Vec<String>,
 }
 
 fn default_live_mode_interval() -> u64 {
@@ -99,9 +354,21 @@ fn default_live_mode_interval() -> u64 {
 fn default_live_mode_footer() -> bool {
     true
 }
+fn default_use_http_cache() -> bool {
+    true
+}
 fn default_connect_timeout() -> u64 {
     10
 }
+fn default_read_timeout() -> u64 {
+    30
+}
+fn default_geocoding_cache_ttl_days() -> u64 {
+    30
+}
+fn default_weather_cache_ttl() -> u64 {
+    600
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -111,13 +378,30 @@ impl Default for Config {
             city: None,
             lat: None,
             lon: None,
+            locations: Locations::default(),
+            default_location: None,
+            at: Vec::new(),
+            shown: Vec::new(),
             format: FormatterConfig::default(),
             live_mode: false,
             live_mode_interval: default_live_mode_interval(),
             live_mode_footer: default_live_mode_footer(),
             use_geocoding_cache: false,
+            geocoding_cache_ttl_days: default_geocoding_cache_ttl_days(),
+            use_weather_cache: false,
+            weather_cache_ttl: default_weather_cache_ttl(),
+            serve_stale_weather: false,
             verbose: 0,
             connect_timeout: default_connect_timeout(),
+            read_timeout: default_read_timeout(),
+            proxy: None,
+            no_proxy: None,
+            ca_certificates: Vec::new(),
+            base_urls: BaseUrls::default(),
+            profiles: BTreeMap::new(),
+            http_mode: HttpMode::default(),
+            use_http_cache: default_use_http_cache(),
+            unknown_env_vars: Vec::new(),
         }
     }
 }
@@ -132,7 +416,14 @@ impl Config {
         };
         #[cfg(test)]
         let mut config = Self::default();
+        if let Some(name) = profile_name(cli.profile.as_deref(), environment::var) {
+            config = config.with_profile(&name)?;
+        }
+        config = config.with_env(environment::vars())?;
         config.merge_cli(cli)?;
+        config
+            .api_keys
+            .resolve(config.used_providers(), environment::var);
         config.validate()?;
         Ok(config)
     }
@@ -162,13 +453,7 @@ impl Config {
 
     #[cfg(not(test))]
     fn get_config_path() -> Result<PathBuf, RustormyError> {
-        let proj_dirs = ProjectDirs::from("", "", "rustormy")
-            .ok_or_else(|| RustormyError::ConfigNotFound("Could not determine config directory"))?;
-
-        let config_dir = proj_dirs.config_dir();
-        let config_path = config_dir.join("config.toml");
-
-        Ok(config_path)
+        Ok(config_dir()?.join("config.toml"))
     }
 
     fn create_default_config_file(config_path: &Path) -> Result<Self, RustormyError> {
@@ -190,14 +475,115 @@ impl Config {
         Ok(())
     }
 
+    /// This configuration with the settings of `[profiles.<name>]` on top
+    fn with_profile(self, name: &str) -> Result<Self, RustormyError> {
+        let Some(profile) = self.profiles.get(name) else {
+            return Err(RustormyError::UnknownProfile {
+                name: name.to_string(),
+                known: if self.profiles.is_empty() {
+                    "none, add them as [profiles.<name>]".to_string()
+                } else {
+                    self.profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+                },
+            });
+        };
+        let invalid = |reason: String| RustormyError::InvalidProfile {
+            name: name.to_string(),
+            reason,
+        };
+        if profile.contains_key("profiles") {
+            return Err(invalid("profiles can't contain other profiles".to_string()));
+        }
+        let mut table = toml::Table::try_from(&self)?;
+        merge_table(&mut table, profile.clone());
+        normalize_aliases(&mut table);
+        table
+            .try_into()
+            .map_err(|error: toml::de::Error| invalid(error.message().to_string()))
+    }
+
+    /// This configuration with the options set by `RUSTORMY_*` environment variables on top
+    fn with_env(self, vars: Vec<(String, String)>) -> Result<Self, RustormyError> {
+        let mut vars: Vec<(Vec<String>, String, String)> = vars
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .filter_map(|(name, value)| Some((option_path(&name)?, name, value)))
+            .collect();
+        if vars.is_empty() {
+            return Ok(self);
+        }
+        // Whole tables before the options in them
+        vars.sort();
+
+        let mut table = toml::Table::try_from(&self)?;
+        let mut config = self;
+        let mut unknown = Vec::new();
+        for (path, name, value) in vars {
+            let mut reason = String::new();
+            let applied = candidate_values(&value).into_iter().any(|candidate| {
+                let mut attempt = table.clone();
+                set_option(&mut attempt, &path, candidate);
+                normalize_aliases(&mut attempt);
+                match attempt.clone().try_into::<Self>() {
+                    Ok(parsed) => {
+                        (table, config) = (attempt, parsed);
+                        true
+                    }
+                    Err(error) => {
+                        // The error of the most specific reading is the most helpful one
+                        if reason.is_empty() {
+                            reason = error.message().to_string();
+                        }
+                        false
+                    }
+                }
+            });
+            if applied {
+                continue;
+            }
+            // Other programs may use the prefix too, only invalid values of known options fail
+            if is_unknown_option(&reason) {
+                unknown.push(name);
+            } else {
+                return Err(RustormyError::InvalidEnvironmentVariable { name, reason });
+            }
+        }
+        config.unknown_env_vars = unknown;
+        Ok(config)
+    }
+
     #[cfg(test)]
     pub fn merge_cli_test(mut self, cli: Cli) -> Result<Self, RustormyError> {
         self.merge_cli(cli)?;
         Ok(self)
     }
 
-    fn merge_cli(&mut self, cli: Cli) -> Result<(), RustormyError> {
-        if let Some(city) = cli.city {
+    /// Location from the command line, `default_location` or `locations`
+    fn merge_cli_location(&mut self, cli: &mut Cli) {
+        // Command-line options take precedence over the settings of named locations
+        for location in self.locations.named_mut() {
+            if let Some(provider) = cli.provider {
+                location.providers = vec![provider];
+            }
+            if let Some(units) = cli.units {
+                location.units = Some(units);
+            }
+            if let Some(language) = cli.language {
+                location.language = Some(language);
+            }
+        }
+
+        // Locations given on the command line replace the configured ones
+        let cli_location =
+            !cli.city.is_empty() || cli.lat.is_some() || cli.lon.is_some() || !cli.at.is_empty();
+        if !cli.at.is_empty() {
+            self.at = std::mem::take(&mut cli.at);
+        } else if !cli_location {
+            self.at = self.default_location.iter().cloned().collect();
+        }
+        if cli.city.len() > 1 {
+            self.shown = cli.city.drain(..).map(Shown::City).collect();
+        } else if let Some(city) = cli.city.pop() {
             self.city = Some(city);
         }
         if let Some(lat) = cli.lat {
@@ -206,6 +592,24 @@ impl Config {
         if let Some(lon) = cli.lon {
             self.lon = Some(lon);
         }
+        if self.at.len() > 1 {
+            self.shown = self.at.iter().cloned().map(Shown::Named).collect();
+        } else if let Some(name) = self.at.first() {
+            // An unknown name is reported by `validate`
+            if let Ok(location) = self.locations.get(name) {
+                self.apply_location(&location.clone());
+            }
+        } else if !cli_location && let Locations::List(list) = &self.locations {
+            match list.as_slice() {
+                [city] => self.city = Some(city.clone()),
+                [] => {}
+                _ => self.shown = list.iter().cloned().map(Shown::City).collect(),
+            }
+        }
+    }
+
+    fn merge_cli(&mut self, mut cli: Cli) -> Result<(), RustormyError> {
+        self.merge_cli_location(&mut cli);
         if let Some(provider) = cli.provider {
             self.providers = vec![provider];
         }
@@ -221,16 +625,48 @@ impl Config {
         if let Some(live_mode_interval) = cli.live_mode_interval {
             self.live_mode_interval = live_mode_interval;
         }
+        if let Some(template) = cli.template {
+            self.format.template = Some(template);
+        }
+        if let Some(layout) = cli.layout {
+            self.format.layout = layout;
+        }
 
         self.format.show_city_name |= cli.show_city_name;
-        self.format.use_colors |= cli.use_colors;
+        if let Some(icons) = cli.icons {
+            self.format.icons = icons;
+        }
+        if let Some(icon_pack) = cli.icon_pack {
+            self.format.icon_pack = icon_pack;
+        }
+        if let Some(theme) = cli.theme {
+            self.format.theme = theme;
+        }
+
+        if let Some(colors) = cli.colors {
+            self.format.colors = colors;
+        }
+        self.format.gradients |= cli.gradients;
         self.format.wind_in_degrees |= cli.use_degrees_for_wind;
         self.format.align_right |= cli.align_right;
+        self.format.chart |= cli.chart;
+        self.format.sparkline |= cli.sparkline;
+        self.format.csv_header &= !cli.no_header;
         if cli.no_footer {
             self.live_mode_footer = false;
         }
         self.live_mode |= cli.live_mode;
-        self.use_geocoding_cache &= !cli.no_cache;
+        if let Some(dir) = cli.record {
+            self.http_mode = HttpMode::Record(dir);
+        } else if let Some(dir) = cli.replay {
+            self.http_mode = HttpMode::Replay(dir);
+        }
+        // Caches would hide requests from recordings and make replays depend on local state
+        let no_cache = cli.no_cache || self.http_mode != HttpMode::Live;
+        self.use_geocoding_cache &= !no_cache;
+        self.use_weather_cache &= !no_cache;
+        self.serve_stale_weather &= !no_cache;
+        self.use_http_cache &= !no_cache;
         if cli.verbose > 0 {
             self.verbose = cli.verbose;
         }
@@ -255,14 +691,69 @@ impl Config {
         Ok(())
     }
 
+    /// Use a named location with its settings
+    fn apply_location(&mut self, location: &NamedLocation) {
+        self.city.clone_from(&location.city);
+        self.lat = location.lat;
+        self.lon = location.lon;
+        if !location.providers.is_empty() {
+            self.providers.clone_from(&location.providers);
+        }
+        if let Some(units) = location.units {
+            self.format.units = units;
+        }
+        if let Some(language) = location.language {
+            self.format.language = language;
+        }
+    }
+
     pub fn validate(&self) -> Result<(), RustormyError> {
-        // Check if either city or coordinates are provided
-        if self.city.is_none() && (self.lat.is_none() || self.lon.is_none()) {
+        // Check that every profile can be applied, not only the selected one
+        for name in self.profiles.keys() {
+            self.clone().with_profile(name)?;
+        }
+
+        // Check that selected named locations exist, listing the known ones if not
+        for name in self.at.iter().chain(&self.default_location) {
+            self.locations.get(name)?;
+        }
+
+        for location in self.locations.named() {
+            match (&location.city, location.lat, location.lon) {
+                (None, None, _) | (None, _, None) => {
+                    return Err(RustormyError::InvalidConfiguration(
+                        "Every location in `[locations]` needs `city` or `lat` and `lon`",
+                    ));
+                }
+                (_, Some(lat), Some(lon))
+                    if !((-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)) =>
+                {
+                    return Err(RustormyError::InvalidCoordinates { lat, lon });
+                }
+                _ => {}
+            }
+        }
+
+        // Check if either city, coordinates or a list of locations are provided
+        let has_locations = !self.shown.is_empty();
+        if self.city.is_none() && (self.lat.is_none() || self.lon.is_none()) && !has_locations {
             return Err(RustormyError::NoLocationProvided);
         }
 
+        if has_locations && self.live_mode {
+            return Err(RustormyError::InvalidConfiguration(
+                "Live mode shows a single location, not `locations`",
+            ));
+        }
+
+        if has_locations && !self.format.output_format.shows_many_locations() {
+            return Err(RustormyError::InvalidConfiguration(
+                "Status bar, HTML and SVG output show a single location, not `locations`",
+            ));
+        }
+
         // Check if city name is to be shown but no city is provided
-        if self.city.is_none() && self.format.show_city_name {
+        if self.city.is_none() && !has_locations && self.format.show_city_name {
             return Err(RustormyError::InvalidConfiguration(
                 "Cannot show city name when no city is provided",
             ));
@@ -276,8 +767,35 @@ impl Config {
         }
 
         // Check if API key is provided for every provider that requires one
-        for provider in &self.providers {
-            self.api_keys.validate(*provider)?;
+        for provider in self.used_providers() {
+            self.api_keys.validate(provider)?;
+        }
+
+        if self.format.fields.is_empty() {
+            return Err(RustormyError::InvalidConfiguration(
+                "At least one field must be specified in `format.fields`",
+            ));
+        }
+
+        // Check that the icon pack exists and is valid when its art is shown
+        if self.format.icons == IconStyle::Ascii {
+            IconPack::load(&self.format.icon_pack)?;
+        }
+
+        if !(1..=48).contains(&self.format.chart_hours) {
+            return Err(RustormyError::InvalidConfiguration(
+                "`format.chart_hours` must be between 1 and 48",
+            ));
+        }
+        if !(1..=48).contains(&self.format.sparkline_hours) {
+            return Err(RustormyError::InvalidConfiguration(
+                "`format.sparkline_hours` must be between 1 and 48",
+            ));
+        }
+
+        // Check that the output template parses
+        if let Some(template) = &self.format.template {
+            Template::parse(template)?;
         }
 
         // Validate coordinates if provided
@@ -319,6 +837,44 @@ impl Config {
         self.city.as_deref()
     }
 
+    /// Providers that may be asked for weather: `providers` and those of the selected named
+    /// locations
+    fn used_providers(&self) -> Vec<Provider> {
+        let named = self
+            .at
+            .iter()
+            .filter_map(|name| self.locations.get(name).ok())
+            .flat_map(|location| location.providers.iter().copied());
+        self.providers.iter().copied().chain(named).collect()
+    }
+
+    /// Cities or named locations shown together, empty when a single location is shown
+    pub fn locations(&self) -> &[Shown] {
+        &self.shown
+    }
+
+    /// The configuration of one of `locations`, with its name shown in text output
+    pub fn for_location(&self, location: &Shown) -> Self {
+        let mut config = self.clone();
+        match location {
+            // An unknown name is reported by `validate`
+            Shown::Named(name) => {
+                if let Ok(location) = self.locations.get(name) {
+                    config.apply_location(location);
+                }
+            }
+            Shown::City(city) => {
+                config.city = Some(city.clone());
+                config.lat = None;
+                config.lon = None;
+            }
+        }
+        config.at = Vec::new();
+        config.shown = Vec::new();
+        config.format.show_city_name = true;
+        config
+    }
+
     pub fn coordinates(&self) -> Option<(f64, f64)> {
         match (self.lat, self.lon) {
             (Some(lat), Some(lon)) => Some((lat, lon)),
@@ -373,6 +929,26 @@ impl Config {
         self.use_geocoding_cache
     }
 
+    pub fn geocoding_cache_ttl_days(&self) -> u64 {
+        self.geocoding_cache_ttl_days
+    }
+
+    pub fn use_weather_cache(&self) -> bool {
+        self.use_weather_cache
+    }
+
+    pub fn weather_cache_ttl(&self) -> u64 {
+        self.weather_cache_ttl
+    }
+
+    pub fn serve_stale_weather(&self) -> bool {
+        self.serve_stale_weather
+    }
+
+    pub fn use_http_cache(&self) -> bool {
+        self.use_http_cache
+    }
+
     pub fn verbose(&self) -> u8 {
         self.verbose
     }
@@ -384,6 +960,39 @@ impl Config {
             self.connect_timeout
         }
     }
+
+    pub fn read_timeout(&self) -> u64 {
+        if self.read_timeout == 0 {
+            default_read_timeout()
+        } else {
+            self.read_timeout
+        }
+    }
+
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+
+    pub fn no_proxy(&self) -> Option<&str> {
+        self.no_proxy.as_deref()
+    }
+
+    pub fn ca_certificates(&self) -> &[PathBuf] {
+        &self.ca_certificates
+    }
+
+    pub fn base_urls(&self) -> &BaseUrls {
+        &self.base_urls
+    }
+
+    pub fn http_mode(&self) -> &HttpMode {
+        &self.http_mode
+    }
+
+    /// `RUSTORMY_*` environment variables that were skipped because they set no option
+    pub fn unknown_env_vars(&self) -> &[String] {
+        &self.unknown_env_vars
+    }
 }
 
 impl From<LegacyConfig> for Config {
@@ -416,9 +1025,7 @@ impl From<LegacyConfig> for Config {
                 },
                 world_weather_online: value.api_key_wwo,
                 weather_api: value.api_key_wa,
-                weather_bit: String::default(),
-                tomorrow_io: String::default(),
-                open_uv: String::default(),
+                ..ApiKeys::default()
             }
         };
         let format = if let Some(format) = value.format {
@@ -430,10 +1037,27 @@ impl From<LegacyConfig> for Config {
                 output_format: value.output_format,
                 language: value.language,
                 show_city_name: value.show_city_name,
-                use_colors: value.use_colors,
+                colors: if value.use_colors {
+                    ColorMode::Always
+                } else {
+                    ColorMode::Never
+                },
                 wind_in_degrees: value.wind_in_degrees,
                 align_right: value.align_right,
-                color_theme: ColorTheme::default(),
+                theme: ThemeName::default(),
+                color_theme: ColorThemeOverrides::default(),
+                gradients: false,
+                icons: IconStyle::default(),
+                icon_pack: default_icon_pack(),
+                template: None,
+                fields: default_fields(),
+                responsive: true,
+                chart: false,
+                chart_hours: default_chart_hours(),
+                sparkline: false,
+                sparkline_hours: default_sparkline_hours(),
+                csv_header: true,
+                layout: Layout::default(),
             }
         };
 
@@ -443,13 +1067,30 @@ impl From<LegacyConfig> for Config {
             city: value.city,
             lat: value.lat,
             lon: value.lon,
+            locations: Locations::default(),
+            default_location: None,
+            at: Vec::new(),
+            shown: Vec::new(),
             format,
             live_mode: value.live_mode,
             live_mode_interval: value.live_mode_interval,
             live_mode_footer: default_live_mode_footer(),
             use_geocoding_cache: value.use_geocoding_cache,
+            geocoding_cache_ttl_days: default_geocoding_cache_ttl_days(),
+            use_weather_cache: false,
+            weather_cache_ttl: default_weather_cache_ttl(),
+            serve_stale_weather: false,
             verbose: value.verbose,
             connect_timeout: value.connect_timeout,
+            read_timeout: default_read_timeout(),
+            proxy: None,
+            no_proxy: None,
+            ca_certificates: Vec::new(),
+            base_urls: BaseUrls::default(),
+            profiles: BTreeMap::new(),
+            http_mode: HttpMode::default(),
+            use_http_cache: default_use_http_cache(),
+            unknown_env_vars: Vec::new(),
         }
     }
 }
@@ -457,6 +1098,8 @@ impl From<LegacyConfig> for Config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{AnsiColor, Color};
+    use test_case::test_case;
 
     #[test]
     fn test_validate_no_location() {
@@ -486,6 +1129,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_empty_fields() {
+        let config = Config {
+            city: Some("TestCity".to_string()),
+            format: FormatterConfig {
+                fields: Vec::new(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = config.validate();
+        assert!(
+            matches!(result, Err(RustormyError::InvalidConfiguration(_))),
+            "Expected InvalidConfiguration error got {result:?}",
+        );
+    }
+
     #[test]
     fn test_validate_missing_api_key_owm() {
         let config = Config {
@@ -497,7 +1157,10 @@ mod tests {
         assert!(
             matches!(
                 result,
-                Err(RustormyError::MissingApiKey(Provider::OpenWeatherMap))
+                Err(RustormyError::MissingApiKey {
+                    provider: Provider::OpenWeatherMap,
+                    ..
+                })
             ),
             "Expected MissingApiKey error got {result:?}",
         );
@@ -514,7 +1177,10 @@ mod tests {
         assert!(
             matches!(
                 result,
-                Err(RustormyError::MissingApiKey(Provider::WorldWeatherOnline))
+                Err(RustormyError::MissingApiKey {
+                    provider: Provider::WorldWeatherOnline,
+                    ..
+                })
             ),
             "Expected MissingApiKey error got {result:?}",
         );
@@ -531,7 +1197,10 @@ mod tests {
         assert!(
             matches!(
                 result,
-                Err(RustormyError::MissingApiKey(Provider::WeatherApi))
+                Err(RustormyError::MissingApiKey {
+                    provider: Provider::WeatherApi,
+                    ..
+                })
             ),
             "Expected MissingApiKey error got {result:?}",
         );
@@ -548,7 +1217,10 @@ mod tests {
         assert!(
             matches!(
                 result,
-                Err(RustormyError::MissingApiKey(Provider::WeatherBit))
+                Err(RustormyError::MissingApiKey {
+                    provider: Provider::WeatherBit,
+                    ..
+                })
             ),
             "Expected MissingApiKey error got {result:?}",
         );
@@ -796,6 +1468,71 @@ live_mode_footer = false
         assert!(!config.live_mode_footer());
     }
 
+    #[test]
+    fn test_color_theme_overrides_named_theme() {
+        let format: FormatterConfig = toml::from_str(
+            r##"
+            theme = "nord"
+
+            [color_theme]
+            label = "#FF8800"
+            wind = "Red"
+            "##,
+        )
+        .unwrap();
+        let colors = format.color_theme();
+        assert_eq!(colors.label, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(colors.wind, Color::Ansi(AnsiColor::Red));
+        assert_eq!(colors.temperature, ColorTheme::nord().temperature);
+    }
+
+    #[test]
+    fn test_default_color_theme_table_does_not_override_theme() {
+        let format: FormatterConfig = toml::from_str(
+            r#"
+            theme = "gruvbox"
+
+            [color_theme]
+            label = "BrightBlue"
+            location = "BrightWhite"
+            temperature = "BrightYellow"
+            wind = "BrightRed"
+            precipitation = "BrightCyan"
+            pressure = "BrightGreen"
+            humidity = "Blue"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(format.color_theme(), ColorTheme::gruvbox());
+    }
+
+    #[test_case("#ff88" ; "short hex")]
+    #[test_case("#gg8800" ; "not hex")]
+    #[test_case("Orange" ; "unknown name")]
+    fn test_invalid_theme_color(color: &str) {
+        let result = toml::from_str::<FormatterConfig>(&format!("color_theme.label = \"{color}\""));
+        assert!(result.is_err());
+    }
+
+    #[test_case("colors = \"never\"", ColorMode::Never ; "mode")]
+    #[test_case("use_colors = true", ColorMode::Always ; "old bool true")]
+    #[test_case("use_colors = false", ColorMode::Never ; "old bool false")]
+    #[test_case("", ColorMode::Auto ; "missing")]
+    fn test_colors_config(toml: &str, expected: ColorMode) {
+        let format: FormatterConfig = toml::from_str(toml).unwrap();
+        assert_eq!(format.colors, expected);
+    }
+
+    #[test_case(&[], None ; "absent")]
+    #[test_case(&["--colors"], Some(ColorMode::Always) ; "flag alone")]
+    #[test_case(&["--colors", "never"], Some(ColorMode::Never) ; "with value")]
+    #[test_case(&["--colors=auto"], Some(ColorMode::Auto) ; "with equals")]
+    fn test_colors_cli(args: &[&str], expected: Option<ColorMode>) {
+        use clap::Parser;
+        let cli = Cli::parse_from(["rustormy", "-c", "London"].iter().chain(args));
+        assert_eq!(cli.colors, expected);
+    }
+
     #[test]
     fn test_load_incorrect_config_file() {
         let config_file_path = std::env::temp_dir().join("test_load_incorrect_config_file.toml");
@@ -860,23 +1597,38 @@ live_mode_footer = false
             live_mode_interval: 300,
             live_mode_footer: true,
             use_geocoding_cache: true,
+            use_weather_cache: true,
             verbose: 1,
             format: FormatterConfig {
                 output_format: OutputFormat::Text,
                 text_mode: TextMode::Full,
-                use_colors: false,
+                colors: ColorMode::Auto,
                 show_city_name: false,
                 align_right: false,
                 wind_in_degrees: false,
                 units: Units::Metric,
                 language: Language::English,
-                color_theme: ColorTheme::default(),
+                theme: ThemeName::default(),
+                color_theme: ColorThemeOverrides::default(),
+                gradients: false,
+                icons: IconStyle::default(),
+                icon_pack: default_icon_pack(),
+                template: None,
+                fields: default_fields(),
+                responsive: true,
+                chart: false,
+                chart_hours: default_chart_hours(),
+                sparkline: false,
+                sparkline_hours: default_sparkline_hours(),
+                csv_header: true,
+                layout: Layout::default(),
             },
             ..Default::default()
         };
 
         let cli = Cli {
-            city: Some("CLI City".to_string()),
+            city: vec!["CLI City".to_string()],
+            at: Vec::new(),
             lat: Some(30.0),
             lon: Some(40.0),
             provider: Some(Provider::OpenWeatherMap),
@@ -884,18 +1636,31 @@ live_mode_footer = false
             output_format: Some(OutputFormat::Json),
             language: Some(Language::Spanish),
             show_city_name: true,
-            use_colors: true,
+            colors: Some(ColorMode::Always),
             use_degrees_for_wind: true,
             compact_mode: true,
             one_line_mode: false,
             text_mode: None,
             align_right: true,
+            layout: None,
+            chart: false,
+            sparkline: false,
             no_footer: false,
             live_mode: true,
             live_mode_interval: Some(600),
             no_cache: true,
             verbose: 3,
             clear_cache: false,
+            no_header: false,
+            theme: None,
+            gradients: false,
+            icons: None,
+            icon_pack: None,
+            template: None,
+            record: None,
+            replay: None,
+            profile: None,
+            command: None,
         };
         config.merge_cli(cli).unwrap();
         assert_eq!(config.city(), Some("CLI City"));
@@ -905,19 +1670,22 @@ live_mode_footer = false
         assert_eq!(config.format.output_format, OutputFormat::Json);
         assert_eq!(config.format.language, Language::Spanish);
         assert!(config.format.show_city_name);
-        assert!(config.format.use_colors);
+        assert_eq!(config.format.colors, ColorMode::Always);
         assert!(config.format.wind_in_degrees);
         assert_eq!(config.format.text_mode, TextMode::Compact);
         assert!(config.live_mode);
         assert_eq!(config.live_mode_interval, 600);
         assert!(config.format.align_right);
         assert!(!config.use_geocoding_cache);
+        assert!(!config.use_weather_cache);
+        assert!(!config.use_http_cache);
         assert_eq!(config.verbose, 3);
     }
 
     fn base_cli() -> Cli {
         Cli {
-            city: Some("TestCity".to_string()),
+            city: vec!["TestCity".to_string()],
+            at: Vec::new(),
             lat: None,
             lon: None,
             provider: None,
@@ -925,18 +1693,31 @@ live_mode_footer = false
             output_format: None,
             language: None,
             show_city_name: false,
-            use_colors: false,
+            colors: None,
             use_degrees_for_wind: false,
             compact_mode: false,
             one_line_mode: false,
             text_mode: None,
             align_right: false,
+            layout: None,
+            chart: false,
+            sparkline: false,
             no_footer: false,
             live_mode: false,
             live_mode_interval: None,
             no_cache: false,
             verbose: 0,
             clear_cache: false,
+            no_header: false,
+            theme: None,
+            gradients: false,
+            icons: None,
+            icon_pack: None,
+            template: None,
+            record: None,
+            replay: None,
+            profile: None,
+            command: None,
         }
     }
 
@@ -952,6 +1733,22 @@ live_mode_footer = false
         assert_eq!(config.format.text_mode, TextMode::Compact);
     }
 
+    #[test]
+    fn test_http_cache_off_with_no_cache_and_replay() {
+        let mut config = Config::default();
+        config.merge_cli(base_cli()).unwrap();
+        assert!(config.use_http_cache());
+
+        let mut config = Config::default();
+        config
+            .merge_cli(Cli {
+                replay: Some("recordings".into()),
+                ..base_cli()
+            })
+            .unwrap();
+        assert!(!config.use_http_cache());
+    }
+
     #[test]
     fn test_text_mode_one_line_only() {
         let mut config = Config::default();
@@ -1035,4 +1832,493 @@ live_mode_footer = false
             "Expected no more providers, but got one"
         );
     }
+
+    #[test_case(false, false, None, None ; "nothing")]
+    #[test_case(true, false, None, Some(24) ; "chart")]
+    #[test_case(false, true, None, Some(12) ; "sparkline")]
+    #[test_case(false, false, Some("%t %T"), Some(12) ; "template trend")]
+    #[test_case(false, false, Some("%t %w"), None ; "template without trends")]
+    #[test_case(true, true, None, Some(24) ; "longest of both")]
+    fn test_forecast_hours(
+        chart: bool,
+        sparkline: bool,
+        template: Option<&str>,
+        hours: Option<u8>,
+    ) {
+        let format = FormatterConfig {
+            chart,
+            sparkline,
+            template: template.map(ToString::to_string),
+            ..Default::default()
+        };
+        assert_eq!(format.forecast_hours(), hours);
+    }
+
+    #[test_case(&[], &["London", "Lisbon"], None ; "configured list")]
+    #[test_case(&["-c", "Tokyo"], &[], Some("Tokyo") ; "one city replaces list")]
+    #[test_case(&["-c", "Tokyo", "-c", "Oslo"], &["Tokyo", "Oslo"], None ; "repeated city replaces list")]
+    #[test_case(&["-y", "1", "-x", "2"], &[], None ; "coordinates replace list")]
+    fn test_merge_cli_locations(args: &[&str], locations: &[&str], city: Option<&str>) {
+        use clap::Parser;
+        let config = Config {
+            locations: Locations::List(vec!["London".to_string(), "Lisbon".to_string()]),
+            ..Default::default()
+        };
+        let cli = Cli::parse_from(["rustormy"].iter().chain(args));
+        let config = config.merge_cli_test(cli).unwrap();
+        let expected: Vec<Shown> = locations
+            .iter()
+            .map(|city| Shown::City((*city).to_string()))
+            .collect();
+        assert_eq!(config.locations(), expected);
+        assert_eq!(config.city(), city);
+    }
+
+    #[test]
+    fn test_single_configured_location_is_city() {
+        use clap::Parser;
+        let config = Config {
+            locations: Locations::List(vec!["London".to_string()]),
+            ..Default::default()
+        }
+        .merge_cli_test(Cli::parse_from(["rustormy"]))
+        .unwrap();
+        assert!(config.locations().is_empty());
+        assert_eq!(config.city(), Some("London"));
+    }
+
+    #[test]
+    fn test_validate_locations() {
+        use clap::Parser;
+        let mut config = Config {
+            locations: Locations::List(vec!["London".to_string(), "Lisbon".to_string()]),
+            format: FormatterConfig {
+                show_city_name: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .merge_cli_test(Cli::parse_from(["rustormy"]))
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        config.live_mode = true;
+        assert!(matches!(
+            config.validate(),
+            Err(RustormyError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test_case(OutputFormat::Markdown, true ; "markdown")]
+    #[test_case(OutputFormat::Ndjson, true ; "ndjson")]
+    #[test_case(OutputFormat::Html, false ; "html")]
+    #[test_case(OutputFormat::Svg, false ; "svg")]
+    #[test_case(OutputFormat::Waybar, false ; "waybar")]
+    #[test_case(OutputFormat::I3bar, false ; "i3bar")]
+    #[test_case(OutputFormat::Polybar, false ; "polybar")]
+    #[test_case(OutputFormat::Tmux, false ; "tmux")]
+    #[test_case(OutputFormat::Starship, false ; "starship")]
+    fn test_validate_locations_output_format(output_format: OutputFormat, valid: bool) {
+        use clap::Parser;
+        let config = Config {
+            locations: Locations::List(vec!["London".to_string(), "Lisbon".to_string()]),
+            format: FormatterConfig {
+                output_format,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .merge_cli_test(Cli::parse_from(["rustormy"]))
+        .unwrap();
+        assert_eq!(config.validate().is_ok(), valid);
+    }
+
+    #[test]
+    fn test_for_location() {
+        let config = Config {
+            lat: Some(51.5),
+            lon: Some(-0.1),
+            locations: Locations::List(vec!["London".to_string(), "Lisbon".to_string()]),
+            ..Default::default()
+        };
+        let lisbon = config.for_location(&Shown::City("Lisbon".to_string()));
+        assert_eq!(lisbon.city(), Some("Lisbon"));
+        assert_eq!(lisbon.coordinates(), None);
+        assert!(lisbon.locations().is_empty());
+        assert!(lisbon.format().show_city_name);
+        assert!(lisbon.validate().is_ok());
+    }
+
+    fn bookmarked_config() -> Config {
+        let (config, _) = Config::parse_config(
+            r#"
+            providers = ["om"]
+            city = "London"
+            default_location = "home"
+
+            [api_keys]
+
+            [locations.home]
+            lat = 52.52
+            lon = 13.40
+            units = "imperial"
+
+            [locations.office]
+            city = "Lisbon"
+            providers = ["yr"]
+            language = "es"
+            "#,
+        )
+        .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_named_locations_roundtrip() {
+        let config = bookmarked_config();
+        let (parsed, _) = Config::parse_config(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(parsed.locations, config.locations);
+        assert_eq!(parsed.default_location.as_deref(), Some("home"));
+    }
+
+    #[test]
+    fn test_default_location() {
+        use clap::Parser;
+        let config = bookmarked_config()
+            .merge_cli_test(Cli::parse_from(["rustormy"]))
+            .unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.city(), None);
+        assert_eq!(config.coordinates(), Some((52.52, 13.40)));
+        assert_eq!(config.units(), Units::Imperial);
+    }
+
+    #[test]
+    fn test_at_named_location() {
+        use clap::Parser;
+        let config = bookmarked_config()
+            .merge_cli_test(Cli::parse_from(["rustormy", "-L", "office"]))
+            .unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.city(), Some("Lisbon"));
+        assert_eq!(config.coordinates(), None);
+        assert_eq!(config.providers(), &vec![Provider::Yr]);
+        assert_eq!(config.language(), Language::Spanish);
+        assert_eq!(config.units(), Units::Metric);
+    }
+
+    #[test]
+    fn test_cli_overrides_named_location() {
+        use clap::Parser;
+        let config = bookmarked_config()
+            .merge_cli_test(Cli::parse_from([
+                "rustormy", "--at", "office", "-p", "om", "-g", "en",
+            ]))
+            .unwrap();
+        assert_eq!(config.providers(), &vec![Provider::OpenMeteo]);
+        assert_eq!(config.language(), Language::English);
+
+        // A location on the command line replaces `default_location`
+        let config = bookmarked_config()
+            .merge_cli_test(Cli::parse_from(["rustormy", "-c", "Paris"]))
+            .unwrap();
+        assert_eq!(config.city(), Some("Paris"));
+        assert_eq!(config.units(), Units::Metric);
+    }
+
+    #[test]
+    fn test_at_several_named_locations() {
+        use clap::Parser;
+        let config = bookmarked_config()
+            .merge_cli_test(Cli::parse_from([
+                "rustormy", "--at", "home", "--at", "office",
+            ]))
+            .unwrap();
+        assert!(config.validate().is_ok());
+        let home = Shown::Named("home".to_string());
+        let office = Shown::Named("office".to_string());
+        assert_eq!(config.locations(), [home.clone(), office.clone()]);
+
+        let office = config.for_location(&office);
+        assert_eq!(office.city(), Some("Lisbon"));
+        assert_eq!(office.providers(), &vec![Provider::Yr]);
+        assert_eq!(office.language(), Language::Spanish);
+        let home = config.for_location(&home);
+        assert_eq!(home.coordinates(), Some((52.52, 13.40)));
+        assert_eq!(home.providers(), &vec![Provider::OpenMeteo]);
+        assert_eq!(home.units(), Units::Imperial);
+    }
+
+    #[test]
+    fn test_city_named_like_location() {
+        use clap::Parser;
+        let config = bookmarked_config()
+            .merge_cli_test(Cli::parse_from(["rustormy", "-c", "home", "-c", "Paris"]))
+            .unwrap();
+        let home = config.for_location(&config.locations()[0]);
+        assert_eq!(home.city(), Some("home"));
+        assert_eq!(home.coordinates(), None);
+        assert_eq!(home.units(), Units::Metric);
+    }
+
+    #[test_case(&["rustormy", "--at", "gym"] ; "unknown at")]
+    #[test_case(&["rustormy"] ; "unknown default")]
+    fn test_unknown_named_location(args: &[&str]) {
+        use clap::Parser;
+        let mut config = bookmarked_config();
+        config.default_location = Some("gym".to_string());
+        let config = config.merge_cli_test(Cli::parse_from(args)).unwrap();
+        let error = config.validate().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown location \"gym\", known locations: home, office"
+        );
+    }
+
+    #[test]
+    fn test_validate_named_location_without_place() {
+        let mut config = bookmarked_config();
+        config.locations = Locations::Named(
+            [("home".to_string(), NamedLocation::default())]
+                .into_iter()
+                .collect(),
+        );
+        assert!(matches!(
+            config.validate(),
+            Err(RustormyError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_keys_of_selected_locations() {
+        use clap::Parser;
+        let mut config = bookmarked_config();
+        if let Locations::Named(named) = &mut config.locations {
+            named.get_mut("office").unwrap().providers = vec![Provider::OpenWeatherMap];
+        }
+        let home = config
+            .clone()
+            .merge_cli_test(Cli::parse_from(["rustormy", "--at", "home"]))
+            .unwrap();
+        assert!(home.validate().is_ok());
+
+        let both = config
+            .merge_cli_test(Cli::parse_from([
+                "rustormy", "--at", "home", "--at", "office",
+            ]))
+            .unwrap();
+        assert!(matches!(
+            both.validate(),
+            Err(RustormyError::MissingApiKey {
+                provider: Provider::OpenWeatherMap,
+                ..
+            })
+        ));
+    }
+
+    fn profiled_config() -> Config {
+        let (config, _) = Config::parse_config(
+            r#"
+            providers = ["om"]
+            city = "London"
+            verbose = 1
+
+            [api_keys]
+
+            [format]
+            text_mode = "full"
+            colors = "always"
+            units = "imperial"
+
+            [profiles.bar]
+            verbose = 0
+            [profiles.bar.format]
+            text_mode = "one_line"
+            colors = "never"
+            icons = "emoji"
+
+            [profiles.kiosk]
+            live_mode = true
+            live_mode_interval = 60
+            "#,
+        )
+        .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_with_profile() {
+        let config = profiled_config().with_profile("bar").unwrap();
+        assert_eq!(config.verbose(), 0);
+        assert_eq!(config.format.text_mode, TextMode::OneLine);
+        assert_eq!(config.format.colors, ColorMode::Never);
+        assert_eq!(config.format.icons, IconStyle::Emoji);
+        // Settings the profile doesn't mention are kept
+        assert_eq!(config.units(), Units::Imperial);
+        assert_eq!(config.city(), Some("London"));
+        assert!(config.validate().is_ok());
+
+        let config = profiled_config().with_profile("kiosk").unwrap();
+        assert!(config.live_mode());
+        assert_eq!(config.live_mode_interval(), 60);
+        assert_eq!(config.format.text_mode, TextMode::Full);
+    }
+
+    #[test]
+    fn test_profile_with_aliased_option() {
+        let mut config = profiled_config();
+        config.profiles.insert(
+            "old".to_string(),
+            toml::from_str("format = { use_colors = false }").unwrap(),
+        );
+        config.validate().unwrap();
+        let config = config.with_profile("old").unwrap();
+        assert_eq!(config.format.colors, ColorMode::Never);
+    }
+
+    #[test]
+    fn test_env_with_aliased_option() {
+        let config = Config::default()
+            .with_env(vars(&[("RUSTORMY_FORMAT__USE_COLORS", "true")]))
+            .unwrap();
+        assert_eq!(config.format.colors, ColorMode::Always);
+    }
+
+    #[test]
+    fn test_cli_overrides_profile() {
+        use clap::Parser;
+        let config = profiled_config()
+            .with_profile("bar")
+            .unwrap()
+            .merge_cli_test(Cli::parse_from(["rustormy", "--compact", "-v"]))
+            .unwrap();
+        assert_eq!(config.format.text_mode, TextMode::Compact);
+        assert_eq!(config.format.colors, ColorMode::Never);
+        assert_eq!(config.verbose(), 1);
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let error = profiled_config().with_profile("desk").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown profile \"desk\", known profiles: bar, kiosk"
+        );
+        let error = Config::default().with_profile("desk").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown profile \"desk\", known profiles: none, add them as [profiles.<name>]"
+        );
+    }
+
+    #[test_case("[profiles.bar]\ncolour = \"never\"" ; "unknown field")]
+    #[test_case("[profiles.bar.format]\ntext_mode = \"tiny\"" ; "invalid value")]
+    #[test_case("[profiles.bar.profiles.baz]\nverbose = 1" ; "nested profile")]
+    fn test_validate_invalid_profile(profile: &str) {
+        let (config, _) = Config::parse_config(&format!(
+            "city = \"London\"\nproviders = [\"om\"]\n[api_keys]\n{profile}"
+        ))
+        .unwrap();
+        let result = config.validate();
+        assert!(
+            matches!(result, Err(RustormyError::InvalidProfile { ref name, .. }) if name == "bar"),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_profiles_roundtrip() {
+        let config = profiled_config();
+        let (parsed, _) = Config::parse_config(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(parsed.profiles, config.profiles);
+    }
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_with_env() {
+        let config = profiled_config()
+            .with_env(vars(&[
+                ("RUSTORMY_CITY", "Berlin"),
+                ("RUSTORMY_LIVE_MODE_INTERVAL", "120"),
+                ("RUSTORMY_PROVIDERS", r#"["owm", "om"]"#),
+                ("RUSTORMY_FORMAT__TEXT_MODE", "compact"),
+                ("RUSTORMY_API_KEYS__OPEN_WEATHER_MAP", "12345"),
+                ("RUSTORMY_PROFILE", "bar"),
+                ("RUSTORMY_VERBOSE", ""),
+                ("HOME", "/root"),
+            ]))
+            .unwrap();
+        assert_eq!(config.city(), Some("Berlin"));
+        assert_eq!(config.live_mode_interval(), 120);
+        assert_eq!(
+            config.providers(),
+            &vec![Provider::OpenWeatherMap, Provider::OpenMeteo]
+        );
+        assert_eq!(config.format.text_mode, TextMode::Compact);
+        assert_eq!(config.api_keys().open_weather_map, "12345");
+        // Options not set in the environment are kept
+        assert_eq!(config.verbose(), 1);
+        assert_eq!(config.units(), Units::Imperial);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cli_overrides_env() {
+        use clap::Parser;
+        let config = profiled_config()
+            .with_profile("bar")
+            .unwrap()
+            .with_env(vars(&[
+                ("RUSTORMY_FORMAT__TEXT_MODE", "compact"),
+                ("RUSTORMY_FORMAT__COLORS", "always"),
+            ]))
+            .unwrap()
+            .merge_cli_test(Cli::parse_from(["rustormy", "--colors", "never"]))
+            .unwrap();
+        assert_eq!(config.format.text_mode, TextMode::Compact);
+        assert_eq!(config.format.colors, ColorMode::Never);
+        assert_eq!(config.format.icons, IconStyle::Emoji);
+    }
+
+    #[test_case("RUSTORMY_LIVE_MODE_INTERVAL", "often" ; "invalid value")]
+    #[test_case("RUSTORMY_FORMAT__TEXT_MODE", "tiny" ; "invalid variant")]
+    fn test_invalid_env(name: &str, value: &str) {
+        let result = Config::default().with_env(vars(&[(name, value)]));
+        assert!(
+            matches!(
+                result,
+                Err(RustormyError::InvalidEnvironmentVariable { name: ref var, .. }) if var == name
+            ),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_unknown_env_is_skipped() {
+        let config = Config::default()
+            .with_env(vars(&[
+                ("RUSTORMY_CITTY", "Berlin"),
+                ("RUSTORMY_CITY", "Paris"),
+                ("RUSTORMY_TOKEN__SECRET", "x"),
+                ("RUSTORMY_FORMAT__TEXTMODE", "compact"),
+                ("RUSTORMY_API_KEYS__OPEN_WEATER_MAP", "key"),
+            ]))
+            .unwrap();
+        assert_eq!(config.city(), Some("Paris"));
+        assert_eq!(config.format.text_mode, TextMode::default());
+        assert_eq!(
+            config.unknown_env_vars(),
+            [
+                "RUSTORMY_API_KEYS__OPEN_WEATER_MAP",
+                "RUSTORMY_CITTY",
+                "RUSTORMY_FORMAT__TEXTMODE",
+                "RUSTORMY_TOKEN__SECRET"
+            ]
+        );
+    }
 }
@@ -162,6 +162,7 @@ impl Default for LegacyConfig {
 mod tests {
     use super::*;
     use crate::config::{Cli, Config};
+    use crate::models::ColorMode;
     use clap::Parser;
 
     #[test]
@@ -260,7 +261,7 @@ mod tests {
         assert_eq!(config.format().output_format, OutputFormat::Json);
         assert_eq!(config.format().text_mode, TextMode::OneLine);
         assert!(config.format().show_city_name);
-        assert!(config.format().use_colors);
+        assert_eq!(config.format().colors, ColorMode::Always);
         assert!(config.format().wind_in_degrees);
         assert!(config.format().align_right);
     }
@@ -303,7 +304,7 @@ mod tests {
         assert_eq!(config.format().units, Units::Metric);
         assert_eq!(config.format().output_format, OutputFormat::Text);
         assert!(config.format().show_city_name);
-        assert!(config.format().use_colors);
+        assert_eq!(config.format().colors, ColorMode::Always);
         let valid = config.validate();
         assert!(valid.is_ok(), "Expected valid config, got {valid:?}");
     }
@@ -334,7 +335,7 @@ mod tests {
         assert_eq!(config.format().output_format, OutputFormat::Text);
         assert_eq!(config.format().language, Language::Spanish);
         assert!(config.format().show_city_name);
-        assert!(config.format().use_colors);
+        assert_eq!(config.format().colors, ColorMode::Always);
         assert!(!config.format().wind_in_degrees);
         assert_eq!(config.format().text_mode, TextMode::Compact);
         assert!(!config.live_mode());
@@ -369,7 +370,7 @@ mod tests {
         assert_eq!(config.format().output_format, OutputFormat::Text);
         assert_eq!(config.format().language, Language::Spanish);
         assert!(config.format().show_city_name);
-        assert!(config.format().use_colors);
+        assert_eq!(config.format().colors, ColorMode::Always);
         assert!(!config.format().wind_in_degrees);
         assert_eq!(config.format().text_mode, TextMode::Full);
         assert!(!config.live_mode());
@@ -472,7 +473,7 @@ mod tests {
         assert_eq!(config.format().output_format, OutputFormat::Text);
         assert_eq!(config.format().language, Language::Spanish);
         assert!(config.format().show_city_name);
-        assert!(config.format().use_colors);
+        assert_eq!(config.format().colors, ColorMode::Always);
         assert!(!config.format().wind_in_degrees);
         assert_eq!(config.format().text_mode, TextMode::Full);
         assert!(!config.live_mode());
@@ -596,7 +597,7 @@ mod tests {
         assert_eq!(config.format().output_format, OutputFormat::Text);
         assert_eq!(config.format().language, Language::Spanish);
         assert!(config.format().show_city_name);
-        assert!(config.format().use_colors);
+        assert_eq!(config.format().colors, ColorMode::Always);
         assert!(!config.format().wind_in_degrees);
         assert_eq!(config.format().text_mode, TextMode::Full);
         assert!(!config.live_mode());
@@ -0,0 +1,157 @@
+use crate::errors::RustormyError;
+use crate::models::{Language, Provider, Units};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A location saved under a name in `[locations.<name>]`, with settings that apply only to it
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NamedLocation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+    /// Providers tried for this location instead of the global `providers`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<Provider>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<Units>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+}
+
+/// The `locations` key: a list of cities shown together, or a table of named locations
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Locations {
+    List(Vec<String>),
+    Named(BTreeMap<String, NamedLocation>),
+}
+
+impl Default for Locations {
+    fn default() -> Self {
+        Self::List(Vec::new())
+    }
+}
+
+/// One of several locations shown together: a city looked up by its name, or a location saved
+/// in `[locations]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shown {
+    City(String),
+    Named(String),
+}
+
+impl Shown {
+    /// Name the location is shown with
+    pub fn name(&self) -> &str {
+        match self {
+            Self::City(name) | Self::Named(name) => name,
+        }
+    }
+}
+
+impl Locations {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::List(list) => list.is_empty(),
+            Self::Named(named) => named.is_empty(),
+        }
+    }
+
+    /// The location saved as `name`, or an error listing the known names
+    pub fn get(&self, name: &str) -> Result<&NamedLocation, RustormyError> {
+        let named = match self {
+            Self::Named(named) => Some(named),
+            Self::List(_) => None,
+        };
+        named
+            .and_then(|named| named.get(name))
+            .ok_or_else(|| RustormyError::UnknownLocation {
+                name: name.to_string(),
+                known: named.map_or_else(
+                    || "none, add them as [locations.<name>]".to_string(),
+                    |named| named.keys().cloned().collect::<Vec<_>>().join(", "),
+                ),
+            })
+    }
+
+    /// Named locations, for settings that apply to each of them
+    pub fn named_mut(&mut self) -> impl Iterator<Item = &mut NamedLocation> {
+        match self {
+            Self::Named(named) => Some(named.values_mut()),
+            Self::List(_) => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+
+    pub fn named(&self) -> impl Iterator<Item = &NamedLocation> {
+        match self {
+            Self::Named(named) => Some(named.values()),
+            Self::List(_) => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_and_table() {
+        #[derive(Deserialize)]
+        struct File {
+            locations: Locations,
+        }
+
+        let file: File = toml::from_str(r#"locations = ["London", "Lisbon"]"#).unwrap();
+        assert_eq!(
+            file.locations,
+            Locations::List(vec!["London".to_string(), "Lisbon".to_string()])
+        );
+
+        let file: File = toml::from_str(
+            r#"
+            [locations.home]
+            lat = 52.52
+            lon = 13.40
+            units = "imperial"
+
+            [locations.office]
+            city = "Lisbon"
+            providers = ["yr"]
+            "#,
+        )
+        .unwrap();
+        let home = file.locations.get("home").unwrap();
+        assert_eq!(home.lat, Some(52.52));
+        assert_eq!(home.units, Some(Units::Imperial));
+        let office = file.locations.get("office").unwrap();
+        assert_eq!(office.city.as_deref(), Some("Lisbon"));
+        assert_eq!(office.providers, vec![Provider::Yr]);
+    }
+
+    #[test]
+    fn test_get_unknown_lists_known_names() {
+        let locations = Locations::Named(BTreeMap::from([
+            ("office".to_string(), NamedLocation::default()),
+            ("home".to_string(), NamedLocation::default()),
+        ]));
+        let error = locations.get("gym").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown location \"gym\", known locations: home, office"
+        );
+
+        let error = Locations::default().get("home").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown location \"home\", known locations: none, add them as [locations.<name>]"
+        );
+    }
+}
@@ -1,8 +1,14 @@
 mod api_keys;
+mod base_urls;
 mod cli;
+mod env;
 mod file;
 mod legacy;
+mod locations;
+mod profiles;
 
 pub use api_keys::ApiKeys;
+pub use base_urls::BaseUrls;
 pub use cli::Cli;
-pub use file::{Config, FormatterConfig};
+pub use file::{Config, FormatterConfig, config_dir};
+pub use locations::{Locations, NamedLocation, Shown};
@@ -0,0 +1,109 @@
+use toml::{Table, Value};
+
+/// Environment variable selecting a profile when `--profile` is not given
+pub const PROFILE_ENV: &str = "RUSTORMY_PROFILE";
+
+/// `overlay` merged into `base`: tables are merged key by key, any other value is replaced
+pub fn merge_table(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge_table(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Options still read under an older name: the table they are in, the old name and the name
+const ALIASES: [(&str, &str, &str); 1] = [("format", "use_colors", "colors")];
+
+/// Rename the options of `table` given by an older name, which would otherwise clash with the
+/// option of the same meaning, e.g. after merging a profile into a serialized configuration
+pub fn normalize_aliases(table: &mut Table) {
+    for (parent, alias, name) in ALIASES {
+        if let Some(Value::Table(parent)) = table.get_mut(parent)
+            && let Some(value) = parent.remove(alias)
+        {
+            parent.insert(name.to_string(), value);
+        }
+    }
+}
+
+/// Profile named by `--profile`, or else by a non-empty `RUSTORMY_PROFILE`
+pub fn profile_name(
+    cli_profile: Option<&str>,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    cli_profile
+        .map(ToString::to_string)
+        .or_else(|| env(PROFILE_ENV).filter(|name| !name.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_merge_table() {
+        let mut base: Table = toml::from_str(
+            r#"
+            verbose = 1
+            providers = ["om", "yr"]
+            [format]
+            text_mode = "full"
+            colors = "always"
+            "#,
+        )
+        .unwrap();
+        let overlay: Table = toml::from_str(
+            r#"
+            providers = ["wa"]
+            [format]
+            text_mode = "one_line"
+            "#,
+        )
+        .unwrap();
+        merge_table(&mut base, overlay);
+
+        let expected: Table = toml::from_str(
+            r#"
+            verbose = 1
+            providers = ["wa"]
+            [format]
+            text_mode = "one_line"
+            colors = "always"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn test_normalize_aliases() {
+        let mut table: Table = toml::from_str(
+            r#"
+            [format]
+            colors = "always"
+            use_colors = false
+            "#,
+        )
+        .unwrap();
+        normalize_aliases(&mut table);
+        let expected: Table = toml::from_str("format = { colors = false }").unwrap();
+        assert_eq!(table, expected);
+    }
+
+    #[test_case(Some("kiosk"), Some("bar"), Some("kiosk") ; "flag over environment")]
+    #[test_case(None, Some("bar"), Some("bar") ; "environment")]
+    #[test_case(None, Some(""), None ; "empty environment")]
+    #[test_case(None, None, None ; "none")]
+    fn test_profile_name(cli: Option<&str>, env: Option<&str>, expected: Option<&str>) {
+        let name = profile_name(cli, |name| {
+            assert_eq!(name, PROFILE_ENV);
+            env.map(ToString::to_string)
+        });
+        assert_eq!(name.as_deref(), expected);
+    }
+}
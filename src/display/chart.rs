@@ -0,0 +1,412 @@
+use crate::display::color::ColorSyntax;
+use crate::display::translations::ll;
+use crate::display::width::{pad, visible_width};
+use crate::models::{ColorTheme, ForecastHour, Language};
+use chrono::{TimeZone, Timelike};
+use std::fmt::Write;
+
+/// Character rows of the temperature line, four braille dots high each
+const TEMPERATURE_ROWS: usize = 4;
+
+/// Character rows of the precipitation bars, eight steps high each
+const PRECIPITATION_ROWS: usize = 2;
+
+/// Hours between the marks of the time axis
+const TICK_HOURS: u32 = 6;
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Bits of the braille dots of a character, by dot row from the top, in the left and right column
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Language, units and colors of the charts
+pub struct ChartStyle {
+    pub language: Language,
+    pub temperature_unit: &'static str,
+    pub precipitation_unit: &'static str,
+    pub colors: ColorSyntax,
+    pub theme: ColorTheme,
+}
+
+/// Values of the charted hours, with the ranges of the axes and their labels
+struct Scales {
+    temperatures: Vec<f64>,
+    min: f64,
+    max: f64,
+    temperature_labels: [String; 2],
+    precipitation: Vec<f64>,
+    /// Value of a full precipitation bar
+    scale: f64,
+    precipitation_labels: [String; 2],
+}
+
+impl Scales {
+    fn new(hours: &[ForecastHour], style: &ChartStyle) -> Self {
+        let temperatures: Vec<f64> = hours.iter().map(|hour| hour.temperature).collect();
+        let (min, max) = bounds(&temperatures);
+        let temperature_labels = [
+            format!("{max:.0}{}", style.temperature_unit),
+            format!("{min:.0}{}", style.temperature_unit),
+        ];
+        let (precipitation, scale, precipitation_labels) = precipitation_values(hours, style);
+        Self {
+            temperatures,
+            min,
+            max,
+            temperature_labels,
+            precipitation,
+            scale,
+            precipitation_labels,
+        }
+    }
+
+    fn label_width(&self) -> usize {
+        self.temperature_labels
+            .iter()
+            .chain(&self.precipitation_labels)
+            .map(|label| visible_width(label))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Temperature line and precipitation bars of `hours`, a column per hour, with as many hours as
+/// fit in `width` columns. Times on the axis are shown in `tz`. Empty if less than two hours fit.
+pub fn chart_lines<Tz: TimeZone>(
+    hours: &[ForecastHour],
+    style: &ChartStyle,
+    width: Option<usize>,
+    tz: &Tz,
+) -> Vec<String> {
+    // The axes are scaled to the hours shown, whose labels may be narrower or wider than those
+    // of every hour, so fewer hours are tried until they fit with their own labels
+    let mut columns = hours.len();
+    let scales = loop {
+        let scales = Scales::new(&hours[..columns], style);
+        let fit = width.map_or(columns, |width| {
+            columns.min(width.saturating_sub(scales.label_width() + 2))
+        });
+        let settled = fit == columns || fit < 2;
+        columns = fit;
+        if settled {
+            break scales;
+        }
+    };
+    if columns < 2 {
+        return Vec::new();
+    }
+    let label_width = scales.label_width();
+
+    let label = |text: &str| style.colors.paint(text, style.theme.label);
+    let mut lines = vec![label(ll(style.language, "Temperature"))];
+    lines.extend(axis_rows(
+        temperature_line(&scales.temperatures, scales.min, scales.max),
+        &scales.temperature_labels,
+        label_width,
+        |plot| style.colors.paint(plot, style.theme.temperature),
+        label,
+    ));
+    lines.push(label(ll(style.language, "Precipitation")));
+    lines.extend(axis_rows(
+        precipitation_bars(&scales.precipitation, scales.scale),
+        &scales.precipitation_labels,
+        label_width,
+        |plot| style.colors.paint(plot, style.theme.precipitation),
+        label,
+    ));
+    lines.extend(time_axis(&hours[..columns], label_width, tz));
+    lines
+}
+
+/// A bar per value, from the lowest at `min` to the highest at `max`
+pub fn sparkline(values: &[f64], min: f64, max: f64) -> String {
+    let top = (BARS.len() - 1) as f64;
+    values
+        .iter()
+        .map(|&value| {
+            let step = if (max - min).abs() < f64::EPSILON {
+                0.0
+            } else {
+                ((value - min) / (max - min) * top).round().clamp(0.0, top)
+            };
+            BARS[step as usize]
+        })
+        .collect()
+}
+
+/// Lowest and highest of `values`
+pub fn bounds(values: &[f64]) -> (f64, f64) {
+    values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+/// Chance of precipitation if the forecast has one, its amount otherwise, with the value of a
+/// full bar and the labels of the top and bottom rows
+fn precipitation_values(
+    hours: &[ForecastHour],
+    style: &ChartStyle,
+) -> (Vec<f64>, f64, [String; 2]) {
+    if hours
+        .iter()
+        .any(|hour| hour.precipitation_probability.is_some())
+    {
+        let values = hours
+            .iter()
+            .map(|hour| f64::from(hour.precipitation_probability.unwrap_or(0)))
+            .collect();
+        return (values, 100.0, ["100%".to_string(), "0%".to_string()]);
+    }
+    let values: Vec<f64> = hours.iter().map(|hour| hour.precipitation).collect();
+    let (_, max) = bounds(&values);
+    let scale = max.max(0.0);
+    let unit = style.precipitation_unit;
+    let top = format!("{} {unit}", (scale * 100.0).round() / 100.0);
+    (values, scale, [top, format!("0 {unit}")])
+}
+
+/// `plots` with the axis on their left, labeled with `labels` on the top and bottom rows
+fn axis_rows(
+    plots: Vec<String>,
+    labels: &[String; 2],
+    label_width: usize,
+    paint_plot: impl Fn(&str) -> String,
+    paint_label: impl Fn(&str) -> String,
+) -> Vec<String> {
+    let last = plots.len() - 1;
+    plots
+        .into_iter()
+        .enumerate()
+        .map(|(row, plot)| {
+            let axis = match row {
+                0 => format!("{} ┤", paint_label(&pad(&labels[0], label_width, true))),
+                row if row == last => {
+                    format!("{} ┤", paint_label(&pad(&labels[1], label_width, true)))
+                }
+                _ => format!("{} │", " ".repeat(label_width)),
+            };
+            format!("{axis}{}", paint_plot(&plot))
+        })
+        .collect()
+}
+
+/// Braille rows of a line through `temperatures`, two dots wide per hour
+fn temperature_line(temperatures: &[f64], min: f64, max: f64) -> Vec<String> {
+    let height = TEMPERATURE_ROWS * 4;
+    let level = |value: f64| {
+        if (max - min).abs() < f64::EPSILON {
+            height / 2
+        } else {
+            ((value - min) / (max - min) * (height - 1) as f64).round() as usize
+        }
+    };
+    // The right dot of an hour is halfway to the next one
+    let levels: Vec<usize> = temperatures
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &value)| {
+            let next = temperatures
+                .get(i + 1)
+                .map_or(value, |&next| f64::midpoint(value, next));
+            [level(value), level(next)]
+        })
+        .collect();
+
+    let mut cells = vec![vec![0; temperatures.len()]; TEMPERATURE_ROWS];
+    let mut previous = None;
+    for (x, &y) in levels.iter().enumerate() {
+        // Steps are joined by a vertical run of dots
+        let run = match previous {
+            Some(p) if y > p => p + 1..=y,
+            Some(p) if y < p => y..=p - 1,
+            _ => y..=y,
+        };
+        for dot in run {
+            let from_top = height - 1 - dot;
+            cells[from_top / 4][x / 2] |= DOTS[from_top % 4][x % 2];
+        }
+        previous = Some(y);
+    }
+    cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&bits| match bits {
+                    0 => ' ',
+                    bits => char::from_u32(0x2800 + bits).unwrap_or(' '),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Rows of bars of `values`, full at `scale`
+fn precipitation_bars(values: &[f64], scale: f64) -> Vec<String> {
+    let steps = PRECIPITATION_ROWS * BARS.len();
+    let heights: Vec<usize> = values
+        .iter()
+        .map(|&value| {
+            if value <= 0.0 || scale <= 0.0 {
+                0
+            } else {
+                // Even a trace of precipitation gets a bar
+                ((value / scale * steps as f64).round() as usize).clamp(1, steps)
+            }
+        })
+        .collect();
+    (0..PRECIPITATION_ROWS)
+        .rev()
+        .map(|row| {
+            heights
+                .iter()
+                .map(|height| match height.saturating_sub(row * BARS.len()) {
+                    0 => ' ',
+                    step => BARS[step.min(BARS.len()) - 1],
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Axis line marking every sixth hour, and the hours of the marks below it
+fn time_axis<Tz: TimeZone>(hours: &[ForecastHour], label_width: usize, tz: &Tz) -> [String; 2] {
+    let mut axis = format!("{} └", " ".repeat(label_width));
+    let mut times = " ".repeat(label_width + 2);
+    for (column, hour) in hours.iter().enumerate() {
+        let local = hour.time.with_timezone(tz);
+        if local.hour() % TICK_HOURS == 0 {
+            axis.push('┬');
+            // Marks are far enough apart for their hours not to overlap
+            let start = label_width + 2 + column;
+            if visible_width(&times) <= start {
+                times = pad(&times, start, false);
+                let _ = write!(times, "{:02}", local.hour());
+            }
+        } else {
+            axis.push('─');
+        }
+    }
+    [axis, times.trim_end().to_string()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::hourly::sample_hours;
+    use chrono::{DateTime, Utc};
+    use test_case::test_case;
+
+    fn style() -> ChartStyle {
+        ChartStyle {
+            language: Language::English,
+            temperature_unit: "°C",
+            precipitation_unit: "mm",
+            colors: ColorSyntax::Plain,
+            theme: ColorTheme::default(),
+        }
+    }
+
+    /// Hours from 04:00 UTC with the given temperatures and chances of precipitation
+    fn hours(temperatures: &[f64], probabilities: &[Option<u8>]) -> Vec<ForecastHour> {
+        let start: DateTime<Utc> = "2026-10-18T04:00:00Z".parse().unwrap();
+        sample_hours(start, temperatures, probabilities)
+    }
+
+    #[test]
+    fn test_chart_lines() {
+        let hours = hours(
+            &[10.0, 12.0, 14.0, 16.0, 18.0, 20.0, 22.0, 20.0, 18.0],
+            &[
+                Some(0),
+                Some(0),
+                Some(25),
+                Some(50),
+                Some(100),
+                Some(50),
+                Some(0),
+                None,
+                None,
+            ],
+        );
+        let lines = chart_lines(&hours, &style(), None, &Utc);
+
+        assert_eq!(
+            lines,
+            [
+                "Temperature",
+                "22°C ┤     ⡔⠑⢄ ",
+                "     │   ⡠⠊  ⠈⠒",
+                "     │ ⢀⠔⠁     ",
+                "10°C ┤⡠⠃       ",
+                "Precip",
+                "100% ┤    █    ",
+                "  0% ┤  ▄███   ",
+                "     └──┬─────┬",
+                "        06    12",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chart_lines_fit_width() {
+        let hours = hours(&[10.0; 48], &[None; 48]);
+        let lines = chart_lines(&hours, &style(), Some(30), &Utc);
+
+        assert!(
+            lines.iter().all(|line| visible_width(line) <= 30),
+            "got {lines:#?}"
+        );
+        assert_eq!(visible_width(&lines[8]), 30);
+        assert!(chart_lines(&hours, &style(), Some(7), &Utc).is_empty());
+    }
+
+    #[test]
+    fn test_chart_lines_scaled_to_shown_hours() {
+        let mut temperatures = vec![10.0; 24];
+        temperatures.extend([40.0; 24]);
+        let mut hours = hours(&temperatures, &[None; 48]);
+        hours[1].precipitation = 0.5;
+        hours[30].precipitation = 5.0;
+        let lines = chart_lines(&hours, &style(), Some(30), &Utc);
+
+        // The 40°C and 5 mm hours don't fit, so they don't stretch the axes
+        assert_eq!(lines[1], format!("  10°C ┤{}", " ".repeat(22)));
+        assert_eq!(lines[6], format!("0.5 mm ┤ █{}", " ".repeat(20)));
+    }
+
+    #[test]
+    fn test_chart_lines_precipitation_amount() {
+        let mut hours = hours(&[10.0; 4], &[None; 4]);
+        hours[1].precipitation = 0.05;
+        hours[2].precipitation = 1.5;
+        let lines = chart_lines(&hours, &style(), None, &Utc);
+
+        assert_eq!(lines[6], "1.5 mm ┤  █ ");
+        assert_eq!(lines[7], "  0 mm ┤ ▁█ ");
+    }
+
+    #[test_case(&[10.0, 12.0, 14.0, 17.0, 20.0, 24.0, 22.0], 10.0, 24.0, "▁▂▃▅▆█▇" ; "range of the values")]
+    #[test_case(&[0.0, 10.0, 80.0, 100.0], 0.0, 100.0, "▁▂▇█" ; "fixed range")]
+    #[test_case(&[7.0, 7.0, 7.0], 7.0, 7.0, "▁▁▁" ; "flat")]
+    fn test_sparkline(values: &[f64], min: f64, max: f64, expected: &str) {
+        assert_eq!(sparkline(values, min, max), expected);
+    }
+
+    #[test_case(&[5.0, 5.0], 1 ; "flat")]
+    #[test_case(&[0.0, 30.0], 4 ; "rising")]
+    fn test_temperature_line(temperatures: &[f64], rows_with_dots: usize) {
+        let rows = temperature_line(temperatures, bounds(temperatures).0, bounds(temperatures).1);
+        assert_eq!(rows.len(), TEMPERATURE_ROWS);
+        assert!(
+            rows.iter()
+                .all(|row| row.chars().count() == temperatures.len())
+        );
+        assert_eq!(
+            rows.iter().filter(|row| !row.trim().is_empty()).count(),
+            rows_with_dots.min(TEMPERATURE_ROWS),
+            "got {rows:#?}"
+        );
+    }
+}
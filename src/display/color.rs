@@ -1,6 +1,356 @@
-use crate::models::AnsiColor;
+use crate::environment;
+use crate::models::{AnsiColor, Color, ColorMode};
 use std::fmt::Display;
 
 pub fn colored_text(text: impl Display, color: AnsiColor) -> String {
     format!("\x1b[{color}m{text}\x1b[0m")
 }
+
+impl ColorMode {
+    /// Whether to color standard output
+    pub fn for_stdout(self) -> bool {
+        self.resolve(environment::var, environment::stdout_is_terminal())
+    }
+
+    /// Whether to color standard error, where logs are written
+    pub fn for_stderr(self) -> bool {
+        self.resolve(environment::var, environment::stderr_is_terminal())
+    }
+
+    fn resolve(self, env: impl Fn(&str) -> Option<String>, is_terminal: bool) -> bool {
+        let is_set = |name: &str| env(name).is_some_and(|value| !value.is_empty());
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            // https://no-color.org and https://bixense.com/clicolors
+            Self::Auto if is_set("NO_COLOR") => false,
+            Self::Auto
+                if is_set("CLICOLOR_FORCE") && env("CLICOLOR_FORCE").as_deref() != Some("0") =>
+            {
+                true
+            }
+            Self::Auto if env("TERM").as_deref() == Some("dumb") => false,
+            Self::Auto => is_terminal,
+        }
+    }
+}
+
+/// Colors a terminal can show; `#rrggbb` colors are approximated on the smaller palettes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detect the color depth from the `COLORTERM` and `TERM` environment variables
+    pub fn detect() -> Self {
+        Self::from_env(
+            environment::var("COLORTERM").as_deref(),
+            environment::var("TERM").as_deref(),
+        )
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            Self::TrueColor
+        } else if term.is_some_and(|term| term.contains("256color")) {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// SGR parameters setting `color` as the foreground color
+    pub fn sgr(self, color: Color) -> String {
+        match (color, self) {
+            (Color::Ansi(color), _) => color.to_string(),
+            (Color::Rgb(r, g, b), Self::TrueColor) => format!("38;2;{r};{g};{b}"),
+            (Color::Rgb(r, g, b), Self::Ansi256) => format!("38;5;{}", nearest_xterm(r, g, b)),
+            (Color::Rgb(r, g, b), Self::Ansi16) => nearest_ansi(r, g, b).to_string(),
+        }
+    }
+}
+
+const ANSI_COLORS: [AnsiColor; 16] = [
+    AnsiColor::Black,
+    AnsiColor::Red,
+    AnsiColor::Green,
+    AnsiColor::Yellow,
+    AnsiColor::Blue,
+    AnsiColor::Magenta,
+    AnsiColor::Cyan,
+    AnsiColor::White,
+    AnsiColor::BrightBlack,
+    AnsiColor::BrightRed,
+    AnsiColor::BrightGreen,
+    AnsiColor::BrightYellow,
+    AnsiColor::BrightBlue,
+    AnsiColor::BrightMagenta,
+    AnsiColor::BrightCyan,
+    AnsiColor::BrightWhite,
+];
+
+fn rgb_of(hex: &str) -> (u8, u8, u8) {
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0);
+    (channel(1), channel(3), channel(5))
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| u32::from(a.abs_diff(b)).pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Closest of the 16 ANSI colors in the xterm default palette
+fn nearest_ansi(r: u8, g: u8, b: u8) -> AnsiColor {
+    ANSI_COLORS
+        .into_iter()
+        .min_by_key(|&color| distance(rgb_of(hex_color(color)), (r, g, b)))
+        .unwrap_or(AnsiColor::White)
+}
+
+/// Closest color of the 6×6×6 cube or the grayscale ramp of the 256-color palette
+fn nearest_xterm(r: u8, g: u8, b: u8) -> u8 {
+    (16..=255)
+        .min_by_key(|&index| distance(rgb_of(&xterm_color(index)), (r, g, b)))
+        .unwrap_or(16)
+}
+
+/// RGB value of a theme color, `#rrggbb`
+pub fn color_hex(color: Color) -> String {
+    match color {
+        Color::Ansi(color) => hex_color(color).to_string(),
+        Color::Rgb(..) => String::from(color),
+    }
+}
+
+/// How colored values are written: raw ANSI escapes for terminals, or the
+/// formatting tags of a status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSyntax {
+    Plain,
+    Ansi(ColorDepth),
+    /// `%{F#rrggbb}text%{F-}`
+    Polybar,
+    /// `#[fg=colour]text#[default]`
+    Tmux,
+}
+
+impl ColorSyntax {
+    pub fn paint(self, text: impl Display, color: impl Into<Color>) -> String {
+        let color = color.into();
+        match self {
+            Self::Plain => text.to_string(),
+            Self::Ansi(depth) => format!("\x1b[{}m{text}\x1b[0m", depth.sgr(color)),
+            Self::Polybar => format!(
+                "%{{F{}}}{}%{{F-}}",
+                color_hex(color),
+                self.escape(&text.to_string())
+            ),
+            Self::Tmux => format!(
+                "#[fg={}]{}#[default]",
+                tmux_color(color),
+                self.escape(&text.to_string())
+            ),
+        }
+    }
+
+    /// `text` with the characters that start a formatting tag escaped, so it is shown as is
+    pub fn escape(self, text: &str) -> String {
+        match self {
+            Self::Plain | Self::Ansi(_) => text.to_string(),
+            Self::Polybar => text.replace('%', "%%"),
+            Self::Tmux => text.replace('#', "##"),
+        }
+    }
+}
+
+/// The 16 system colors of the xterm default palette
+const SYSTEM_COLORS: [&str; 16] = [
+    "#000000", "#cd0000", "#00cd00", "#cdcd00", "#0000ee", "#cd00cd", "#00cdcd", "#e5e5e5",
+    "#7f7f7f", "#ff0000", "#00ff00", "#ffff00", "#5c5cff", "#ff00ff", "#00ffff", "#ffffff",
+];
+
+/// RGB value of an ANSI color in the xterm default palette
+pub const fn hex_color(color: AnsiColor) -> &'static str {
+    match color {
+        AnsiColor::Black => SYSTEM_COLORS[0],
+        AnsiColor::Red => SYSTEM_COLORS[1],
+        AnsiColor::Green => SYSTEM_COLORS[2],
+        AnsiColor::Yellow => SYSTEM_COLORS[3],
+        AnsiColor::Blue => SYSTEM_COLORS[4],
+        AnsiColor::Magenta => SYSTEM_COLORS[5],
+        AnsiColor::Cyan => SYSTEM_COLORS[6],
+        AnsiColor::White => SYSTEM_COLORS[7],
+        AnsiColor::BrightBlack => SYSTEM_COLORS[8],
+        AnsiColor::BrightRed => SYSTEM_COLORS[9],
+        AnsiColor::BrightGreen => SYSTEM_COLORS[10],
+        AnsiColor::BrightYellow => SYSTEM_COLORS[11],
+        AnsiColor::BrightBlue => SYSTEM_COLORS[12],
+        AnsiColor::BrightMagenta => SYSTEM_COLORS[13],
+        AnsiColor::BrightCyan => SYSTEM_COLORS[14],
+        AnsiColor::BrightWhite => SYSTEM_COLORS[15],
+    }
+}
+
+/// RGB value of a color of the xterm 256-color palette
+pub fn xterm_color(index: u8) -> String {
+    match index {
+        0..=15 => SYSTEM_COLORS[usize::from(index)].to_string(),
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(index / 36),
+                level(index / 6 % 6),
+                level(index % 6)
+            )
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            format!("#{gray:02x}{gray:02x}{gray:02x}")
+        }
+    }
+}
+
+fn tmux_color(color: Color) -> String {
+    match color {
+        Color::Ansi(color) => tmux_ansi_color(color).to_string(),
+        Color::Rgb(..) => color_hex(color),
+    }
+}
+
+const fn tmux_ansi_color(color: AnsiColor) -> &'static str {
+    match color {
+        AnsiColor::Black => "black",
+        AnsiColor::Red => "red",
+        AnsiColor::Green => "green",
+        AnsiColor::Yellow => "yellow",
+        AnsiColor::Blue => "blue",
+        AnsiColor::Magenta => "magenta",
+        AnsiColor::Cyan => "cyan",
+        AnsiColor::White => "white",
+        AnsiColor::BrightBlack => "brightblack",
+        AnsiColor::BrightRed => "brightred",
+        AnsiColor::BrightGreen => "brightgreen",
+        AnsiColor::BrightYellow => "brightyellow",
+        AnsiColor::BrightBlue => "brightblue",
+        AnsiColor::BrightMagenta => "brightmagenta",
+        AnsiColor::BrightCyan => "brightcyan",
+        AnsiColor::BrightWhite => "brightwhite",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(ColorSyntax::Plain, "22°C" ; "plain")]
+    #[test_case(ColorSyntax::Ansi(ColorDepth::Ansi16), "\x1b[33m22°C\x1b[0m" ; "ansi")]
+    #[test_case(ColorSyntax::Polybar, "%{F#cdcd00}22°C%{F-}" ; "polybar")]
+    #[test_case(ColorSyntax::Tmux, "#[fg=yellow]22°C#[default]" ; "tmux")]
+    fn test_paint(syntax: ColorSyntax, expected: &str) {
+        assert_eq!(syntax.paint("22°C", AnsiColor::Yellow), expected);
+    }
+
+    #[test_case(AnsiColor::Yellow, "#cdcd00" ; "normal")]
+    #[test_case(AnsiColor::BrightBlue, "#5c5cff" ; "bright")]
+    fn test_hex_color(color: AnsiColor, expected: &str) {
+        assert_eq!(hex_color(color), expected);
+    }
+
+    #[test_case(3, "#cdcd00" ; "system")]
+    #[test_case(226, "#ffff00" ; "cube")]
+    #[test_case(111, "#87afff" ; "cube mixed")]
+    #[test_case(240, "#585858" ; "grayscale")]
+    fn test_xterm_color(index: u8, expected: &str) {
+        assert_eq!(xterm_color(index), expected);
+    }
+
+    #[test]
+    fn test_paint_escapes_status_bar_syntax() {
+        assert_eq!(
+            ColorSyntax::Polybar.paint("100%", AnsiColor::Blue),
+            "%{F#0000ee}100%%%{F-}"
+        );
+        assert_eq!(
+            ColorSyntax::Tmux.paint("#1", AnsiColor::Blue),
+            "#[fg=blue]##1#[default]"
+        );
+    }
+
+    #[test_case(ColorSyntax::Plain, "100% #1" ; "plain")]
+    #[test_case(ColorSyntax::Ansi(ColorDepth::Ansi16), "100% #1" ; "ansi")]
+    #[test_case(ColorSyntax::Polybar, "100%% #1" ; "polybar")]
+    #[test_case(ColorSyntax::Tmux, "100% ##1" ; "tmux")]
+    fn test_escape(syntax: ColorSyntax, expected: &str) {
+        assert_eq!(syntax.escape("100% #1"), expected);
+    }
+
+    #[test_case(Some("truecolor"), Some("xterm-256color"), ColorDepth::TrueColor ; "truecolor")]
+    #[test_case(Some("24bit"), None, ColorDepth::TrueColor ; "24bit")]
+    #[test_case(None, Some("xterm-256color"), ColorDepth::Ansi256 ; "256 colors")]
+    #[test_case(None, Some("xterm"), ColorDepth::Ansi16 ; "16 colors")]
+    #[test_case(None, None, ColorDepth::Ansi16 ; "unknown")]
+    fn test_color_depth_from_env(
+        colorterm: Option<&str>,
+        term: Option<&str>,
+        expected: ColorDepth,
+    ) {
+        assert_eq!(ColorDepth::from_env(colorterm, term), expected);
+    }
+
+    #[test_case(ColorDepth::TrueColor, "\x1b[38;2;235;203;139mx\x1b[0m" ; "truecolor")]
+    #[test_case(ColorDepth::Ansi256, "\x1b[38;5;186mx\x1b[0m" ; "256 colors")]
+    #[test_case(ColorDepth::Ansi16, "\x1b[37mx\x1b[0m" ; "16 colors")]
+    fn test_paint_rgb(depth: ColorDepth, expected: &str) {
+        assert_eq!(
+            ColorSyntax::Ansi(depth).paint("x", Color::Rgb(0xeb, 0xcb, 0x8b)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_paint_rgb_status_bars() {
+        let color = Color::Rgb(0x88, 0xc0, 0xd0);
+        assert_eq!(ColorSyntax::Polybar.paint("x", color), "%{F#88c0d0}x%{F-}");
+        assert_eq!(
+            ColorSyntax::Tmux.paint("x", color),
+            "#[fg=#88c0d0]x#[default]"
+        );
+    }
+
+    #[test_case(0xcd, 0x00, 0x00, AnsiColor::Red ; "exact")]
+    #[test_case(0xdc, 0x32, 0x2f, AnsiColor::Red ; "close")]
+    #[test_case(0x10, 0x10, 0x10, AnsiColor::Black ; "dark")]
+    fn test_nearest_ansi(r: u8, g: u8, b: u8, expected: AnsiColor) {
+        assert_eq!(nearest_ansi(r, g, b), expected);
+    }
+
+    #[test_case(ColorMode::Auto, &[], true, true ; "auto terminal")]
+    #[test_case(ColorMode::Auto, &[], false, false ; "auto pipe")]
+    #[test_case(ColorMode::Auto, &[("NO_COLOR", "1")], true, false ; "no color")]
+    #[test_case(ColorMode::Auto, &[("NO_COLOR", "")], true, true ; "empty no color")]
+    #[test_case(ColorMode::Auto, &[("CLICOLOR_FORCE", "1")], false, true ; "clicolor force")]
+    #[test_case(ColorMode::Auto, &[("CLICOLOR_FORCE", "0")], false, false ; "clicolor force off")]
+    #[test_case(ColorMode::Auto, &[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], true, false ; "no color wins")]
+    #[test_case(ColorMode::Auto, &[("TERM", "dumb")], true, false ; "dumb terminal")]
+    #[test_case(ColorMode::Always, &[("NO_COLOR", "1")], false, true ; "always")]
+    #[test_case(ColorMode::Never, &[("CLICOLOR_FORCE", "1")], true, false ; "never")]
+    fn test_color_mode_resolve(
+        mode: ColorMode,
+        env: &[(&str, &str)],
+        is_terminal: bool,
+        expected: bool,
+    ) {
+        let env = |name: &str| {
+            env.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value).to_string())
+        };
+        assert_eq!(mode.resolve(env, is_terminal), expected);
+    }
+}
@@ -1,55 +1,219 @@
 use crate::config::{Config, FormatterConfig};
-use crate::display::color::colored_text;
-use crate::display::theme::condition_color;
+use crate::display::chart::{ChartStyle, bounds, chart_lines, sparkline};
+use crate::display::color::{ColorDepth, ColorSyntax, color_hex, hex_color};
+use crate::display::html::{Card, CardRow};
+use crate::display::icon_pack::IconPack;
+use crate::display::icons::{WIND_DIRECTION_GLYPHS, field_glyph, fit_rows};
+use crate::display::prometheus;
+use crate::display::records::{CSV_HEADER, csv_row, ndjson_line};
+use crate::display::status_bar::{I3BAR_HEADER, I3barBlock, WaybarOutput, condition_class};
+use crate::display::svg::render_svg;
+use crate::display::template::{Field, Template};
+use crate::display::theme::{
+    aqi_color, condition_color, temperature_gradient, uv_index_color, uv_index_gradient,
+    wind_gradient,
+};
 use crate::display::translations::ll;
+use crate::display::width::{pad, terminal_width, text_width, visible_width};
 use crate::errors::RustormyError;
-use crate::models::{AnsiColor, Language, OutputFormat, TextMode, Units, Weather};
-use std::fmt::Display;
+use crate::models::{
+    Color, ColorMode, ForecastHour, IconStyle, Language, Layout, OutputFormat, TextField, TextMode,
+    Units, Weather,
+};
+use crate::weather::sun;
+use chrono::{Local, Utc};
+use std::fmt::{Display, Write};
+
+/// Label, value and color of a line in full/compact text mode
+type TextLine = (&'static str, String, Color);
+
+/// Rows of the HTML card, every value that is available
+const CARD_FIELDS: [TextField; 10] = [
+    TextField::Condition,
+    TextField::Temperature,
+    TextField::Wind,
+    TextField::Precipitation,
+    TextField::Pressure,
+    TextField::Humidity,
+    TextField::UvIndex,
+    TextField::Aqi,
+    TextField::Sunrise,
+    TextField::Sunset,
+];
+
+/// Lines shown beside full and compact mode output when the terminal is wide enough
+const EXTRA_FIELDS: [TextField; 3] = [TextField::Sunrise, TextField::Sunset, TextField::Aqi];
+
+/// Space between the main lines and the extra fields beside them
+const COLUMN_GAP: &str = "   ";
+
+/// Columns of the comparison table of several locations, right-aligned if they are numbers
+const TABLE_COLUMNS: [(&str, bool); 6] = [
+    ("Location", false),
+    ("Condition", false),
+    ("Temperature", true),
+    ("Wind", false),
+    ("Precipitation", true),
+    ("Humidity", true),
+];
+
+/// Weather of one of several locations, or why it couldn't be fetched, with the formatting
+/// settings of that location
+pub struct LocationWeather {
+    /// Name the location was given
+    pub name: String,
+    pub format: FormatterConfig,
+    pub weather: Result<Weather, RustormyError>,
+}
 
 pub struct WeatherFormatter {
     config: FormatterConfig,
+    template: Option<Template>,
+    icon_pack: IconPack,
+    /// Configured location, for output that has to name it without weather data
+    location_name: String,
+    color_depth: ColorDepth,
+    /// Terminal columns full and compact mode are fitted to, `None` when unknown
+    width: Option<usize>,
 }
 
 fn make_line(
     i: &str,
     l: &'static str,
+    label_width: usize,
     value: impl Display,
-    color: AnsiColor,
+    color: Color,
+    colors: ColorSyntax,
     config: &FormatterConfig,
 ) -> String {
-    let value = if config.use_colors {
-        colored_text(value, color)
+    let value = colors.paint(value, color);
+    let i = if i.is_empty() {
+        String::new()
     } else {
-        value.to_string()
+        format!("{i} ")
     };
 
     if config.text_mode == TextMode::Compact {
-        format!("{i} {value}")
+        format!("{i}{value}")
     } else {
-        format!("{i} {} {value}", label(l, config))
+        format!("{i}{} {value}", label(l, label_width, colors, config))
     }
 }
 
-fn label(text: &'static str, config: &FormatterConfig) -> String {
-    let lang = config.language;
-    let width = lang.label_width();
-    let translated = ll(lang, text).to_string() + ":";
-    let padded = if config.align_right {
-        format!("{translated:>width$}")
-    } else {
-        format!("{translated:<width$}")
-    };
-    if config.use_colors {
-        colored_text(padded, config.color_theme.label)
-    } else {
-        padded
+/// Terminal columns taken by the widest of `lines`
+fn max_width(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .map(|line| visible_width(line))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Blocks of lines side by side, wrapped onto more rows of blocks when wider than `width`
+fn side_by_side(blocks: &[Vec<String>], width: Option<usize>) -> String {
+    let mut rows = Vec::new();
+    let (mut start, mut row_width) = (0, 0);
+    for (i, block) in blocks.iter().enumerate() {
+        let block_width = max_width(block);
+        if i == start {
+            row_width = block_width;
+        } else if width.is_some_and(|width| row_width + COLUMN_GAP.len() + block_width > width) {
+            rows.push(&blocks[start..i]);
+            (start, row_width) = (i, block_width);
+        } else {
+            row_width += COLUMN_GAP.len() + block_width;
+        }
     }
+    rows.push(&blocks[start..]);
+
+    let rows: Vec<String> = rows
+        .into_iter()
+        .map(|row| {
+            let widths: Vec<usize> = row.iter().map(|block| max_width(block)).collect();
+            let height = row.iter().map(Vec::len).max().unwrap_or(0);
+            (0..height).fold(String::new(), |mut output, line| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(block, &width)| {
+                        pad(block.get(line).map_or("", String::as_str), width, false)
+                    })
+                    .collect();
+                let _ = writeln!(output, "{}", cells.join(COLUMN_GAP).trim_end());
+                output
+            })
+        })
+        .collect();
+    rows.join("\n")
+}
+
+/// Translated label with its colon
+fn label_text(text: &'static str, lang: Language) -> String {
+    ll(lang, text).to_string() + ":"
+}
+
+/// Terminal columns of the widest of `labels`, translated
+fn label_width(labels: impl IntoIterator<Item = &'static str>, lang: Language) -> usize {
+    labels
+        .into_iter()
+        .map(|label| text_width(&label_text(label, lang)))
+        .max()
+        .unwrap_or(0)
+}
+
+fn label(
+    text: &'static str,
+    width: usize,
+    colors: ColorSyntax,
+    config: &FormatterConfig,
+) -> String {
+    let padded = pad(
+        &label_text(text, config.language),
+        width,
+        config.align_right,
+    );
+    colors.paint(padded, config.color_theme().label)
 }
 
-const fn wind_deg_to_symbol(deg: u16) -> &'static str {
+/// Arrow pointing where the wind blows to, a Nerd Font glyph with `IconStyle::Nerdfont`
+const fn wind_deg_to_symbol(deg: u16, icons: IconStyle) -> &'static str {
     let symbols = ["↓", "↙", "←", "↖", "↑", "↗", "→", "↘"];
     let index = ((deg as f32 + 22.5) / 45.0) as usize % 8;
-    symbols[index]
+    if matches!(icons, IconStyle::Nerdfont) {
+        WIND_DIRECTION_GLYPHS[index]
+    } else {
+        symbols[index]
+    }
+}
+
+/// Translation key of the direction the wind comes from, for sentence mode
+const fn wind_direction_key(deg: u16) -> &'static str {
+    let keys = [
+        "from N", "from NE", "from E", "from SE", "from S", "from SW", "from W", "from NW",
+    ];
+    let index = ((deg as f32 + 22.5) / 45.0) as usize % 8;
+    keys[index]
+}
+
+/// Lowercase the first letter of a description to use it mid-sentence, unless it starts an acronym
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(first), second) if !second.is_some_and(char::is_uppercase) => {
+            first.to_lowercase().chain(text.chars().skip(1)).collect()
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Whole number for reading aloud, without a "-0"
+fn spoken_number(value: f64) -> String {
+    let rounded = value.round();
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        format!("{rounded}")
+    }
 }
 
 fn unit_strings(units: Units, lang: Language) -> (&'static str, &'static str, &'static str) {
@@ -59,21 +223,53 @@ fn unit_strings(units: Units, lang: Language) -> (&'static str, &'static str, &'
     }
 }
 
-fn format_wind_value(speed: f64, direction: u16, unit: &str, in_degrees: bool) -> String {
-    if in_degrees {
+/// Marker appended to readings served from the weather cache after every provider failed
+fn stale_marker(weather: &Weather, lang: Language) -> Option<String> {
+    weather.stale_since.map(|fetched_at| {
+        format!(
+            "[{} {}]",
+            ll(lang, "stale"),
+            fetched_at.with_timezone(&Local).format("%H:%M")
+        )
+    })
+}
+
+fn format_wind_value(speed: f64, direction: u16, unit: &str, config: &FormatterConfig) -> String {
+    if config.wind_in_degrees {
         format!("{speed:.1} {unit} {direction}°")
     } else {
-        format!("{speed:.1} {unit} {}", wind_deg_to_symbol(direction))
+        format!(
+            "{speed:.1} {unit} {}",
+            wind_deg_to_symbol(direction, config.icons)
+        )
     }
 }
 
 impl WeatherFormatter {
     pub fn new(config: &Config) -> Self {
+        // The template was already checked by `Config::validate`
+        let template = config
+            .format()
+            .template
+            .as_deref()
+            .and_then(|template| Template::parse(template).ok());
+        // So was the icon pack, if it is shown
+        let icon_pack = IconPack::load(&config.format().icon_pack).unwrap_or_default();
         Self {
             config: config.format().clone(),
+            template,
+            icon_pack,
+            location_name: config.location_name(),
+            color_depth: ColorDepth::detect(),
+            width: terminal_width(),
         }
     }
 
+    /// Fit full and compact mode to `width` columns from now on, e.g. after the terminal was resized
+    pub fn set_width(&mut self, width: usize) {
+        self.width = Some(width);
+    }
+
     pub fn display(&self, weather: &Weather) {
         print!("{}", self.render_to_string(weather));
     }
@@ -82,162 +278,996 @@ impl WeatherFormatter {
         match self.config.output_format {
             OutputFormat::Json => self.render_json(weather),
             OutputFormat::Text => self.render_text(weather),
+            OutputFormat::Waybar => self.render_waybar(weather),
+            OutputFormat::I3bar => format!("{}\n", self.render_i3bar_block(weather)),
+            OutputFormat::Polybar | OutputFormat::Tmux | OutputFormat::Starship => {
+                format!("{}\n", self.format_status_line(weather))
+            }
+            OutputFormat::Prometheus => {
+                prometheus::render_metrics(weather, &self.location_name, self.config.units)
+            }
+            OutputFormat::Csv => {
+                format!("{}{}", self.stream_header(), self.render_csv_row(weather))
+            }
+            OutputFormat::Ndjson => self.render_ndjson(weather),
+            OutputFormat::Html => self.render_html(weather),
+            OutputFormat::Svg => self.render_svg(weather),
+            OutputFormat::Markdown => self.render_markdown(weather),
+        }
+    }
+
+    /// Output of several locations, in the order they were given. Text output is arranged as
+    /// configured by `layout`, JSON is an array of the weather or the error of each location.
+    pub fn render_many(&self, results: &[LocationWeather]) -> String {
+        let fetched = || {
+            results.iter().filter_map(|location| {
+                Some((self.for_location(location), location.weather.as_ref().ok()?))
+            })
+        };
+        match self.config.output_format {
+            OutputFormat::Json => self.render_json_many(results),
+            OutputFormat::Prometheus => {
+                let outputs: Vec<String> = results
+                    .iter()
+                    .map(|location| {
+                        let units = location.format.units;
+                        match &location.weather {
+                            Ok(weather) => {
+                                prometheus::render_metrics(weather, &location.name, units)
+                            }
+                            Err(_) => prometheus::render_failure(&location.name, units),
+                        }
+                    })
+                    .collect();
+                prometheus::merge(outputs.iter().map(String::as_str))
+            }
+            OutputFormat::Csv => fetched()
+                .fold(self.stream_header(), |output, (formatter, weather)| {
+                    output + &formatter.render_csv_row(weather)
+                }),
+            OutputFormat::Text => match self.config.layout {
+                Layout::Stacked => {
+                    let outputs: Vec<String> = fetched()
+                        .map(|(formatter, weather)| formatter.render_text(weather))
+                        .collect();
+                    // Views of more than one line are told apart by a blank line
+                    let separator = if outputs.iter().any(|output| output.lines().count() > 1) {
+                        "\n"
+                    } else {
+                        ""
+                    };
+                    outputs.join(separator)
+                }
+                Layout::Columns => {
+                    let blocks: Vec<Vec<String>> = fetched()
+                        .map(|(mut formatter, weather)| {
+                            // Every column gets the lines it would have on its own
+                            formatter.width = None;
+                            formatter
+                                .render_text(weather)
+                                .lines()
+                                .map(String::from)
+                                .collect()
+                        })
+                        .collect();
+                    side_by_side(&blocks, self.width.filter(|_| self.config.responsive))
+                }
+                Layout::Table => self.render_table(&fetched().collect::<Vec<_>>()),
+            },
+            OutputFormat::Ndjson => fetched()
+                .map(|(formatter, weather)| formatter.render_ndjson(weather))
+                .collect(),
+            // Markdown sections are told apart by a blank line, the other formats show a single
+            // location (see `Config::validate`)
+            _ => fetched()
+                .map(|(formatter, weather)| formatter.render_to_string(weather))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    /// Print the output of several locations and the errors of those that failed, exiting with
+    /// an error status if any did
+    pub fn display_many(&self, results: &[LocationWeather]) {
+        print!("{}", self.render_many(results));
+        let mut failed = false;
+        for location in results {
+            if let Err(error) = &location.weather {
+                failed = true;
+                // JSON output has the errors in its array
+                if self.config.output_format != OutputFormat::Json {
+                    self.print_error(format!("{}: {error}", location.name));
+                }
+            }
+        }
+        if failed {
+            std::process::exit(1);
+        }
+    }
+
+    /// Formatter of one of several locations, with its own settings and named in its output
+    fn for_location(&self, location: &LocationWeather) -> Self {
+        Self {
+            location_name: location.name.clone(),
+            width: self.width,
+            ..self.with_format(FormatterConfig {
+                show_city_name: true,
+                ..location.format.clone()
+            })
+        }
+    }
+
+    /// Formatter with the same template and location, for rendering with a different configuration
+    fn with_format(&self, config: FormatterConfig) -> Self {
+        Self {
+            config,
+            template: self.template.clone(),
+            icon_pack: self.icon_pack.clone(),
+            location_name: self.location_name.clone(),
+            color_depth: self.color_depth,
+            // Not written to the terminal as it is
+            width: None,
+        }
+    }
+
+    /// Output written once before the updates when a format is streamed in live mode
+    pub fn stream_header(&self) -> String {
+        match self.config.output_format {
+            OutputFormat::I3bar => I3BAR_HEADER.to_string(),
+            OutputFormat::Csv if self.config.csv_header => format!("{CSV_HEADER}\n"),
+            _ => String::new(),
+        }
+    }
+
+    /// Output written on every refresh when a format is streamed in live mode
+    pub fn render_stream_update(&self, weather: &Weather) -> String {
+        match self.config.output_format {
+            OutputFormat::I3bar => format!("[{}],\n", self.render_i3bar_block(weather)),
+            OutputFormat::Csv => self.render_csv_row(weather),
+            _ => self.render_to_string(weather),
+        }
+    }
+
+    fn render_csv_row(&self, weather: &Weather) -> String {
+        format!("{}\n", csv_row(weather, self.config.units, Utc::now()))
+    }
+
+    fn render_ndjson(&self, weather: &Weather) -> String {
+        let line = ndjson_line(weather, self.config.units, Utc::now()).unwrap_or_else(|e| {
+            self.display_error(&RustormyError::JsonSerializeError(e));
+        });
+        format!("{line}\n")
+    }
+
+    /// Color escapes used for values in one-line and template output
+    fn color_syntax(&self) -> ColorSyntax {
+        match self.config.output_format {
+            OutputFormat::Text if self.config.use_colors() => ColorSyntax::Ansi(self.color_depth),
+            OutputFormat::Polybar => ColorSyntax::Polybar,
+            OutputFormat::Tmux => ColorSyntax::Tmux,
+            _ => ColorSyntax::Plain,
         }
     }
 
     pub fn display_error(&self, error: &RustormyError) -> ! {
-        if self.config.output_format == OutputFormat::Json {
+        if self.config.output_format == OutputFormat::Prometheus {
+            // Scrapers still get a `rustormy_fetch_success 0` sample
+            print!(
+                "{}",
+                prometheus::render_failure(&self.location_name, self.config.units)
+            );
+        }
+        self.print_error(error);
+        std::process::exit(1);
+    }
+
+    /// Error message on stderr, as JSON for formats read by other programs
+    fn print_error(&self, error: impl Display) {
+        if matches!(
+            self.config.output_format,
+            OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Waybar | OutputFormat::I3bar
+        ) {
             let error_json = serde_json::json!({ "error": format!("{}", error) });
             eprintln!("{error_json}");
         } else {
             eprintln!("Error: {error}");
         }
-        std::process::exit(1);
     }
 
     fn render_text(&self, weather: &Weather) -> String {
-        if self.config.text_mode == TextMode::OneLine {
-            return format!("{}\n", self.format_one_line(weather));
+        if let Some(template) = &self.template {
+            return format!("{}\n", self.format_template(template, weather));
         }
         let mut s = String::new();
-        for line in self.format_text(weather) {
-            s.push_str(&line);
-            s.push('\n');
+        match self.config.text_mode {
+            TextMode::OneLine => s = format!("{}\n", self.format_one_line(weather)),
+            TextMode::Sentence => return format!("{}\n", self.format_sentence(weather)),
+            TextMode::Full | TextMode::Compact => {
+                for line in self.format_text(weather) {
+                    s.push_str(&line);
+                    s.push('\n');
+                }
+            }
         }
+        s.push_str(&self.render_chart(weather));
         s
     }
 
+    /// Charts of the hourly forecast after a blank line, when enabled and there is a forecast
+    fn render_chart(&self, weather: &Weather) -> String {
+        if !self.config.chart {
+            return String::new();
+        }
+        let (temperature_unit, _, precipitation_unit) =
+            unit_strings(self.config.units, self.config.language);
+        let style = ChartStyle {
+            language: self.config.language,
+            temperature_unit,
+            precipitation_unit,
+            colors: self.color_syntax(),
+            theme: self.config.color_theme(),
+        };
+        let hours = &weather.hourly[..weather.hourly.len().min(self.config.chart_hours.into())];
+        let width = self.width.filter(|_| self.config.responsive);
+        let lines = chart_lines(hours, &style, width, &Local);
+        if lines.is_empty() {
+            return String::new();
+        }
+        format!("\n{}\n", lines.join("\n"))
+    }
+
     fn format_one_line(&self, weather: &Weather) -> String {
-        let color_theme = &self.config.color_theme;
+        let color_theme = self.config.color_theme();
+        let colors = self.color_syntax();
         let (temp_unit, wind_unit, _) = unit_strings(self.config.units, self.config.language);
-        let emoji = weather.icon.emoji(weather.is_day.unwrap_or(true));
-        let mut temperature = format!("{:.1}{}", weather.temperature, temp_unit);
-        if self.config.use_colors {
-            temperature = colored_text(temperature, color_theme.temperature);
-        }
-        let wind = format_wind_value(
-            weather.wind_speed,
-            weather.wind_direction,
-            wind_unit,
-            self.config.wind_in_degrees,
-        );
-        let wind = if self.config.use_colors {
-            colored_text(wind, color_theme.wind)
+        // Symbols keep their padding to two cells here, as in the text modes
+        let symbol = match weather
+            .icon
+            .symbol(weather.is_day.unwrap_or(true), self.config.icons)
+        {
+            "" => String::new(),
+            symbol => format!("{symbol} "),
+        };
+        let temperature = colors.paint(
+            format!("{:.1}{}", weather.temperature, temp_unit),
+            self.temperature_color(weather.temperature),
+        );
+        let wind = colors.paint(
+            format_wind_value(
+                weather.wind_speed,
+                weather.wind_direction,
+                wind_unit,
+                &self.config,
+            ),
+            self.wind_color(weather.wind_speed),
+        );
+        let wind = if self.config.icons == IconStyle::Nerdfont {
+            format!("{}{wind}", field_glyph(TextField::Wind))
         } else {
             wind
         };
-        let value = format!("{emoji} {temperature} {wind}");
+        let mut value = format!("{symbol}{temperature} {wind}");
+        if self.config.sparkline {
+            let trends = [
+                self.temperature_trend(weather),
+                self.precipitation_trend(weather),
+            ];
+            for (trend, color) in trends.into_iter().flatten() {
+                value = format!("{value} {}", colors.paint(trend, color));
+            }
+        }
+        if let Some(marker) = stale_marker(weather, self.config.language) {
+            value = format!("{value} {marker}");
+        }
 
         if self.config.show_city_name {
-            let location = if self.config.use_colors {
-                colored_text(&weather.location.name, color_theme.location)
-            } else {
-                weather.location.name.clone()
-            };
+            let location = colors.paint(&weather.location.name, color_theme.location);
             format!("{location}: {value}")
         } else {
             value
         }
     }
 
+    /// Hours of the forecast shown in sparklines, `None` if there are too few for a trend
+    fn sparkline_hours<'a>(&self, weather: &'a Weather) -> Option<&'a [ForecastHour]> {
+        let count = weather.hourly.len().min(self.config.sparkline_hours.into());
+        (count > 1).then(|| &weather.hourly[..count])
+    }
+
+    /// Sparkline of the temperature in the coming hours and its range, e.g. `▁▃▅█▆ 12–24°C`
+    fn temperature_trend(&self, weather: &Weather) -> Option<(String, Color)> {
+        let (temp_unit, _, _) = unit_strings(self.config.units, self.config.language);
+        let values: Vec<f64> = self
+            .sparkline_hours(weather)?
+            .iter()
+            .map(|hour| hour.temperature)
+            .collect();
+        let (min, max) = bounds(&values);
+        Some((
+            format!(
+                "{} {min:.0}–{max:.0}{temp_unit}",
+                sparkline(&values, min, max)
+            ),
+            self.config.color_theme().temperature,
+        ))
+    }
+
+    /// Sparkline of the chance of precipitation in the coming hours and its range, e.g. `▁▁▃▇▅ 0–80%`
+    fn precipitation_trend(&self, weather: &Weather) -> Option<(String, Color)> {
+        let values: Vec<f64> = self
+            .sparkline_hours(weather)?
+            .iter()
+            .map(|hour| hour.precipitation_probability.map(f64::from))
+            .collect::<Option<_>>()?;
+        let (min, max) = bounds(&values);
+        Some((
+            format!("{} {min:.0}–{max:.0}%", sparkline(&values, 0.0, 100.0)),
+            self.config.color_theme().precipitation,
+        ))
+    }
+
+    fn format_sentence(&self, weather: &Weather) -> String {
+        let lang = self.config.language;
+        let (temp_unit, wind_unit, _) = unit_strings(self.config.units, lang);
+        let key = if self.config.show_city_name && !weather.location.name.is_empty() {
+            "sentence with location"
+        } else {
+            "sentence"
+        };
+        let mut sentence = ll(lang, key)
+            .replace(
+                "{temperature}",
+                &format!("{} {temp_unit}", spoken_number(weather.temperature)),
+            )
+            .replace(
+                "{feels_like}",
+                &format!("{} {temp_unit}", spoken_number(weather.feels_like)),
+            )
+            .replace(
+                "{wind}",
+                &format!("{} {wind_unit}", spoken_number(weather.wind_speed)),
+            )
+            .replace(
+                "{direction}",
+                ll(lang, wind_direction_key(weather.wind_direction)),
+            )
+            .replace("{condition}", &lowercase_first(&weather.description))
+            .replace("{location}", &weather.location.name);
+        if let Some(marker) = stale_marker(weather, lang) {
+            sentence = format!("{sentence} {marker}");
+        }
+        sentence
+    }
+
+    /// Condition symbol followed by a space, empty without icons
+    fn condition_symbol(&self, weather: &Weather) -> String {
+        let symbol = weather
+            .icon
+            .symbol(weather.is_day.unwrap_or(true), self.config.icons)
+            .trim_end();
+        if symbol.is_empty() {
+            String::new()
+        } else {
+            format!("{symbol} ")
+        }
+    }
+
+    /// Condition symbol and rounded temperature, for places with little room
+    fn format_short(&self, weather: &Weather) -> String {
+        let (temp_unit, _, _) = unit_strings(self.config.units, self.config.language);
+        format!(
+            "{}{:.0}{temp_unit}",
+            self.condition_symbol(weather),
+            weather.temperature
+        )
+    }
+
+    /// Single line shown by status bars: the template if set, the one-line view otherwise
+    fn format_status_line(&self, weather: &Weather) -> String {
+        if let Some(template) = &self.template {
+            return self.format_template(template, weather);
+        }
+        if self.config.output_format == OutputFormat::Starship {
+            let mut line = self.format_short(weather);
+            if let Some(marker) = stale_marker(weather, self.config.language) {
+                line = format!("{line} {marker}");
+            }
+            return line;
+        }
+        self.format_one_line(weather)
+    }
+
+    fn render_waybar(&self, weather: &Weather) -> String {
+        // The tooltip is the full (or compact) text view without terminal colors
+        let tooltip_formatter = self.with_format(FormatterConfig {
+            colors: ColorMode::Never,
+            text_mode: match self.config.text_mode {
+                TextMode::OneLine => TextMode::Full,
+                mode => mode,
+            },
+            ..self.config.clone()
+        });
+        let tooltip = tooltip_formatter
+            .format_text(weather)
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_matches('\n')
+            .to_string();
+        let class = condition_class(weather.icon);
+        let mut classes = vec![class];
+        if weather.stale_since.is_some() {
+            classes.push("stale");
+        }
+        let output = WaybarOutput {
+            text: self.format_status_line(weather),
+            tooltip,
+            class: classes,
+            alt: class,
+        };
+        let json = serde_json::to_string(&output).unwrap_or_else(|e| {
+            self.display_error(&RustormyError::JsonSerializeError(e));
+        });
+        format!("{json}\n")
+    }
+
+    /// Translated label, value and color of every available value, for cards and tables
+    fn card_lines(&self, weather: &Weather) -> Vec<(String, String, Color)> {
+        let lang = self.config.language;
+        // Every value gets its own row, so the UV index is not repeated in the condition
+        let card_formatter = self.with_format(FormatterConfig {
+            fields: CARD_FIELDS.to_vec(),
+            ..self.config.clone()
+        });
+        CARD_FIELDS
+            .iter()
+            .filter_map(|&field| card_formatter.text_line(field, weather))
+            .map(|(label, value, color)| (ll(lang, label).to_string(), value, color))
+            .collect()
+    }
+
+    /// Location shown as the title of cards and tables
+    fn title<'a>(&'a self, weather: &'a Weather) -> &'a str {
+        if weather.location.name.is_empty() {
+            &self.location_name
+        } else {
+            &weather.location.name
+        }
+    }
+
+    fn render_html(&self, weather: &Weather) -> String {
+        let color_theme = self.config.color_theme();
+        let rows = self
+            .card_lines(weather)
+            .into_iter()
+            .map(|(label, value, color)| CardRow {
+                label,
+                value,
+                color: color_hex(color),
+            })
+            .collect();
+        Card {
+            lang: self.config.language.code(),
+            title: self.title(weather),
+            title_color: color_hex(color_theme.location),
+            label_color: color_hex(color_theme.label),
+            emoji: weather
+                .icon
+                .emoji(weather.is_day.unwrap_or(true))
+                .trim_end(),
+            class: condition_class(weather.icon),
+            rows,
+        }
+        .render()
+    }
+
+    /// Heading with the location and a two-column table of field names and values
+    fn render_markdown(&self, weather: &Weather) -> String {
+        let lang = self.config.language;
+        let cell = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+        let emoji = weather
+            .icon
+            .emoji(weather.is_day.unwrap_or(true))
+            .trim_end();
+        let mut markdown = format!("### {emoji} {}\n\n", cell(self.title(weather)));
+        let _ = writeln!(
+            markdown,
+            "| {} | {} |\n| --- | --- |",
+            ll(lang, "Field"),
+            ll(lang, "Value")
+        );
+        for (label, value, _) in self.card_lines(weather) {
+            let _ = writeln!(markdown, "| {} | {} |", cell(&label), cell(&value));
+        }
+        markdown
+    }
+
+    /// The text output as it looks in a terminal with colors
+    fn render_svg(&self, weather: &Weather) -> String {
+        let mut formatter = self.with_format(FormatterConfig {
+            output_format: OutputFormat::Text,
+            colors: ColorMode::Always,
+            ..self.config.clone()
+        });
+        // Images show theme colors exactly, whatever the terminal supports
+        formatter.color_depth = ColorDepth::TrueColor;
+        let text = formatter.render_text(weather);
+        render_svg(&text.lines().collect::<Vec<_>>())
+    }
+
+    fn render_i3bar_block(&self, weather: &Weather) -> String {
+        let block = I3barBlock {
+            name: "rustormy",
+            full_text: self.format_status_line(weather),
+            short_text: self.format_short(weather),
+            color: hex_color(condition_color(weather.icon)),
+        };
+        serde_json::to_string(&block).unwrap_or_else(|e| {
+            self.display_error(&RustormyError::JsonSerializeError(e));
+        })
+    }
+
+    fn format_template(&self, template: &Template, weather: &Weather) -> String {
+        let lang = self.config.language;
+        let color_theme = self.config.color_theme();
+        let (temp_unit, wind_unit, precip_unit) = unit_strings(self.config.units, lang);
+        let mut output = template.render(self.color_syntax(), |field| {
+            let (value, color) = match field {
+                Field::ConditionIcon => {
+                    let symbol = weather
+                        .icon
+                        .symbol(weather.is_day.unwrap_or(true), self.config.icons)
+                        .trim_end();
+                    if symbol.is_empty() {
+                        return None;
+                    }
+                    (symbol.to_string(), None)
+                }
+                Field::Condition => (
+                    weather.description.clone(),
+                    Some(condition_color(weather.icon).into()),
+                ),
+                Field::Temperature => (
+                    format!("{:.1}{temp_unit}", weather.temperature),
+                    Some(self.temperature_color(weather.temperature)),
+                ),
+                Field::FeelsLike => (
+                    format!("{:.1}{temp_unit}", weather.feels_like),
+                    Some(self.temperature_color(weather.feels_like)),
+                ),
+                Field::Humidity => (format!("{}%", weather.humidity), Some(color_theme.humidity)),
+                Field::Wind => (
+                    format_wind_value(
+                        weather.wind_speed,
+                        weather.wind_direction,
+                        wind_unit,
+                        &self.config,
+                    ),
+                    Some(self.wind_color(weather.wind_speed)),
+                ),
+                Field::Precipitation => (
+                    format!("{} {precip_unit}", weather.precipitation),
+                    Some(color_theme.precipitation),
+                ),
+                Field::Pressure => (
+                    format!("{} {}", weather.pressure, ll(lang, "hPa")),
+                    Some(color_theme.pressure),
+                ),
+                Field::UvIndex if self.config.gradients => {
+                    let uv = weather.uv_index?;
+                    (format!("{uv:.1}"), Some(uv_index_gradient(uv)))
+                }
+                Field::UvIndex => (format!("{:.1}", weather.uv_index?), None),
+                Field::Location if weather.location.name.is_empty() => return None,
+                Field::Location => (weather.location.name.clone(), Some(color_theme.location)),
+                Field::TemperatureTrend => {
+                    let (trend, color) = self.temperature_trend(weather)?;
+                    (trend, Some(color))
+                }
+                Field::PrecipitationTrend => {
+                    let (trend, color) = self.precipitation_trend(weather)?;
+                    (trend, Some(color))
+                }
+            };
+            Some((value, color))
+        });
+        if let Some(marker) = stale_marker(weather, lang) {
+            output = format!("{output} {marker}");
+        }
+        output
+    }
+
+    /// Full/compact mode lines fitted to the terminal: without the icon when they are too wide,
+    /// also without labels when that is not enough, and with extra fields beside them when
+    /// there is room
     fn format_text(&self, weather: &Weather) -> Vec<String> {
-        let (compact, colors, name, lang) = (
-            self.config.text_mode == TextMode::Compact,
-            self.config.use_colors,
-            self.config.show_city_name,
+        let lines = self.text_lines(weather);
+        let Some(width) = self.width.filter(|_| self.config.responsive) else {
+            return lines;
+        };
+        if max_width(&lines) <= width {
+            return self.with_extra_fields(lines, weather, width);
+        }
+        let without_icon = self
+            .with_format(FormatterConfig {
+                icons: IconStyle::None,
+                ..self.config.clone()
+            })
+            .text_lines(weather);
+        if max_width(&without_icon) <= width || self.config.text_mode == TextMode::Compact {
+            return without_icon;
+        }
+        self.with_format(FormatterConfig {
+            icons: IconStyle::None,
+            text_mode: TextMode::Compact,
+            ..self.config.clone()
+        })
+        .text_lines(weather)
+    }
+
+    /// Extra fields not shown yet, in a column right of `lines` if it fits in `width`
+    fn with_extra_fields(
+        &self,
+        lines: Vec<String>,
+        weather: &Weather,
+        width: usize,
+    ) -> Vec<String> {
+        let extra: Vec<TextLine> = EXTRA_FIELDS
+            .into_iter()
+            .filter(|field| !self.config.fields.contains(field))
+            .filter_map(|field| self.text_line(field, weather))
+            .collect();
+        let width_of_labels = label_width(
+            extra.iter().map(|(label, _, _)| *label),
             self.config.language,
         );
-        let (temp_unit, wind_unit, precip_unit) = unit_strings(self.config.units, lang);
+        let extra: Vec<String> = extra
+            .into_iter()
+            .map(|(label, value, color)| {
+                make_line(
+                    "",
+                    label,
+                    width_of_labels,
+                    value,
+                    color,
+                    self.color_syntax(),
+                    &self.config,
+                )
+            })
+            .collect();
+        // Next to the first value, below the row a hidden location keeps in full mode
+        let skip = usize::from(
+            self.config.text_mode == TextMode::Full
+                && !self.config.show_city_name
+                && self.config.fields.first() == Some(&TextField::Location),
+        );
+        let main_width = max_width(&lines);
+        if extra.is_empty()
+            || skip + extra.len() > lines.len()
+            || main_width + COLUMN_GAP.len() + max_width(&extra) > width
+        {
+            return lines;
+        }
+        let mut extra = extra.into_iter().peekable();
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(row, line)| match extra.next_if(|_| row >= skip) {
+                Some(extra) => {
+                    let padding = " ".repeat(main_width - visible_width(&line));
+                    format!("{line}{padding}{COLUMN_GAP}{extra}")
+                }
+                None => line,
+            })
+            .collect()
+    }
+
+    fn text_lines(&self, weather: &Weather) -> Vec<String> {
+        let compact = self.config.text_mode == TextMode::Compact;
+
+        // Fields without a value are skipped. A hidden location still takes its icon row,
+        // left blank in full mode and dropped together with the row in compact mode.
+        let lines: Vec<(TextField, Option<TextLine>)> = self
+            .config
+            .fields
+            .iter()
+            .filter_map(|&field| {
+                if field == TextField::Location && !self.config.show_city_name {
+                    Some((field, None))
+                } else {
+                    self.text_line(field, weather)
+                        .map(|line| (field, Some(line)))
+                }
+            })
+            .collect();
+        let icon_column = self.icon_column(weather, &lines);
+        let width_of_labels = label_width(
+            lines
+                .iter()
+                .filter_map(|(_, line)| line.as_ref().map(|(label, _, _)| *label)),
+            self.config.language,
+        );
+
+        lines
+            .into_iter()
+            .zip(icon_column)
+            .filter_map(|((_, line), i)| {
+                let i = i.as_str();
+                match line {
+                    Some((label, value, color)) => Some(make_line(
+                        i,
+                        label,
+                        width_of_labels,
+                        value,
+                        color,
+                        self.color_syntax(),
+                        &self.config,
+                    )),
+                    None if compact => None,
+                    None => Some(i.to_string()),
+                }
+            })
+            .collect()
+    }
+
+    /// Left column of full/compact mode, one cell per line, all as wide
+    fn icon_column(
+        &self,
+        weather: &Weather,
+        lines: &[(TextField, Option<TextLine>)],
+    ) -> Vec<String> {
+        let cells = self.icon_cells(weather, lines);
+        let width = max_width(&cells);
+        cells.iter().map(|cell| pad(cell, width, false)).collect()
+    }
+
+    fn icon_cells(
+        &self,
+        weather: &Weather,
+        lines: &[(TextField, Option<TextLine>)],
+    ) -> Vec<String> {
         let is_day = weather.is_day.unwrap_or(true);
-        let icon = if colors {
-            weather.icon.colored_icon(is_day)
-        } else {
-            weather.icon.icon(is_day)
-        };
-        let color_theme = &self.config.color_theme;
+        match self.config.icons {
+            IconStyle::Ascii => {
+                let plain_icon = self.icon_pack.plain(weather.icon, is_day);
+                let icon = if self.config.use_colors() {
+                    self.icon_pack
+                        .colored(weather.icon, is_day, self.color_depth)
+                } else {
+                    plain_icon.clone()
+                };
+                fit_rows(&plain_icon, lines.len())
+                    .into_iter()
+                    .map(|row| {
+                        row.map_or_else(|| self.icon_pack.blank_row(), |row| icon[row].clone())
+                    })
+                    .collect()
+            }
+            // The condition symbol next to the condition, glyphs of the values next to them
+            IconStyle::Emoji | IconStyle::Nerdfont => lines
+                .iter()
+                .map(|(field, line)| match (field, line) {
+                    (_, None) => String::new(),
+                    (TextField::Condition, Some((_, _, color))) => self.color_syntax().paint(
+                        weather.icon.symbol(is_day, self.config.icons).trim_end(),
+                        *color,
+                    ),
+                    (_, Some(_)) if self.config.icons == IconStyle::Emoji => String::new(),
+                    (&field, Some((_, _, color))) => {
+                        self.color_syntax().paint(field_glyph(field), *color)
+                    }
+                })
+                .collect(),
+            IconStyle::None => vec![String::new(); lines.len()],
+        }
+    }
 
-        let mut output = Vec::with_capacity(if compact { 6 } else { 7 });
+    /// Label, value and color of a full/compact mode line, `None` if there is no value to show
+    fn text_line(&self, field: TextField, weather: &Weather) -> Option<TextLine> {
+        let lang = self.config.language;
+        let color_theme = self.config.color_theme();
+        let (temp_unit, wind_unit, precip_unit) = unit_strings(self.config.units, lang);
 
-        if name {
-            output.push(make_line(
-                icon[0],
+        Some(match field {
+            TextField::Location => (
                 "Location",
-                &weather.location.name,
+                weather.location.name.clone(),
                 color_theme.location,
-                &self.config,
-            ));
-        } else if !compact {
-            output.push(icon[0].to_string());
+            ),
+            TextField::Condition => {
+                let mut condition = match weather.uv_index {
+                    // UV index goes to its own line when that is configured
+                    Some(uv) if !self.config.fields.contains(&TextField::UvIndex) => {
+                        format!("{} ({} {uv:.1})", weather.description, ll(lang, "UV index"))
+                    }
+                    _ => weather.description.clone(),
+                };
+                if let Some(marker) = stale_marker(weather, lang) {
+                    condition = format!("{condition} {marker}");
+                }
+                ("Condition", condition, condition_color(weather.icon).into())
+            }
+            TextField::Temperature => (
+                "Temperature",
+                format!(
+                    "{:.1}{temp_unit} ({} {:.1}{temp_unit})",
+                    weather.temperature,
+                    ll(lang, "feels like"),
+                    weather.feels_like
+                ),
+                self.temperature_color(weather.temperature),
+            ),
+            TextField::Wind => (
+                "Wind",
+                format_wind_value(
+                    weather.wind_speed,
+                    weather.wind_direction,
+                    wind_unit,
+                    &self.config,
+                ),
+                self.wind_color(weather.wind_speed),
+            ),
+            TextField::Precipitation => (
+                "Precipitation",
+                format!("{} {precip_unit}", weather.precipitation),
+                color_theme.precipitation,
+            ),
+            TextField::Pressure => (
+                "Pressure",
+                format!("{} {}", weather.pressure, ll(lang, "hPa")),
+                color_theme.pressure,
+            ),
+            TextField::Humidity => (
+                "Humidity",
+                format!(
+                    "{}% ({} {:.1}{temp_unit})",
+                    weather.humidity,
+                    ll(lang, "dew point"),
+                    weather.dew_point,
+                ),
+                color_theme.humidity,
+            ),
+            TextField::UvIndex => {
+                let uv = weather.uv_index?;
+                let color = if self.config.gradients {
+                    uv_index_gradient(uv)
+                } else {
+                    uv_index_color(uv).into()
+                };
+                ("UV", format!("{uv:.1}"), color)
+            }
+            TextField::Sunrise | TextField::Sunset => {
+                let (sunrise, sunset) = sun::sun_times(&weather.location, Utc::now())?;
+                let (label, time, color) = if field == TextField::Sunrise {
+                    ("Sunrise", sunrise, color_theme.sunrise)
+                } else {
+                    ("Sunset", sunset, color_theme.sunset)
+                };
+                (
+                    label,
+                    time.with_timezone(&Local).format("%H:%M").to_string(),
+                    color,
+                )
+            }
+            TextField::Aqi => {
+                let aqi = weather.aqi?;
+                ("Air quality", aqi.to_string(), aqi_color(aqi).into())
+            }
+        })
+    }
+
+    /// Theme color of temperatures, or their gradient color when gradients are enabled
+    fn temperature_color(&self, temperature: f64) -> Color {
+        if !self.config.gradients {
+            return self.config.color_theme().temperature;
         }
+        temperature_gradient(match self.config.units {
+            Units::Metric => temperature,
+            Units::Imperial => (temperature - 32.0) * 5.0 / 9.0,
+        })
+    }
 
-        output.push(make_line(
-            icon[1],
-            "Condition",
-            if let Some(uv) = weather.uv_index {
-                format!("{} ({} {uv:.1})", weather.description, ll(lang, "UV index"))
-            } else {
-                weather.description.clone()
-            },
-            condition_color(weather.icon),
-            &self.config,
-        ));
+    /// Theme color of wind speeds, or their gradient color when gradients are enabled
+    fn wind_color(&self, speed: f64) -> Color {
+        if !self.config.gradients {
+            return self.config.color_theme().wind;
+        }
+        wind_gradient(match self.config.units {
+            Units::Metric => speed,
+            Units::Imperial => speed * 0.447_04,
+        })
+    }
 
-        output.push(make_line(
-            icon[2],
-            "Temperature",
-            format!(
-                "{:.1}{temp_unit} ({} {:.1}{temp_unit})",
-                weather.temperature,
-                ll(lang, "feels like"),
-                weather.feels_like
-            ),
-            color_theme.temperature,
-            &self.config,
-        ));
+    /// Table of several locations, each row formatted with the settings of its location
+    fn render_table(&self, locations: &[(Self, &Weather)]) -> String {
+        let lang = self.config.language;
+        let colors = self.color_syntax();
+        let color_theme = self.config.color_theme();
 
-        output.push(make_line(
-            icon[3],
-            "Wind",
-            format_wind_value(
-                weather.wind_speed,
-                weather.wind_direction,
-                wind_unit,
-                self.config.wind_in_degrees,
-            ),
-            color_theme.wind,
-            &self.config,
-        ));
-
-        output.push(make_line(
-            icon[4],
-            "Precipitation",
-            format!("{} {precip_unit}", weather.precipitation),
-            color_theme.precipitation,
-            &self.config,
-        ));
-
-        output.push(make_line(
-            icon[5],
-            "Pressure",
-            format!("{} {}", weather.pressure, ll(lang, "hPa")),
-            color_theme.pressure,
-            &self.config,
-        ));
-
-        output.push(make_line(
-            icon[6],
-            "Humidity",
-            format!(
-                "{}% ({} {:.1}{temp_unit})",
-                weather.humidity,
-                ll(lang, "dew point"),
-                weather.dew_point,
-            ),
-            color_theme.humidity,
-            &self.config,
-        ));
+        let header: Vec<(String, Color)> = TABLE_COLUMNS
+            .iter()
+            .map(|&(key, _)| (ll(lang, key).to_string(), color_theme.label))
+            .collect();
+        let rows: Vec<Vec<(String, Color)>> = locations
+            .iter()
+            .map(|(formatter, weather)| {
+                let (temp_unit, wind_unit, precip_unit) =
+                    unit_strings(formatter.config.units, formatter.config.language);
+                let mut condition = weather.description.clone();
+                if let Some(marker) = stale_marker(weather, lang) {
+                    condition = format!("{condition} {marker}");
+                }
+                vec![
+                    (weather.location.name.clone(), color_theme.location),
+                    (condition, condition_color(weather.icon).into()),
+                    (
+                        format!("{:.1}{temp_unit}", weather.temperature),
+                        formatter.temperature_color(weather.temperature),
+                    ),
+                    (
+                        format_wind_value(
+                            weather.wind_speed,
+                            weather.wind_direction,
+                            wind_unit,
+                            &formatter.config,
+                        ),
+                        formatter.wind_color(weather.wind_speed),
+                    ),
+                    (
+                        format!("{} {precip_unit}", weather.precipitation),
+                        color_theme.precipitation,
+                    ),
+                    (format!("{}%", weather.humidity), color_theme.humidity),
+                ]
+            })
+            .collect();
 
-        output
+        let rows: Vec<Vec<(String, Color)>> = std::iter::once(header).chain(rows).collect();
+        let widths: Vec<usize> = (0..TABLE_COLUMNS.len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| text_width(&row[column].0))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        rows.into_iter().fold(String::new(), |mut output, row| {
+            let cells: Vec<String> = row
+                .into_iter()
+                .zip(&widths)
+                .zip(TABLE_COLUMNS)
+                .map(|(((text, color), &width), (_, align_right))| {
+                    colors.paint(pad(&text, width, align_right), color)
+                })
+                .collect();
+            let _ = writeln!(output, "{}", cells.join(COLUMN_GAP).trim_end());
+            output
+        })
+    }
+
+    fn render_json_many(&self, results: &[LocationWeather]) -> String {
+        #[derive(serde::Serialize)]
+        struct Entry<'a> {
+            location: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            weather: Option<&'a Weather>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            error: Option<String>,
+        }
+
+        let entries: Vec<Entry> = results
+            .iter()
+            .map(|location| Entry {
+                location: &location.name,
+                weather: location.weather.as_ref().ok(),
+                error: location.weather.as_ref().err().map(ToString::to_string),
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&entries).unwrap_or_else(|e| {
+            self.display_error(&RustormyError::JsonSerializeError(e));
+        });
+        format!("{json}\n")
     }
 
     fn render_json(&self, weather: &Weather) -> String {
@@ -252,7 +1282,12 @@ impl WeatherFormatter {
 mod tests {
     use super::*;
     use crate::config::Config;
-    use crate::models::{Language, Location, TextMode, Units, WeatherConditionIcon};
+    use crate::models::{
+        ForecastHour, Language, Location, OutputFormat, TextField, TextMode, Units,
+        WeatherConditionIcon,
+    };
+    use crate::weather::hourly::sample_hours;
+    use test_case::test_case;
 
     fn sample_weather() -> Weather {
         Weather {
@@ -265,10 +1300,15 @@ mod tests {
             wind_speed: 5.0,
             wind_direction: 90,
             uv_index: None,
+            aqi: None,
             is_day: Some(true),
             description: "Partly cloudy".to_string(),
             icon: WeatherConditionIcon::PartlyCloudy,
             location: Location::new("Test City".to_string(), 0.0, 0.0),
+            stale_since: None,
+            expires: None,
+            provider: None,
+            hourly: Vec::new(),
         }
     }
 
@@ -536,7 +1576,7 @@ mod tests {
         let weather = sample_weather();
         let mut config = Config::default();
         config.set_format(FormatterConfig {
-            use_colors: true,
+            colors: ColorMode::Always,
             ..Default::default()
         });
         let formatter = WeatherFormatter::new(&config);
@@ -552,6 +1592,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_format_text_with_gradients() {
+        let mut weather = sample_weather();
+        weather.uv_index = Some(11.0);
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            colors: ColorMode::Always,
+            gradients: true,
+            fields: vec![TextField::Temperature, TextField::UvIndex],
+            ..Default::default()
+        });
+        let mut formatter = WeatherFormatter::new(&config);
+        formatter.color_depth = ColorDepth::TrueColor;
+        let lines = formatter.format_text(&weather);
+
+        // 22.49°C is between the green and the yellow stops
+        assert!(
+            lines[0].contains("\x1b[38;2;192;198;40m22.5°C"),
+            "{}",
+            lines[0]
+        );
+        assert!(
+            lines[1].contains("\x1b[38;2;142;68;173m11.0"),
+            "{}",
+            lines[1]
+        );
+    }
+
+    #[test_case(IconStyle::Emoji, "⛅️ Condition:   Partly cloudy", "   Temperature:" ; "emoji")]
+    #[test_case(IconStyle::Nerdfont, "\u{e302}  Condition:   Partly cloudy", "\u{e350}  Temperature:" ; "nerd font")]
+    #[test_case(IconStyle::None, "Condition:   Partly cloudy", "Temperature:" ; "none")]
+    fn test_format_text_icon_styles(icons: IconStyle, condition: &str, temperature: &str) {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            icons,
+            fields: vec![TextField::Condition, TextField::Temperature],
+            ..Default::default()
+        });
+        let lines = WeatherFormatter::new(&config).format_text(&sample_weather());
+        assert_eq!(lines[0], condition);
+        assert!(lines[1].starts_with(temperature), "{}", lines[1]);
+    }
+
+    #[test_case(IconStyle::Ascii, "⛅️ 22.5°C 5.0 m/s ←" ; "default emoji")]
+    #[test_case(IconStyle::Nerdfont, "\u{e302}  22.5°C \u{e34b} 5.0 m/s \u{e344}" ; "nerd font")]
+    #[test_case(IconStyle::None, "22.5°C 5.0 m/s ←" ; "none")]
+    fn test_one_line_icon_styles(icons: IconStyle, expected: &str) {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            icons,
+            text_mode: TextMode::OneLine,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        assert_eq!(formatter.format_one_line(&sample_weather()), expected);
+    }
+
     #[test]
     fn test_format_text_imperial_units() {
         let weather = sample_weather();
@@ -766,20 +1863,577 @@ mod tests {
     }
 
     #[test]
-    fn test_night_clear_uses_moon_emoji() {
-        let mut weather = sample_weather();
-        weather.icon = WeatherConditionIcon::Clear;
-        weather.is_day = Some(false);
+    fn test_format_text_custom_fields_order() {
+        let weather = sample_weather();
         let mut config = Config::default();
         config.set_format(FormatterConfig {
-            text_mode: TextMode::OneLine,
+            fields: vec![TextField::Wind, TextField::Temperature],
             ..Default::default()
         });
         let formatter = WeatherFormatter::new(&config);
-        let line = formatter.format_one_line(&weather);
-        assert!(
-            line.contains("🌙"),
-            "expected moon emoji in night clear output, got '{line}'"
+        let lines = formatter.format_text(&weather);
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("Wind"), "got '{}'", lines[0]);
+        assert!(lines[1].contains("Temperature"), "got '{}'", lines[1]);
+        // Blank top row of the icon is dropped to fit two lines
+        let icon = IconPack::default().plain(weather.icon, true);
+        assert!(lines[0].starts_with(&icon[1]), "got '{}'", lines[0]);
+    }
+
+    /// Visible column where the value after the label of `line` starts, if it has a label
+    fn value_column(line: &str) -> Option<usize> {
+        let colon = line.find(':')?;
+        let value = colon + line[colon + 1..].find(|c| c != ' ')? + 1;
+        Some(visible_width(&line[..value]))
+    }
+
+    #[test_case(Language::English ; "english")]
+    #[test_case(Language::Korean ; "korean")]
+    #[test_case(Language::Spanish ; "spanish")]
+    fn test_format_text_labels_aligned(language: Language) {
+        let weather = sample_weather();
+        for icons in [IconStyle::None, IconStyle::Emoji, IconStyle::Ascii] {
+            let mut config = Config::default();
+            config.set_format(FormatterConfig {
+                language,
+                icons,
+                ..Default::default()
+            });
+            let lines = WeatherFormatter::new(&config).format_text(&weather);
+            let columns: Vec<usize> = lines.iter().filter_map(|line| value_column(line)).collect();
+            assert!(columns.len() > 1, "got {lines:#?}");
+            assert!(columns.windows(2).all(|w| w[0] == w[1]), "got {lines:#?}");
+        }
+    }
+
+    #[test]
+    fn test_format_text_label_width_from_shown_labels() {
+        let weather = sample_weather();
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            fields: vec![TextField::Wind, TextField::Pressure],
+            icons: IconStyle::None,
+            ..Default::default()
+        });
+        let lines = WeatherFormatter::new(&config).format_text(&weather);
+
+        assert!(lines[0].starts_with("Wind:     "), "got '{}'", lines[0]);
+        assert!(lines[1].starts_with("Pressure: "), "got '{}'", lines[1]);
+    }
+
+    #[test_case(TextMode::Full, 200, true, true ; "wide")]
+    #[test_case(TextMode::Full, 60, true, true ; "natural width")]
+    #[test_case(TextMode::Full, 45, false, true ; "without icon")]
+    #[test_case(TextMode::Full, 30, false, false ; "without labels")]
+    #[test_case(TextMode::Compact, 30, false, false ; "compact without icon")]
+    fn test_format_text_fits_width(text_mode: TextMode, width: usize, icon: bool, labels: bool) {
+        let weather = sample_weather();
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            text_mode,
+            ..Default::default()
+        });
+        let mut formatter = WeatherFormatter::new(&config);
+        formatter.set_width(width);
+        let lines = formatter.format_text(&weather);
+
+        let condition = lines
+            .iter()
+            .find(|line| line.contains("Partly cloudy"))
+            .unwrap();
+        assert_eq!(condition.starts_with(' '), icon, "got '{condition}'");
+        assert_eq!(
+            condition.contains("Condition:"),
+            labels,
+            "got '{condition}'"
+        );
+        if width < 60 {
+            assert!(max_width(&lines) <= width, "got {lines:#?}");
+        }
+    }
+
+    #[test]
+    fn test_format_text_extra_fields_beside() {
+        let weather = sample_weather();
+        let mut formatter = WeatherFormatter::new(&Config::default());
+        let narrow = formatter.format_text(&weather);
+        formatter.set_width(120);
+        let lines = formatter.format_text(&weather);
+
+        assert_eq!(lines.len(), narrow.len());
+        // The row of the hidden location stays as it is
+        assert_eq!(lines[0], narrow[0]);
+        assert!(lines[1].starts_with(&narrow[1]), "got '{}'", lines[1]);
+        assert!(lines[1].contains("Sunrise:"), "got '{}'", lines[1]);
+        assert!(lines[2].contains("Sunset:"), "got '{}'", lines[2]);
+        assert_eq!(lines[3], narrow[3]);
+        let column = |line: &str, label: &str| visible_width(&line[..line.find(label).unwrap()]);
+        assert_eq!(column(&lines[1], "Sunrise:"), column(&lines[2], "Sunset:"));
+    }
+
+    #[test]
+    fn test_format_text_not_responsive() {
+        let weather = sample_weather();
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            responsive: false,
+            ..Default::default()
+        });
+        let mut formatter = WeatherFormatter::new(&config);
+        let unfitted = formatter.format_text(&weather);
+        for width in [20, 200] {
+            formatter.set_width(width);
+            assert_eq!(formatter.format_text(&weather), unfitted);
+        }
+    }
+
+    /// Forecast from now with the given temperatures and chances of precipitation
+    fn hourly(temperatures: &[f64], probabilities: &[Option<u8>]) -> Vec<ForecastHour> {
+        sample_hours(Utc::now(), temperatures, probabilities)
+    }
+
+    fn sparkline_weather() -> Weather {
+        let mut weather = sample_weather();
+        weather.hourly = hourly(
+            &[12.0, 14.0, 17.0, 20.0, 24.0, 22.0],
+            &[Some(0), Some(0), Some(30), Some(80), Some(50), Some(0)],
+        );
+        weather
+    }
+
+    #[test_case(true, "⛅️ 22.5°C 5.0 m/s ← ▁▂▄▆█▇ 12–24°C ▁▁▃▇▅▁ 0–80%" ; "enabled")]
+    #[test_case(false, "⛅️ 22.5°C 5.0 m/s ←" ; "disabled")]
+    fn test_one_line_sparkline(sparkline: bool, expected: &str) {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            sparkline,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        assert_eq!(formatter.format_one_line(&sparkline_weather()), expected);
+    }
+
+    #[test_case("%t %T", 12, true, "22.5°C ▁▂▄▆█▇ 12–24°C" ; "temperature")]
+    #[test_case("%R", 12, true, "▁▁▃▇▅▁ 0–80%" ; "chance of precipitation")]
+    #[test_case("%T", 4, true, "▁▃▅█ 12–20°C" ; "fewer hours")]
+    #[test_case("%t%{ %R%}", 12, false, "22.5°C" ; "without chance of precipitation")]
+    fn test_render_template_sparklines(
+        template: &str,
+        sparkline_hours: u8,
+        with_probability: bool,
+        expected: &str,
+    ) {
+        let mut weather = sparkline_weather();
+        if !with_probability {
+            for hour in &mut weather.hourly {
+                hour.precipitation_probability = None;
+            }
+        }
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            template: Some(template.to_string()),
+            sparkline_hours,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        assert_eq!(formatter.render_to_string(&weather).trim_end(), expected);
+    }
+
+    #[test_case(true, 24, true ; "enabled")]
+    #[test_case(false, 24, false ; "disabled")]
+    #[test_case(true, 0, false ; "without forecast")]
+    fn test_render_text_chart(chart: bool, forecast_hours: usize, shown: bool) {
+        let mut weather = sample_weather();
+        let temperatures: Vec<f64> = (0..forecast_hours).map(|i| 15.0 + i as f64).collect();
+        weather.hourly = hourly(&temperatures, &vec![Some(10); forecast_hours]);
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            chart,
+            chart_hours: 12,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        let text = formatter.render_to_string(&weather);
+
+        assert_eq!(text.contains("┤"), shown, "got '{text}'");
+        assert!(text.starts_with(&formatter.render_text(&sample_weather())));
+        if shown {
+            // One column per hour, up to `chart_hours`
+            let axis = text.lines().find(|line| line.contains('└')).unwrap();
+            assert_eq!(axis.trim_start().chars().count(), 13, "got '{axis}'");
+        }
+    }
+
+    #[test]
+    fn test_format_text_icon_pack() {
+        let weather = sample_weather();
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            icon_pack: "minimal".to_string(),
+            ..Default::default()
+        });
+        let lines = WeatherFormatter::new(&config).format_text(&weather);
+
+        let icon = IconPack::load("minimal").unwrap().plain(weather.icon, true);
+        for (line, row) in lines.iter().zip(&icon) {
+            assert!(line.starts_with(row.as_str()), "got '{line}'");
+        }
+        for line in &lines[3..] {
+            assert!(line.starts_with(&" ".repeat(11)), "got '{line}'");
+            assert!(!line.starts_with(&" ".repeat(12)), "got '{line}'");
+        }
+    }
+
+    #[test]
+    fn test_format_text_uv_index_on_its_own_line() {
+        let mut weather = sample_weather();
+        weather.uv_index = Some(7.2);
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            fields: vec![TextField::Condition, TextField::UvIndex],
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        let lines = formatter.format_text(&weather);
+
+        assert_eq!(lines.len(), 2);
+        assert!(!lines[0].contains("UV index"), "got '{}'", lines[0]);
+        assert!(lines[1].contains("UV:"), "got '{}'", lines[1]);
+        assert!(lines[1].contains("7.2"), "got '{}'", lines[1]);
+    }
+
+    #[test]
+    fn test_format_text_skips_fields_without_value() {
+        let weather = sample_weather();
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            fields: vec![TextField::Temperature, TextField::UvIndex, TextField::Aqi],
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        let lines = formatter.format_text(&weather);
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("Temperature"), "got '{}'", lines[0]);
+    }
+
+    #[test]
+    fn test_format_text_pads_icon_for_extra_fields() {
+        let mut weather = sample_weather();
+        weather.uv_index = Some(3.0);
+        weather.aqi = Some(42);
+        let mut config = Config::default();
+        let mut fields = config.format().fields.clone();
+        fields.extend([
+            TextField::UvIndex,
+            TextField::Sunrise,
+            TextField::Sunset,
+            TextField::Aqi,
+        ]);
+        config.set_format(FormatterConfig {
+            fields,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        let lines = formatter.format_text(&weather);
+
+        assert_eq!(lines.len(), 11);
+        assert!(lines[8].contains("Sunrise"), "got '{}'", lines[8]);
+        assert!(lines[9].contains("Sunset"), "got '{}'", lines[9]);
+        assert!(lines[10].contains("Air quality: 42"), "got '{}'", lines[10]);
+        for line in &lines[7..] {
+            assert!(
+                line.starts_with(&IconPack::default().blank_row()),
+                "got '{line}'"
+            );
+        }
+    }
+
+    fn status_bar_formatter(output_format: OutputFormat, use_colors: bool) -> WeatherFormatter {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            output_format,
+            colors: if use_colors {
+                ColorMode::Always
+            } else {
+                ColorMode::Never
+            },
+            ..Default::default()
+        });
+        WeatherFormatter::new(&config)
+    }
+
+    #[test]
+    fn test_render_waybar() {
+        let formatter = status_bar_formatter(OutputFormat::Waybar, true);
+        let s = formatter.render_to_string(&sample_weather());
+        assert_eq!(s.lines().count(), 1, "rendered text:\n{s}");
+
+        let json: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert_eq!(json["text"], "⛅️ 22.5°C 5.0 m/s ←");
+        assert_eq!(json["class"], serde_json::json!(["partly_cloudy"]));
+        assert_eq!(json["alt"], "partly_cloudy");
+        let tooltip = json["tooltip"].as_str().unwrap();
+        // The empty location row is trimmed
+        assert_eq!(tooltip.lines().count(), 6);
+        assert!(tooltip.contains("Humidity:"), "tooltip:\n{tooltip}");
+        assert!(
+            !tooltip.contains('\x1b'),
+            "tooltip has ANSI colors:\n{tooltip}"
+        );
+    }
+
+    #[test]
+    fn test_render_waybar_stale_class() {
+        let mut weather = sample_weather();
+        weather.stale_since = Some(chrono::Utc::now());
+        let formatter = status_bar_formatter(OutputFormat::Waybar, false);
+        let json: serde_json::Value =
+            serde_json::from_str(&formatter.render_to_string(&weather)).unwrap();
+        assert_eq!(json["class"], serde_json::json!(["partly_cloudy", "stale"]));
+    }
+
+    #[test]
+    fn test_render_i3bar() {
+        let formatter = status_bar_formatter(OutputFormat::I3bar, false);
+        let weather = sample_weather();
+        let json: serde_json::Value =
+            serde_json::from_str(&formatter.render_to_string(&weather)).unwrap();
+        assert_eq!(json["name"], "rustormy");
+        assert_eq!(json["full_text"], "⛅️ 22.5°C 5.0 m/s ←");
+        assert_eq!(json["short_text"], "⛅️ 22°C");
+        assert_eq!(json["color"], "#cdcd00");
+
+        let update = formatter.render_stream_update(&weather);
+        assert!(
+            update.starts_with("[{") && update.ends_with("}],\n"),
+            "got '{update}'"
+        );
+    }
+
+    #[test_case(OutputFormat::Polybar, "⛅️ %{F#ffff00}22.5°C%{F-} %{F#ff0000}5.0 m/s ←%{F-}\n" ; "polybar")]
+    #[test_case(OutputFormat::Tmux, "⛅️ #[fg=brightyellow]22.5°C#[default] #[fg=brightred]5.0 m/s ←#[default]\n" ; "tmux")]
+    #[test_case(OutputFormat::Starship, "⛅️ 22°C\n" ; "starship")]
+    fn test_render_status_line(output_format: OutputFormat, expected: &str) {
+        let formatter = status_bar_formatter(output_format, false);
+        assert_eq!(formatter.render_to_string(&sample_weather()), expected);
+    }
+
+    #[test]
+    fn test_render_status_line_uses_template() {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            output_format: OutputFormat::Tmux,
+            template: Some("%t #%C".to_string()),
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        assert_eq!(
+            formatter.render_to_string(&sample_weather()),
+            "#[fg=brightyellow]22.5°C#[default] ###[fg=yellow]Partly cloudy#[default]\n"
+        );
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let formatter = status_bar_formatter(OutputFormat::Csv, false);
+        let weather = sample_weather();
+        let s = formatter.render_to_string(&weather);
+        let lines: Vec<_> = s.lines().collect();
+        assert_eq!(lines.len(), 2, "rendered text:\n{s}");
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(
+            lines[1].contains(",Test City,0,0,,metric,22.49,"),
+            "got '{}'",
+            lines[1]
+        );
+
+        // Live mode writes the header once, then only rows
+        assert_eq!(formatter.stream_header(), format!("{CSV_HEADER}\n"));
+        assert_eq!(formatter.render_stream_update(&weather).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_render_csv_without_header() {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            output_format: OutputFormat::Csv,
+            csv_header: false,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        let s = formatter.render_to_string(&sample_weather());
+        assert_eq!(s.lines().count(), 1, "rendered text:\n{s}");
+        assert!(!s.starts_with("timestamp"), "rendered text:\n{s}");
+        assert!(formatter.stream_header().is_empty());
+    }
+
+    #[test]
+    fn test_render_ndjson() {
+        let formatter = status_bar_formatter(OutputFormat::Ndjson, false);
+        let s = formatter.render_to_string(&sample_weather());
+        assert_eq!(s.lines().count(), 1, "rendered text:\n{s}");
+        assert!(s.ends_with('\n'));
+        let json: serde_json::Value = serde_json::from_str(&s).unwrap();
+        assert!(json["timestamp"].is_string());
+        assert_eq!(json["description"], "Partly cloudy");
+    }
+
+    #[test]
+    fn test_render_html() {
+        let mut weather = sample_weather();
+        weather.uv_index = Some(4.0);
+        let formatter = status_bar_formatter(OutputFormat::Html, false);
+        let html = formatter.render_to_string(&weather);
+
+        assert!(html.starts_with("<!DOCTYPE html>"), "{html}");
+        assert!(
+            html.contains("<h1 style=\"color:#ffffff\">Test City</h1>"),
+            "{html}"
+        );
+        assert!(html.contains("rustormy-card partly_cloudy"), "{html}");
+        assert!(
+            html.contains("<dd style=\"color:#ffff00\">22.5°C (feels like 21.5°C)</dd>"),
+            "{html}"
+        );
+        // UV index has its own row instead of being part of the condition
+        assert!(html.contains(">Partly cloudy</dd>"), "{html}");
+        assert!(html.contains(">UV</dt>"), "{html}");
+        assert!(!html.contains("Air quality"), "{html}");
+    }
+
+    #[test]
+    fn test_render_svg_is_colored_without_use_colors() {
+        let formatter = status_bar_formatter(OutputFormat::Svg, false);
+        let svg = formatter.render_to_string(&sample_weather());
+
+        assert!(svg.starts_with("<svg "), "{svg}");
+        assert_eq!(svg.matches("<tspan x=").count(), 7, "{svg}");
+        assert!(svg.contains("<tspan fill=\"#ffff00\">22.5°C (feels like 21.5°C)</tspan>"));
+        assert!(!svg.contains('\x1b'));
+    }
+
+    #[test_case(Language::English, true, "In Test City it is 22 °C and partly cloudy, feels like 22 °C, wind 5 m/s from the east." ; "english")]
+    #[test_case(Language::English, false, "It is 22 °C and partly cloudy, feels like 22 °C, wind 5 m/s from the east." ; "english without location")]
+    #[test_case(Language::Russian, true, "Test City: сейчас 22 °C, partly cloudy, ощущается как 22 °C, ветер 5 м/с с востока." ; "russian")]
+    #[test_case(Language::Spanish, true, "En Test City hace 22 °C (partly cloudy), con sensación térmica de 22 °C y viento de 5 m/s del este." ; "spanish")]
+    #[test_case(Language::Korean, true, "Test City의 현재 날씨는 partly cloudy, 기온 22 °C(체감 22 °C), 동풍 5 m/s입니다." ; "korean")]
+    fn test_format_sentence(language: Language, show_city_name: bool, expected: &str) {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            text_mode: TextMode::Sentence,
+            language,
+            show_city_name,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        assert_eq!(
+            formatter.render_to_string(&sample_weather()),
+            format!("{expected}\n")
+        );
+    }
+
+    #[test_case("Light rain", "light rain" ; "sentence case")]
+    #[test_case("UV index", "UV index" ; "acronym")]
+    #[test_case("", "" ; "empty")]
+    fn test_lowercase_first(text: &str, expected: &str) {
+        assert_eq!(lowercase_first(text), expected);
+    }
+
+    #[test_case(-0.4, "0" ; "negative zero")]
+    #[test_case(-3.6, "-4" ; "negative")]
+    #[test_case(12.5, "13" ; "half")]
+    fn test_spoken_number(value: f64, expected: &str) {
+        assert_eq!(spoken_number(value), expected);
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let mut weather = sample_weather();
+        weather.description = "Cloudy | windy".to_string();
+        let formatter = status_bar_formatter(OutputFormat::Markdown, true);
+        let markdown = formatter.render_to_string(&weather);
+        let lines: Vec<_> = markdown.lines().collect();
+
+        assert_eq!(lines[0], "### ⛅️ Test City");
+        assert_eq!(lines[1], "");
+        assert_eq!(lines[2], "| Field | Value |");
+        assert_eq!(lines[3], "| --- | --- |");
+        assert_eq!(lines[4], "| Condition | Cloudy \\| windy |");
+        assert_eq!(lines[5], "| Temperature | 22.5°C (feels like 21.5°C) |");
+        assert!(!markdown.contains('\x1b'), "{markdown}");
+    }
+
+    #[test]
+    fn test_stale_weather_is_marked() {
+        let mut weather = sample_weather();
+        weather.stale_since = Some(chrono::Utc::now());
+        let formatter = WeatherFormatter::new(&Config::default());
+        let lines = formatter.format_text(&weather);
+        assert!(
+            lines[1].contains("[stale "),
+            "Expected stale marker in condition line, got '{}'",
+            lines[1]
+        );
+
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            text_mode: TextMode::OneLine,
+            ..Default::default()
+        });
+        let line = WeatherFormatter::new(&config).format_one_line(&weather);
+        assert!(
+            line.contains("[stale "),
+            "Expected stale marker in one-line output, got '{line}'"
+        );
+    }
+
+    fn template_formatter(template: &str, language: Language, units: Units) -> WeatherFormatter {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            template: Some(template.to_string()),
+            language,
+            units,
+            ..Default::default()
+        });
+        WeatherFormatter::new(&config)
+    }
+
+    #[test_case("%l: %c %t %w", "Test City: ⛅️ 22.5°C 5.0 m/s ←\n")]
+    #[test_case("%C, %f, %h, %p, %P", "Partly cloudy, 21.5°C, 60%, 0.5 mm, 1013 hPa\n")]
+    #[test_case("%t%{ UV %u%}", "22.5°C\n")]
+    fn test_render_template(template: &str, expected: &str) {
+        let formatter = template_formatter(template, Language::English, Units::Metric);
+        assert_eq!(formatter.render_to_string(&sample_weather()), expected);
+    }
+
+    #[test]
+    fn test_render_template_translates_units() {
+        let mut weather = sample_weather();
+        weather.uv_index = Some(3.0);
+        let formatter = template_formatter("%w %P%{ UV %u%}", Language::Russian, Units::Metric);
+        assert_eq!(
+            formatter.render_to_string(&weather),
+            "5.0 м/с ← 1013 гПа UV 3.0\n"
+        );
+    }
+
+    #[test]
+    fn test_night_clear_uses_moon_emoji() {
+        let mut weather = sample_weather();
+        weather.icon = WeatherConditionIcon::Clear;
+        weather.is_day = Some(false);
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            text_mode: TextMode::OneLine,
+            ..Default::default()
+        });
+        let formatter = WeatherFormatter::new(&config);
+        let line = formatter.format_one_line(&weather);
+        assert!(
+            line.contains("🌙"),
+            "expected moon emoji in night clear output, got '{line}'"
         );
     }
 
@@ -800,4 +2454,155 @@ mod tests {
             "expected sun emoji in day clear output, got '{line}'"
         );
     }
+
+    fn many_formatter(
+        output_format: OutputFormat,
+        text_mode: TextMode,
+        layout: Layout,
+    ) -> WeatherFormatter {
+        let mut config = Config::default();
+        config.set_format(FormatterConfig {
+            output_format,
+            text_mode,
+            layout,
+            ..Default::default()
+        });
+        WeatherFormatter::new(&config)
+    }
+
+    /// Two fetched locations around one that failed, formatted like `formatter`
+    fn many_results(formatter: &WeatherFormatter) -> Vec<LocationWeather> {
+        let mut other = sample_weather();
+        other.location.name = "Other Town".to_string();
+        other.temperature = 8.0;
+        other.humidity = 100;
+        let location = |name: &str, weather| LocationWeather {
+            name: name.to_string(),
+            format: formatter.config.clone(),
+            weather,
+        };
+        vec![
+            location("Test City", Ok(sample_weather())),
+            location("Nowhere", Err(RustormyError::NoLocationProvided)),
+            location("Other Town", Ok(other)),
+        ]
+    }
+
+    #[test]
+    fn test_render_many_table() {
+        let formatter = many_formatter(OutputFormat::Text, TextMode::Full, Layout::Table);
+        assert_eq!(
+            formatter.render_many(&many_results(&formatter)),
+            "Location     Condition       Temperature   Wind        Precip   Humidity\n\
+             Test City    Partly cloudy        22.5°C   5.0 m/s ←   0.5 mm        60%\n\
+             Other Town   Partly cloudy         8.0°C   5.0 m/s ←   0.5 mm       100%\n"
+        );
+    }
+
+    #[test]
+    fn test_render_many_with_settings_of_each_location() {
+        let formatter = many_formatter(OutputFormat::Text, TextMode::Full, Layout::Table);
+        let mut results = many_results(&formatter);
+        results[2].format.units = Units::Imperial;
+        results[2].format.language = Language::Spanish;
+        let output = formatter.render_many(&results);
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[1].contains("22.5°C   5.0 m/s ←"), "{output}");
+        assert!(lines[2].contains("8.0°F   5.0 mph ←"), "{output}");
+
+        let formatter = many_formatter(OutputFormat::Text, TextMode::OneLine, Layout::Stacked);
+        let mut results = many_results(&formatter);
+        results[2].format.units = Units::Imperial;
+        assert_eq!(
+            formatter.render_many(&results),
+            "Test City: ⛅\u{fe0f} 22.5°C 5.0 m/s ←\nOther Town: ⛅\u{fe0f} 8.0°F 5.0 mph ←\n"
+        );
+    }
+
+    #[test]
+    fn test_render_many_stacked() {
+        let formatter = many_formatter(OutputFormat::Text, TextMode::OneLine, Layout::Stacked);
+        assert_eq!(
+            formatter.render_many(&many_results(&formatter)),
+            "Test City: ⛅\u{fe0f} 22.5°C 5.0 m/s ←\nOther Town: ⛅\u{fe0f} 8.0°C 5.0 m/s ←\n"
+        );
+
+        let formatter = many_formatter(OutputFormat::Text, TextMode::Compact, Layout::Stacked);
+        let output = formatter.render_many(&many_results(&formatter));
+        assert_eq!(output.matches("\n\n").count(), 1, "{output}");
+        assert!(output.contains("Test City") && output.contains("Other Town"));
+    }
+
+    #[test]
+    fn test_render_many_columns() {
+        let formatter = many_formatter(OutputFormat::Text, TextMode::Compact, Layout::Columns);
+        let output = formatter.render_many(&many_results(&formatter));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 7, "{output}");
+        assert_eq!(
+            lines[0],
+            "              Test City                                  Other Town"
+        );
+        assert_eq!(
+            lines[6],
+            "              60% (dew point 14.3°C)                     100% (dew point 14.3°C)"
+        );
+    }
+
+    #[test]
+    fn test_side_by_side_wraps() {
+        let blocks = vec![
+            vec!["aaaa".to_string(), "a".to_string()],
+            vec!["bb".to_string()],
+            vec!["cccc".to_string()],
+        ];
+        assert_eq!(side_by_side(&blocks, None), "aaaa   bb   cccc\na\n");
+        assert_eq!(side_by_side(&blocks, Some(10)), "aaaa   bb\na\n\ncccc\n");
+    }
+
+    #[test]
+    fn test_render_many_json() {
+        let formatter = many_formatter(OutputFormat::Json, TextMode::Full, Layout::Stacked);
+        let json: serde_json::Value =
+            serde_json::from_str(&formatter.render_many(&many_results(&formatter))).unwrap();
+        let entries = json.as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["location"], "Test City");
+        assert_eq!(entries[0]["weather"]["temperature"], 22.49);
+        assert_eq!(entries[1]["location"], "Nowhere");
+        assert!(entries[1]["error"].is_string());
+        assert!(entries[1].get("weather").is_none());
+    }
+
+    #[test]
+    fn test_render_many_ndjson() {
+        let formatter = many_formatter(OutputFormat::Ndjson, TextMode::Full, Layout::Stacked);
+        let output = formatter.render_many(&many_results(&formatter));
+        let names: Vec<String> = output
+            .lines()
+            .map(|line| {
+                let record: serde_json::Value = serde_json::from_str(line).unwrap();
+                record["location"]["name"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(names, ["Test City", "Other Town"]);
+    }
+
+    #[test]
+    fn test_render_many_markdown() {
+        let formatter = many_formatter(OutputFormat::Markdown, TextMode::Full, Layout::Stacked);
+        let output = formatter.render_many(&many_results(&formatter));
+        let sections: Vec<&str> = output.split("\n\n### ").collect();
+        assert_eq!(sections.len(), 2, "{output}");
+        assert!(sections[0].contains("Test City"), "{output}");
+        assert!(sections[1].contains("Other Town"), "{output}");
+    }
+
+    #[test]
+    fn test_render_many_csv_has_one_header() {
+        let formatter = many_formatter(OutputFormat::Csv, TextMode::Full, Layout::Stacked);
+        let output = formatter.render_many(&many_results(&formatter));
+        assert_eq!(output.lines().count(), 3);
+        assert!(output.starts_with(CSV_HEADER));
+    }
 }
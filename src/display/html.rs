@@ -0,0 +1,125 @@
+use std::fmt::Write;
+
+const STYLE: &str = "body{margin:0;padding:1rem;background:#111;font-family:system-ui,sans-serif}\
+.rustormy-card{display:inline-block;min-width:18rem;padding:1.25rem 1.5rem;border-radius:12px;\
+background:#1e1e1e;color:#e5e5e5;box-shadow:0 4px 16px rgba(0,0,0,.4)}\
+.rustormy-card header{display:flex;align-items:center;gap:.75rem}\
+.rustormy-card .icon{font-size:3rem;line-height:1}\
+.rustormy-card h1{margin:0;font-size:1.4rem;font-weight:600}\
+.rustormy-card dl{display:grid;grid-template-columns:auto 1fr;gap:.35rem 1rem;margin:1rem 0 0}\
+.rustormy-card dt{font-weight:600}.rustormy-card dd{margin:0}";
+
+/// Escape text for HTML element content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A labelled value of the card with its `#rrggbb` color
+pub struct CardRow {
+    pub label: String,
+    pub value: String,
+    pub color: String,
+}
+
+/// Self-contained HTML page with a weather card
+pub struct Card<'a> {
+    pub lang: &'a str,
+    pub title: &'a str,
+    pub title_color: String,
+    pub label_color: String,
+    pub emoji: &'a str,
+    /// Condition class of the card element, for custom styling
+    pub class: &'a str,
+    pub rows: Vec<CardRow>,
+}
+
+impl Card<'_> {
+    pub fn render(&self) -> String {
+        let mut html = String::new();
+        let _ = writeln!(html, "<!DOCTYPE html>");
+        let _ = writeln!(html, "<html lang=\"{}\">", escape_html(self.lang));
+        let _ = writeln!(html, "<head>");
+        let _ = writeln!(html, "<meta charset=\"utf-8\">");
+        let _ = writeln!(html, "<title>{}</title>", escape_html(self.title));
+        let _ = writeln!(html, "<style>{STYLE}</style>");
+        let _ = writeln!(html, "</head>");
+        let _ = writeln!(html, "<body>");
+        let _ = writeln!(
+            html,
+            "<div class=\"rustormy-card {}\">",
+            escape_html(self.class)
+        );
+        let _ = writeln!(
+            html,
+            "<header><span class=\"icon\">{}</span><h1 style=\"color:{}\">{}</h1></header>",
+            escape_html(self.emoji),
+            self.title_color,
+            escape_html(self.title)
+        );
+        let _ = writeln!(html, "<dl>");
+        for row in &self.rows {
+            let _ = writeln!(
+                html,
+                "<dt style=\"color:{}\">{}</dt><dd style=\"color:{}\">{}</dd>",
+                self.label_color,
+                escape_html(&row.label),
+                row.color,
+                escape_html(&row.value)
+            );
+        }
+        let _ = writeln!(html, "</dl>");
+        let _ = writeln!(html, "</div>");
+        let _ = writeln!(html, "</body>");
+        let _ = writeln!(html, "</html>");
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn test_render_card() {
+        let card = Card {
+            lang: "en",
+            title: "A & B",
+            title_color: "#ffffff".to_string(),
+            label_color: "#5c5cff".to_string(),
+            emoji: "☀️",
+            class: "clear",
+            rows: vec![CardRow {
+                label: "Temperature".to_string(),
+                value: "22.5°C".to_string(),
+                color: "#ffff00".to_string(),
+            }],
+        };
+        let html = card.render();
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+        assert!(html.contains("<title>A &amp; B</title>"));
+        assert!(html.contains("<div class=\"rustormy-card clear\">"));
+        assert!(html.contains(
+            "<dt style=\"color:#5c5cff\">Temperature</dt><dd style=\"color:#ffff00\">22.5°C</dd>"
+        ));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+}
@@ -0,0 +1,692 @@
+use crate::config::config_dir;
+use crate::display::color::ColorDepth;
+use crate::display::width::text_width;
+use crate::errors::RustormyError;
+use crate::models::{Color, WeatherConditionIcon};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+
+/// Icon packs compiled into the binary, by name
+const BUILT_IN: [(&str, &str); 3] = [
+    ("default", include_str!("icon_packs/default.toml")),
+    ("big", include_str!("icon_packs/big.toml")),
+    ("minimal", include_str!("icon_packs/minimal.toml")),
+];
+
+/// Largest icon a pack may have, in lines and columns
+const MAX_HEIGHT: usize = 12;
+const MAX_WIDTH: usize = 40;
+
+const CONDITIONS: [WeatherConditionIcon; 19] = [
+    WeatherConditionIcon::Unknown,
+    WeatherConditionIcon::Clear,
+    WeatherConditionIcon::PartlyCloudy,
+    WeatherConditionIcon::Cloudy,
+    WeatherConditionIcon::LightShowers,
+    WeatherConditionIcon::HeavyShowers,
+    WeatherConditionIcon::LightSnow,
+    WeatherConditionIcon::HeavySnow,
+    WeatherConditionIcon::Thunderstorm,
+    WeatherConditionIcon::Fog,
+    WeatherConditionIcon::Drizzle,
+    WeatherConditionIcon::FreezingRain,
+    WeatherConditionIcon::Sleet,
+    WeatherConditionIcon::Hail,
+    WeatherConditionIcon::Haze,
+    WeatherConditionIcon::Dust,
+    WeatherConditionIcon::Smoke,
+    WeatherConditionIcon::Windy,
+    WeatherConditionIcon::Tornado,
+];
+
+/// Color of a piece of art
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ink {
+    Color(Color),
+    /// Color of the 256-color palette, written as is on every terminal
+    Palette(u8),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Style {
+    ink: Option<Ink>,
+    bold: bool,
+    blink: bool,
+}
+
+impl Style {
+    /// Escape sequence switching from the `previous` style to this one
+    fn escape(self, previous: Self, depth: ColorDepth) -> String {
+        if self == Self::default() {
+            return "\x1b[0m".to_string();
+        }
+        let mut params = Vec::new();
+        match self.ink {
+            Some(Ink::Color(color)) => params.push(depth.sgr(color)),
+            Some(Ink::Palette(index)) => params.push(format!("38;5;{index}")),
+            None if previous.ink.is_some() => params.push("39".to_string()),
+            None => {}
+        }
+        if self.bold {
+            params.push("1".to_string());
+        } else if previous.bold {
+            params.push("22".to_string());
+        }
+        if self.blink {
+            params.push("5".to_string());
+        } else if previous.blink {
+            params.push("25".to_string());
+        }
+        format!("\x1b[{}m", params.join(";"))
+    }
+}
+
+/// A line of art, split where the style changes
+type Row = Vec<(Style, String)>;
+
+/// ASCII art for every weather condition, by day and by night
+#[derive(Debug, Clone)]
+pub struct IconPack {
+    width: usize,
+    height: usize,
+    icons: HashMap<(WeatherConditionIcon, bool), Vec<Row>>,
+}
+
+impl Default for IconPack {
+    fn default() -> Self {
+        Self::from_toml("default", BUILT_IN[0].1).expect("the default icon pack is valid")
+    }
+}
+
+/// Size set by a pack with `width` and `height`, instead of the size of its largest icon
+#[derive(Debug, Default, Deserialize)]
+struct Size {
+    width: Option<usize>,
+    height: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TomlPack {
+    #[serde(flatten)]
+    size: Size,
+    #[serde(flatten)]
+    icons: HashMap<WeatherConditionIcon, TomlIcon>,
+}
+
+/// Art of a condition in a TOML pack: `art` for both, or separate `day` and `night` art
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TomlIcon {
+    art: Option<String>,
+    day: Option<String>,
+    night: Option<String>,
+}
+
+impl IconPack {
+    /// A built-in pack, or `icons/<name>.toml` or `icons/<name>.txt` in the config directory
+    pub fn load(name: &str) -> Result<Self, RustormyError> {
+        if let Some((_, content)) = BUILT_IN.iter().find(|(builtin, _)| *builtin == name) {
+            return Self::from_toml(name, content);
+        }
+        let dir = config_dir()?.join("icons");
+        let toml_path = dir.join(format!("{name}.toml"));
+        if toml_path.exists() {
+            return Self::from_toml(name, &fs::read_to_string(toml_path)?);
+        }
+        let text_path = dir.join(format!("{name}.txt"));
+        if text_path.exists() {
+            return Self::from_text(name, &fs::read_to_string(text_path)?);
+        }
+        Err(RustormyError::IconPackNotFound {
+            name: name.to_string(),
+            dir: dir.display().to_string(),
+        })
+    }
+
+    /// Pack with an optional `width` and `height`, and a table per condition holding `art`, or
+    /// `day` and `night` art
+    fn from_toml(name: &str, content: &str) -> Result<Self, RustormyError> {
+        let invalid = |reason: String| RustormyError::InvalidIconPack {
+            name: name.to_string(),
+            reason,
+        };
+        let pack: TomlPack =
+            toml::from_str(content).map_err(|err| invalid(err.message().to_string()))?;
+        let mut art = HashMap::new();
+        for (condition, icon) in pack.icons {
+            let day = icon.day.or_else(|| icon.art.clone());
+            let night = icon.night.or(icon.art);
+            if day.is_none() && night.is_none() {
+                return Err(invalid(format!(
+                    "`{}` has no `art`, `day` or `night`",
+                    condition_name(condition)
+                )));
+            }
+            for (is_day, text) in [(true, day), (false, night)] {
+                if let Some(text) = text {
+                    art.insert((condition, is_day), text);
+                }
+            }
+        }
+        Self::build(name, &pack.size, art)
+    }
+
+    /// Pack in plain text, the art of each condition following a `[condition]`, `[condition day]`
+    /// or `[condition night]` line. Before the first section, `width = ` and `height = ` lines
+    /// set the size and other lines are ignored.
+    fn from_text(name: &str, content: &str) -> Result<Self, RustormyError> {
+        let invalid = |reason: String| RustormyError::InvalidIconPack {
+            name: name.to_string(),
+            reason,
+        };
+        let mut size = Size::default();
+        let mut sections: Vec<(Vec<bool>, WeatherConditionIcon, String)> = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            let Some(header) = line
+                .trim()
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            else {
+                if let Some((_, _, art)) = sections.last_mut() {
+                    art.push_str(line);
+                    art.push('\n');
+                } else if let Some((key, value)) = line.split_once('=')
+                    && let Some(field) = match key.trim() {
+                        "width" => Some(&mut size.width),
+                        "height" => Some(&mut size.height),
+                        _ => None,
+                    }
+                {
+                    let value = value.trim().parse().map_err(|_| {
+                        invalid(format!(
+                            "invalid size `{}` on line {}",
+                            line.trim(),
+                            number + 1
+                        ))
+                    })?;
+                    *field = Some(value);
+                }
+                continue;
+            };
+            let mut words = header.split_whitespace();
+            let condition = words.next().and_then(parse_condition);
+            let times = match words.next() {
+                None => vec![true, false],
+                Some("day") => vec![true],
+                Some("night") => vec![false],
+                Some(_) => vec![],
+            };
+            match condition {
+                Some(condition) if !times.is_empty() && words.next().is_none() => {
+                    sections.push((times, condition, String::new()));
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "unknown section `{}` on line {}",
+                        line.trim(),
+                        number + 1
+                    )));
+                }
+            }
+        }
+        let mut art = HashMap::new();
+        for (times, condition, text) in sections {
+            for is_day in times {
+                if art.insert((condition, is_day), text.clone()).is_some() {
+                    let time = if is_day { "day" } else { "night" };
+                    return Err(invalid(format!(
+                        "`{}` has more than one {time} art",
+                        condition_name(condition)
+                    )));
+                }
+            }
+        }
+        Self::build(name, &size, art)
+    }
+
+    /// Parse and check the art of every condition
+    fn build(
+        name: &str,
+        size: &Size,
+        mut art: HashMap<(WeatherConditionIcon, bool), String>,
+    ) -> Result<Self, RustormyError> {
+        let invalid = |reason: String| RustormyError::InvalidIconPack {
+            name: name.to_string(),
+            reason,
+        };
+        // Art given only for the day or only for the night is used for both
+        for condition in CONDITIONS {
+            for is_day in [true, false] {
+                if let Some(text) = art.get(&(condition, !is_day)).cloned() {
+                    art.entry((condition, is_day)).or_insert(text);
+                }
+            }
+        }
+        if !art.contains_key(&(WeatherConditionIcon::Unknown, true)) {
+            return Err(invalid(
+                "`unknown` art is missing, it is shown for conditions without art".to_string(),
+            ));
+        }
+
+        let mut icons = HashMap::new();
+        for ((condition, is_day), text) in art {
+            let condition_name = condition_name(condition);
+            let rows = text
+                .trim_end_matches(['\n', ' '])
+                .lines()
+                .enumerate()
+                .map(|(number, line)| {
+                    parse_row(line).map_err(|reason| {
+                        invalid(format!(
+                            "line {} of `{condition_name}` art: {reason}",
+                            number + 1
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<Row>, _>>()?;
+            if rows.is_empty() {
+                return Err(invalid(format!("`{condition_name}` art is empty")));
+            }
+            icons.insert((condition, is_day), rows);
+        }
+
+        let height = size
+            .height
+            .unwrap_or_else(|| icons.values().map(Vec::len).max().unwrap_or(0));
+        let width = size
+            .width
+            .unwrap_or_else(|| icons.values().flatten().map(row_width).max().unwrap_or(0));
+        if !(1..=MAX_HEIGHT).contains(&height) || !(1..=MAX_WIDTH).contains(&width) {
+            return Err(invalid(format!(
+                "icons are {width} × {height}, at most {MAX_WIDTH} × {MAX_HEIGHT} are allowed"
+            )));
+        }
+        for ((condition, _), rows) in &icons {
+            let condition_name = condition_name(*condition);
+            if rows.len() > height {
+                return Err(invalid(format!(
+                    "`{condition_name}` art is {} lines high, the pack has {height}",
+                    rows.len()
+                )));
+            }
+            for (number, row) in rows.iter().enumerate() {
+                if row_width(row) > width {
+                    return Err(invalid(format!(
+                        "line {} of `{condition_name}` art is {} columns wide, the pack has {width}",
+                        number + 1,
+                        row_width(row)
+                    )));
+                }
+            }
+        }
+        Ok(Self {
+            width,
+            height,
+            icons,
+        })
+    }
+
+    /// Art of the condition, of a similar condition when the pack has none
+    fn rows(&self, mut condition: WeatherConditionIcon, is_day: bool) -> &[Row] {
+        loop {
+            if let Some(rows) = self.icons.get(&(condition, is_day)) {
+                return rows;
+            }
+            if condition == WeatherConditionIcon::Unknown {
+                return &[];
+            }
+            condition = fallback(condition);
+        }
+    }
+
+    /// Lines of the icon, all as wide and as many as in the largest icon of the pack
+    pub fn plain(&self, condition: WeatherConditionIcon, is_day: bool) -> Vec<String> {
+        self.render(condition, is_day, None)
+    }
+
+    /// Lines of the icon with its colors
+    pub fn colored(
+        &self,
+        condition: WeatherConditionIcon,
+        is_day: bool,
+        depth: ColorDepth,
+    ) -> Vec<String> {
+        self.render(condition, is_day, Some(depth))
+    }
+
+    /// Empty line as wide as the icons
+    pub fn blank_row(&self) -> String {
+        " ".repeat(self.width)
+    }
+
+    fn render(
+        &self,
+        condition: WeatherConditionIcon,
+        is_day: bool,
+        depth: Option<ColorDepth>,
+    ) -> Vec<String> {
+        let rows = self.rows(condition, is_day);
+        (0..self.height)
+            .map(|index| {
+                rows.get(index)
+                    .map_or_else(|| self.blank_row(), |row| self.render_row(row, depth))
+            })
+            .collect()
+    }
+
+    fn render_row(&self, row: &Row, depth: Option<ColorDepth>) -> String {
+        let mut line = String::new();
+        let mut current = Style::default();
+        for (style, text) in row {
+            if let Some(depth) = depth
+                && *style != current
+            {
+                line.push_str(&style.escape(current, depth));
+                current = *style;
+            }
+            line.push_str(text);
+        }
+        if current != Style::default() {
+            line.push_str("\x1b[0m");
+        }
+        line + &" ".repeat(self.width - row_width(row))
+    }
+}
+
+/// Condition shown instead of one a pack has no art for
+const fn fallback(condition: WeatherConditionIcon) -> WeatherConditionIcon {
+    match condition {
+        WeatherConditionIcon::Drizzle | WeatherConditionIcon::FreezingRain => {
+            WeatherConditionIcon::LightShowers
+        }
+        WeatherConditionIcon::Sleet => WeatherConditionIcon::LightSnow,
+        WeatherConditionIcon::Hail => WeatherConditionIcon::HeavyShowers,
+        WeatherConditionIcon::Haze | WeatherConditionIcon::Dust | WeatherConditionIcon::Smoke => {
+            WeatherConditionIcon::Fog
+        }
+        WeatherConditionIcon::PartlyCloudy | WeatherConditionIcon::Windy => {
+            WeatherConditionIcon::Cloudy
+        }
+        WeatherConditionIcon::Tornado => WeatherConditionIcon::Thunderstorm,
+        _ => WeatherConditionIcon::Unknown,
+    }
+}
+
+fn parse_condition(name: &str) -> Option<WeatherConditionIcon> {
+    WeatherConditionIcon::deserialize(
+        serde::de::value::StrDeserializer::<serde::de::value::Error>::new(name),
+    )
+    .ok()
+}
+
+fn condition_name(condition: WeatherConditionIcon) -> &'static str {
+    crate::display::status_bar::condition_class(condition)
+}
+
+fn row_width(row: &Row) -> usize {
+    row.iter().map(|(_, text)| text_width(text)).sum()
+}
+
+/// Split a line of art at its `{style}` tags: a color name, `#rrggbb` or a palette number,
+/// `bold` and `blink`. `{/}` goes back to the default style and `{{` is a brace.
+fn parse_row(line: &str) -> Result<Row, String> {
+    let mut row: Row = Vec::new();
+    let mut style = Style::default();
+    let mut text = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                text.push('{');
+            }
+            '{' => {
+                let (tag, rest) = chars
+                    .as_str()
+                    .split_once('}')
+                    .ok_or_else(|| "`{` without a closing `}`".to_string())?;
+                let tag = tag.to_string();
+                chars = rest.chars();
+                if !text.is_empty() {
+                    row.push((style, std::mem::take(&mut text)));
+                }
+                style = parse_style(&tag)?;
+            }
+            '\t' => return Err("tabs are not allowed, use spaces".to_string()),
+            c if c.is_control() => return Err(format!("control character {c:?}")),
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        row.push((style, text));
+    }
+    Ok(row)
+}
+
+fn parse_style(tag: &str) -> Result<Style, String> {
+    if tag == "/" {
+        return Ok(Style::default());
+    }
+    let mut style = Style::default();
+    for word in tag.split_whitespace() {
+        match word {
+            "bold" => style.bold = true,
+            "blink" => style.blink = true,
+            word => {
+                let ink = match word.parse::<u8>() {
+                    Ok(index) => Ink::Palette(index),
+                    Err(_) => Ink::Color(Color::try_from(word.to_string())?),
+                };
+                style.ink = Some(ink);
+            }
+        }
+    }
+    if style == Style::default() {
+        return Err(format!("empty style `{{{tag}}}`"));
+    }
+    Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AnsiColor;
+    use test_case::test_case;
+
+    fn strip_escapes(line: &str) -> String {
+        let mut plain = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                plain.push(c);
+            }
+        }
+        plain
+    }
+
+    #[test_case("default", 13, 7)]
+    #[test_case("big", 23, 9)]
+    #[test_case("minimal", 10, 3)]
+    fn test_built_in_packs_are_complete(name: &str, width: usize, height: usize) {
+        let pack = IconPack::load(name).unwrap();
+        assert_eq!((pack.width, pack.height), (width, height));
+        for condition in CONDITIONS {
+            for is_day in [true, false] {
+                assert!(
+                    pack.icons.contains_key(&(condition, is_day)),
+                    "{name} has no {condition:?} art"
+                );
+                let plain = pack.plain(condition, is_day);
+                let colored = pack.colored(condition, is_day, ColorDepth::TrueColor);
+                assert_eq!(plain.len(), height);
+                for (plain, colored) in plain.iter().zip(&colored) {
+                    assert_eq!(text_width(plain), width, "{name} {condition:?}: {plain:?}");
+                    assert_eq!(&strip_escapes(colored), plain);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_pack_day_and_night() {
+        let pack = IconPack::default();
+        for condition in [
+            WeatherConditionIcon::Clear,
+            WeatherConditionIcon::PartlyCloudy,
+            WeatherConditionIcon::LightShowers,
+            WeatherConditionIcon::HeavyShowers,
+        ] {
+            assert_ne!(
+                pack.plain(condition, true),
+                pack.plain(condition, false),
+                "{condition:?}"
+            );
+        }
+        for condition in [
+            WeatherConditionIcon::Unknown,
+            WeatherConditionIcon::Cloudy,
+            WeatherConditionIcon::LightSnow,
+            WeatherConditionIcon::Thunderstorm,
+            WeatherConditionIcon::Fog,
+        ] {
+            assert_eq!(
+                pack.colored(condition, true, ColorDepth::Ansi256),
+                pack.colored(condition, false, ColorDepth::Ansi256),
+                "{condition:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_pack_colors() {
+        let pack = IconPack::default();
+        let clear = pack.colored(WeatherConditionIcon::Clear, true, ColorDepth::Ansi16);
+        assert_eq!(clear[0], " ".repeat(13));
+        assert_eq!(clear[1], "\x1b[38;5;226m    \\   /\x1b[0m    ");
+        let storm = pack.colored(WeatherConditionIcon::Thunderstorm, true, ColorDepth::Ansi16);
+        assert_eq!(
+            storm[4],
+            "\x1b[38;5;228;5m   ⚡\x1b[38;5;111;25m\"\"\x1b[38;5;228;5m⚡\x1b[38;5;111;25m\"\"\x1b[0m  "
+        );
+    }
+
+    #[test]
+    fn test_toml_pack() {
+        let pack = IconPack::from_toml(
+            "test",
+            "[unknown]\nart = '''\n ?\n'''\n\n[clear]\nday = '''\n{Yellow bold}\\|/\n{#ff8000}-o-{/} !\n'''\n",
+        )
+        .unwrap();
+        assert_eq!((pack.width, pack.height), (5, 2));
+        assert_eq!(
+            pack.plain(WeatherConditionIcon::Clear, true),
+            ["\\|/  ", "-o- !"]
+        );
+        // Night art falls back to the day art
+        assert_eq!(
+            pack.plain(WeatherConditionIcon::Clear, false),
+            ["\\|/  ", "-o- !"]
+        );
+        assert_eq!(
+            pack.colored(WeatherConditionIcon::Clear, true, ColorDepth::TrueColor),
+            [
+                "\x1b[33;1m\\|/\x1b[0m  ",
+                "\x1b[38;2;255;128;0m-o-\x1b[0m !"
+            ]
+        );
+        assert_eq!(
+            pack.plain(WeatherConditionIcon::Tornado, true),
+            [" ?   ", "     "]
+        );
+    }
+
+    #[test]
+    fn test_text_pack() {
+        let pack = IconPack::from_text(
+            "test",
+            "My pack\n\n[unknown]\n ?\n\n[clear day]\n\\|/\n{{o}\n[clear night]\n (\n[fog]\n~~~\n",
+        )
+        .unwrap();
+        assert_eq!((pack.width, pack.height), (3, 2));
+        assert_eq!(
+            pack.plain(WeatherConditionIcon::Clear, true),
+            ["\\|/", "{o}"]
+        );
+        assert_eq!(
+            pack.plain(WeatherConditionIcon::Clear, false),
+            [" ( ", "   "]
+        );
+        assert_eq!(
+            pack.plain(WeatherConditionIcon::Haze, false),
+            ["~~~", "   "]
+        );
+    }
+
+    #[test_case("[clear]\nart = '''\no\n'''\n", "`unknown` art is missing" ; "missing unknown")]
+    #[test_case("[sunny]\nart = 'o'\n", "unknown variant `sunny`" ; "unknown condition")]
+    #[test_case("[unknown]\n", "`unknown` has no `art`, `day` or `night`" ; "no art")]
+    #[test_case("[unknown]\nart = '\t?'\n", "line 1 of `unknown` art: tabs are not allowed" ; "tab")]
+    #[test_case("[unknown]\nart = '{Purple}?'\n", "line 1 of `unknown` art: unknown color `Purple`" ; "unknown color")]
+    #[test_case("[unknown]\nart = '{Red?'\n", "line 1 of `unknown` art: `{` without a closing `}`" ; "unclosed tag")]
+    #[test_case("[unknown]\nart = \"\\n\\n\\n\\n\\n\\n\\n\\n\\n\\n\\n\\n?\"\n", "icons are 1 × 13, at most 40 × 12 are allowed" ; "too high")]
+    #[test_case("[unknown]\nart = '?                                        ?'\n", "icons are 42 × 1, at most 40 × 12 are allowed" ; "too wide")]
+    #[test_case("height = 1\n[unknown]\nart = \"?\\n?\"\n", "`unknown` art is 2 lines high, the pack has 1" ; "higher than declared")]
+    #[test_case("width = 2\n[unknown]\nart = '{Red}???'\n", "line 1 of `unknown` art is 3 columns wide, the pack has 2" ; "wider than declared")]
+    fn test_invalid_toml_pack(content: &str, reason: &str) {
+        let error = IconPack::from_toml("test", content)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(reason), "{error}");
+    }
+
+    #[test_case("[unknown]\n?\n[sunny]\no\n", "unknown section `[sunny]` on line 3" ; "unknown condition")]
+    #[test_case("[unknown]\n?\n[clear noon]\no\n", "unknown section `[clear noon]` on line 3" ; "unknown time")]
+    #[test_case("[unknown]\n?\n[clear]\no\n[clear day]\nO\n", "`clear` has more than one day art" ; "duplicate")]
+    fn test_invalid_text_pack(content: &str, reason: &str) {
+        let error = IconPack::from_text("test", content)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(reason), "{error}");
+    }
+
+    #[test]
+    fn test_missing_pack() {
+        let error = IconPack::load("no_such_pack").unwrap_err();
+        assert!(matches!(error, RustormyError::IconPackNotFound { .. }));
+    }
+
+    #[test]
+    fn test_pack_from_config_dir() {
+        let dir = config_dir().unwrap().join("icons");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("test_text_pack.txt"), "[unknown]\n?\n").unwrap();
+        let pack = IconPack::load("test_text_pack").unwrap();
+        assert_eq!(pack.plain(WeatherConditionIcon::Clear, true), ["?"]);
+    }
+
+    #[test]
+    fn test_style_escape() {
+        let bold_red = Style {
+            ink: Some(Ink::Color(AnsiColor::Red.into())),
+            bold: true,
+            blink: false,
+        };
+        let plain = Style {
+            ink: None,
+            bold: false,
+            blink: true,
+        };
+        assert_eq!(
+            bold_red.escape(Style::default(), ColorDepth::Ansi16),
+            "\x1b[31;1m"
+        );
+        assert_eq!(plain.escape(bold_red, ColorDepth::Ansi16), "\x1b[39;22;5m");
+        assert_eq!(
+            Style::default().escape(bold_red, ColorDepth::Ansi16),
+            "\x1b[0m"
+        );
+    }
+}
@@ -1,272 +1,27 @@
-use crate::models::WeatherConditionIcon;
+use crate::models::{IconStyle, TextField, WeatherConditionIcon};
 
-pub type Icon = [&'static str; 7];
-
-impl WeatherConditionIcon {
-    #[allow(clippy::too_many_lines)]
-    pub fn icon(self, is_day: bool) -> Icon {
-        match self {
-            Self::Unknown => [
-                "             ",
-                "    .-.      ",
-                "     __)     ",
-                "    (        ",
-                "     `-’     ",
-                "      •      ",
-                "             ",
-            ],
-            Self::Clear if is_day => [
-                "             ",
-                "    \\   /    ",
-                "     .-.     ",
-                "  ― (   ) ―  ",
-                "     `-’     ",
-                "    /   \\    ",
-                "             ",
-            ],
-            Self::Clear => [
-                "     .  *    ",
-                "      .-.    ",
-                "   . ( (  *  ",
-                "      `-’    ",
-                "   *   .     ",
-                "             ",
-                "             ",
-            ],
-            Self::PartlyCloudy if is_day => [
-                "             ",
-                "   \\  /      ",
-                " _ /\"\".-.    ",
-                "   \\_(   ).  ",
-                "   /(___(__) ",
-                "             ",
-                "             ",
-            ],
-            Self::PartlyCloudy => [
-                "             ",
-                "     . *     ",
-                "   _ .-.     ",
-                "    (   ).   ",
-                "   *(___(__) ",
-                "             ",
-                "             ",
-            ],
-            Self::Cloudy => [
-                "             ",
-                "             ",
-                "     .--.    ",
-                "  .-(    ).  ",
-                " (___.__)__) ",
-                "             ",
-                "             ",
-            ],
-            Self::LightShowers if is_day => [
-                "             ",
-                " _`/\"\".-.    ",
-                "  ,\\_(   ).  ",
-                "   /(___(__) ",
-                "     ' ' ' ' ",
-                "    ' ' ' '  ",
-                "             ",
-            ],
-            Self::LightShowers => [
-                "     . *     ",
-                "   * .-.     ",
-                "    (   ).   ",
-                "   *(___(__) ",
-                "     ' ' ' ' ",
-                "    ' ' ' '  ",
-                "             ",
-            ],
-            Self::HeavyShowers if is_day => [
-                "             ",
-                " _`/\"\".-.    ",
-                "  ,\\_(   ).  ",
-                "   /(___(__) ",
-                "   ‚'‚'‚'‚'  ",
-                "   ‚'‚'‚'‚'  ",
-                "             ",
-            ],
-            Self::HeavyShowers => [
-                "     . *     ",
-                "   * .-.     ",
-                "    (   ).   ",
-                "   *(___(__) ",
-                "   ‚'‚'‚'‚'  ",
-                "   ‚'‚'‚'‚'  ",
-                "             ",
-            ],
-            Self::LightSnow => [
-                "             ",
-                "     .-.     ",
-                "    (   ).   ",
-                "   (___(__)  ",
-                "    *  *  *  ",
-                "   *  *  *   ",
-                "             ",
-            ],
-            Self::HeavySnow => [
-                "             ",
-                "     .-.     ",
-                "    (   ).   ",
-                "   (___(__)  ",
-                "   * * * *   ",
-                "  * * * *    ",
-                "             ",
-            ],
-            Self::Thunderstorm => [
-                "             ",
-                "     .-.     ",
-                "    (   ).   ",
-                "   (___(__)  ",
-                "   ⚡\"\"⚡\"\"  ",
-                "  ‚'‚'‚'‚'   ",
-                "             ",
-            ],
-            Self::Fog => [
-                "             ",
-                "             ",
-                " _ - _ - _ - ",
-                "  _ - _ - _  ",
-                " _ - _ - _ - ",
-                "             ",
-                "             ",
-            ],
-        }
+/// Pick the icon rows shown next to `lines` lines of text.
+///
+/// When there are fewer lines than rows, blank rows are dropped first (bottom, then top)
+/// and the icon is cut at the bottom after that. Extra lines get a blank row (`None`).
+pub fn fit_rows(icon: &[String], lines: usize) -> Vec<Option<usize>> {
+    let is_blank = |row: usize| icon[row].trim().is_empty();
+    let mut rows: Vec<usize> = (0..icon.len()).collect();
+    while rows.len() > lines && rows.last().is_some_and(|&row| is_blank(row)) {
+        rows.pop();
     }
-
-    #[allow(clippy::too_many_lines)]
-    pub fn colored_icon(self, is_day: bool) -> Icon {
-        match self {
-            Self::Unknown => [
-                "             ",
-                "    .-.      ",
-                "     __)     ",
-                "    (        ",
-                "     `-’     ",
-                "      •      ",
-                "             ",
-            ],
-            Self::Clear if is_day => [
-                "             ",
-                "\x1b[38;5;226m    \\   /    \x1b[0m",
-                "\x1b[38;5;226m     .-.     \x1b[0m",
-                "\x1b[38;5;226m  ― (   ) ―  \x1b[0m",
-                "\x1b[38;5;226m     `-’     \x1b[0m",
-                "\x1b[38;5;226m    /   \\    \x1b[0m",
-                "             ",
-            ],
-            Self::Clear => [
-                "\x1b[38;5;111;1m     .  *    \x1b[0m",
-                "\x1b[38;5;230;1m      .-.    \x1b[0m",
-                "\x1b[38;5;111;1m   . \x1b[38;5;230;1m( (\x1b[38;5;111;1m  *  \x1b[0m",
-                "\x1b[38;5;230;1m      `-’    \x1b[0m",
-                "\x1b[38;5;111;1m   *   .     \x1b[0m",
-                "             ",
-                "             ",
-            ],
-            Self::PartlyCloudy if is_day => [
-                "             ",
-                "\x1b[38;5;226m   \\  /\x1b[0m      ",
-                "\x1b[38;5;226m _ /\"\"\x1b[38;5;250m.-.    \x1b[0m",
-                "\x1b[38;5;226m   \\_\x1b[38;5;250m(   ).  \x1b[0m",
-                "\x1b[38;5;226m   /\x1b[38;5;250m(___(__) \x1b[0m",
-                "             ",
-                "             ",
-            ],
-            Self::PartlyCloudy => [
-                "             ",
-                "\x1b[38;5;111;1m     . *     \x1b[0m",
-                "\x1b[38;5;230;1m     \x1b[38;5;250m.-.     \x1b[0m",
-                "\x1b[38;5;250m    (   ).   \x1b[0m",
-                "\x1b[38;5;111;1m   *\x1b[38;5;250m(___(__) \x1b[0m",
-                "             ",
-                "             ",
-            ],
-            Self::Cloudy => [
-                "             ",
-                "             ",
-                "\x1b[38;5;250m     .--.    \x1b[0m",
-                "\x1b[38;5;250m  .-(    ).  \x1b[0m",
-                "\x1b[38;5;250m (___.__)__) \x1b[0m",
-                "             ",
-                "             ",
-            ],
-            Self::LightShowers if is_day => [
-                "             ",
-                "\x1b[38;5;226m _`/\"\"\x1b[38;5;250m.-.    \x1b[0m",
-                "\x1b[38;5;226m  ,\\_\x1b[38;5;250m(   ).  \x1b[0m",
-                "\x1b[38;5;226m   /\x1b[38;5;250m(___(__) \x1b[0m",
-                "\x1b[38;5;111m     ' ' ' ' \x1b[0m",
-                "\x1b[38;5;111m    ' ' ' '  \x1b[0m",
-                "             ",
-            ],
-            Self::LightShowers => [
-                "\x1b[38;5;111;1m     . *     \x1b[0m",
-                "\x1b[38;5;111;1m   *\x1b[38;5;250m .-.     \x1b[0m",
-                "\x1b[38;5;250m    (   ).   \x1b[0m",
-                "\x1b[38;5;111;1m   *\x1b[38;5;250m(___(__) \x1b[0m",
-                "\x1b[38;5;111m     ' ' ' ' \x1b[0m",
-                "\x1b[38;5;111m    ' ' ' '  \x1b[0m",
-                "             ",
-            ],
-            Self::HeavyShowers if is_day => [
-                "             ",
-                "\x1b[38;5;226m _`/\"\"\x1b[38;5;240;1m.-.    \x1b[0m",
-                "\x1b[38;5;226m  ,\\_\x1b[38;5;240;1m(   ).  \x1b[0m",
-                "\x1b[38;5;226m   /\x1b[38;5;240;1m(___(__) \x1b[0m",
-                "\x1b[38;5;21;1m   ‚'‚'‚'‚'  \x1b[0m",
-                "\x1b[38;5;21;1m   ‚'‚'‚'‚'  \x1b[0m",
-                "             ",
-            ],
-            Self::HeavyShowers => [
-                "\x1b[38;5;111;1m     . *     \x1b[0m",
-                "\x1b[38;5;111;1m   *\x1b[38;5;240;1m .-.     \x1b[0m",
-                "\x1b[38;5;240;1m    (   ).   \x1b[0m",
-                "\x1b[38;5;111;1m   *\x1b[38;5;240;1m(___(__) \x1b[0m",
-                "\x1b[38;5;21;1m   ‚'‚'‚'‚'  \x1b[0m",
-                "\x1b[38;5;21;1m   ‚'‚'‚'‚'  \x1b[0m",
-                "             ",
-            ],
-            Self::LightSnow => [
-                "             ",
-                "\x1b[38;5;250m     .-.     \x1b[0m",
-                "\x1b[38;5;250m    (   ).   \x1b[0m",
-                "\x1b[38;5;250m   (___(__)  \x1b[0m",
-                "\x1b[38;5;255m    *  *  *  \x1b[0m",
-                "\x1b[38;5;255m   *  *  *   \x1b[0m",
-                "             ",
-            ],
-            Self::HeavySnow => [
-                "             ",
-                "\x1b[38;5;240;1m     .-.     \x1b[0m",
-                "\x1b[38;5;240;1m    (   ).   \x1b[0m",
-                "\x1b[38;5;240;1m   (___(__)  \x1b[0m",
-                "\x1b[38;5;255;1m   * * * *   \x1b[0m",
-                "\x1b[38;5;255;1m  * * * *    \x1b[0m",
-                "             ",
-            ],
-            Self::Thunderstorm => [
-                "             ",
-                "\x1b[38;5;240;1m     .-.     \x1b[0m",
-                "\x1b[38;5;240;1m    (   ).   \x1b[0m",
-                "\x1b[38;5;240;1m   (___(__)  \x1b[0m",
-                "\x1b[38;5;228;5m   ⚡\x1b[38;5;111;25m\"\"\x1b[38;5;228;5m⚡\x1b[38;5;111;25m\"\"  \x1b[0m",
-                "\x1b[38;5;21;1m  ‚'‚'‚'‚'   \x1b[0m",
-                "             ",
-            ],
-            Self::Fog => [
-                "             ",
-                "             ",
-                "\x1b[38;5;251m _ - _ - _ - \x1b[0m",
-                "\x1b[38;5;251m  _ - _ - _  \x1b[0m",
-                "\x1b[38;5;251m _ - _ - _ - \x1b[0m",
-                "             ",
-                "             ",
-            ],
-        }
+    while rows.len() > lines && rows.first().is_some_and(|&row| is_blank(row)) {
+        rows.remove(0);
     }
+    rows.truncate(lines);
+    rows.into_iter()
+        .map(Some)
+        .chain(std::iter::repeat(None))
+        .take(lines)
+        .collect()
+}
 
+impl WeatherConditionIcon {
     pub fn emoji(self, is_day: bool) -> &'static str {
         match self {
             WeatherConditionIcon::Unknown => "❓",
@@ -275,87 +30,93 @@ impl WeatherConditionIcon {
             WeatherConditionIcon::PartlyCloudy if is_day => "⛅️",
             WeatherConditionIcon::PartlyCloudy | WeatherConditionIcon::Cloudy => "☁️ ",
             WeatherConditionIcon::LightShowers if is_day => "🌦️ ",
-            WeatherConditionIcon::LightShowers | WeatherConditionIcon::HeavyShowers => "🌧️ ",
-            WeatherConditionIcon::LightSnow => "🌨️ ",
+            WeatherConditionIcon::LightShowers
+            | WeatherConditionIcon::HeavyShowers
+            | WeatherConditionIcon::FreezingRain => "🌧️ ",
+            WeatherConditionIcon::LightSnow | WeatherConditionIcon::Sleet => "🌨️ ",
             WeatherConditionIcon::HeavySnow => "❄️ ",
             WeatherConditionIcon::Thunderstorm => "⛈️ ",
             WeatherConditionIcon::Fog => "🌫 ",
+            WeatherConditionIcon::Drizzle => "💧",
+            WeatherConditionIcon::Hail => "🧊",
+            WeatherConditionIcon::Haze => "🌁",
+            WeatherConditionIcon::Dust => "🏜️ ",
+            WeatherConditionIcon::Smoke => "🔥",
+            WeatherConditionIcon::Windy => "💨",
+            WeatherConditionIcon::Tornado => "🌪️ ",
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn rendered_width(s: &str) -> usize {
-        let mut width = 0;
-        let mut chars = s.chars();
-        while let Some(c) = chars.next() {
-            if c == '\x1b' {
-                for c2 in chars.by_ref() {
-                    if c2 == 'm' {
-                        break;
-                    }
-                }
-            } else if c == '⚡' {
-                width += 2;
-            } else {
-                width += 1;
-            }
+impl WeatherConditionIcon {
+    /// Nerd Font glyph (Weather Icons set) followed by a space, so it takes two cells like emoji
+    pub fn nerd_font(self, is_day: bool) -> &'static str {
+        match self {
+            Self::Unknown => "\u{e374} ",
+            Self::Clear if is_day => "\u{e30d} ",
+            Self::Clear => "\u{e32b} ",
+            Self::PartlyCloudy if is_day => "\u{e302} ",
+            Self::PartlyCloudy => "\u{e37e} ",
+            Self::Cloudy => "\u{e312} ",
+            Self::LightShowers if is_day => "\u{e309} ",
+            Self::LightShowers => "\u{e319} ",
+            Self::HeavyShowers if is_day => "\u{e308} ",
+            Self::HeavyShowers | Self::FreezingRain => "\u{e318} ",
+            Self::LightSnow if is_day => "\u{e30a} ",
+            Self::LightSnow | Self::HeavySnow => "\u{e31a} ",
+            Self::Thunderstorm if is_day => "\u{e30f} ",
+            Self::Thunderstorm => "\u{e31d} ",
+            Self::Fog | Self::Haze => "\u{e313} ",
+            Self::Drizzle => "\u{e31b} ",
+            Self::Sleet => "\u{e316} ",
+            Self::Hail => "\u{e314} ",
+            Self::Dust => "\u{e35d} ",
+            Self::Smoke => "\u{e35c} ",
+            Self::Windy => "\u{e31e} ",
+            Self::Tornado => "\u{e351} ",
         }
-        width
     }
 
-    #[test]
-    fn all_icons_have_uniform_rendered_width() {
-        const VARIANTS: &[WeatherConditionIcon] = &[
-            WeatherConditionIcon::Unknown,
-            WeatherConditionIcon::Clear,
-            WeatherConditionIcon::PartlyCloudy,
-            WeatherConditionIcon::Cloudy,
-            WeatherConditionIcon::LightShowers,
-            WeatherConditionIcon::HeavyShowers,
-            WeatherConditionIcon::LightSnow,
-            WeatherConditionIcon::HeavySnow,
-            WeatherConditionIcon::Thunderstorm,
-            WeatherConditionIcon::Fog,
-        ];
-        let mut failures: Vec<String> = Vec::new();
-        for &v in VARIANTS {
-            for is_day in [true, false] {
-                for (kind, icon) in [
-                    ("icon", v.icon(is_day)),
-                    ("colored_icon", v.colored_icon(is_day)),
-                ] {
-                    for (i, line) in icon.iter().enumerate() {
-                        let width = rendered_width(line);
-                        if width != 13 {
-                            failures.push(format!(
-                                "{v:?} is_day={is_day} {kind} line {i}: {width} cells: {line:?}"
-                            ));
-                        }
-                    }
-                }
-            }
+    /// Single symbol of the condition, two cells wide, empty with `IconStyle::None`
+    pub fn symbol(self, is_day: bool, style: IconStyle) -> &'static str {
+        match style {
+            IconStyle::Ascii | IconStyle::Emoji => self.emoji(is_day),
+            IconStyle::Nerdfont => self.nerd_font(is_day),
+            IconStyle::None => "",
         }
-        assert!(
-            failures.is_empty(),
-            "non-13-width icon lines:\n  {}",
-            failures.join("\n  ")
-        );
     }
+}
+
+/// Weather Icons `wi-direction-*` glyphs pointing where the wind blows to, for wind from N, NE,
+/// E, SE, S, SW, W and NW
+pub const WIND_DIRECTION_GLYPHS: [&str; 8] = [
+    "\u{e340}", "\u{e33f}", "\u{e344}", "\u{e37f}", "\u{e353}", "\u{e352}", "\u{e349}", "\u{e380}",
+];
+
+/// Nerd Font glyph shown in front of a value, followed by a space
+pub const fn field_glyph(field: TextField) -> &'static str {
+    match field {
+        TextField::Location => "\u{f041} ",
+        TextField::Condition => "\u{e312} ",
+        TextField::Temperature => "\u{e350} ",
+        TextField::Wind => "\u{e34b} ",
+        TextField::Precipitation => "\u{e371} ",
+        TextField::Pressure => "\u{e372} ",
+        TextField::Humidity => "\u{e373} ",
+        TextField::UvIndex => "\u{e30d} ",
+        TextField::Sunrise => "\u{e34c} ",
+        TextField::Sunset => "\u{e34d} ",
+        TextField::Aqi => "\u{f06c} ",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::icon_pack::IconPack;
 
     #[test]
     fn clear_day_and_night_differ() {
-        assert_ne!(
-            WeatherConditionIcon::Clear.icon(true),
-            WeatherConditionIcon::Clear.icon(false)
-        );
-        assert_ne!(
-            WeatherConditionIcon::Clear.colored_icon(true),
-            WeatherConditionIcon::Clear.colored_icon(false)
-        );
         assert_ne!(
             WeatherConditionIcon::Clear.emoji(true),
             WeatherConditionIcon::Clear.emoji(false)
@@ -364,10 +125,6 @@ mod tests {
 
     #[test]
     fn partly_cloudy_day_and_night_differ() {
-        assert_ne!(
-            WeatherConditionIcon::PartlyCloudy.icon(true),
-            WeatherConditionIcon::PartlyCloudy.icon(false)
-        );
         assert_ne!(
             WeatherConditionIcon::PartlyCloudy.emoji(true),
             WeatherConditionIcon::PartlyCloudy.emoji(false)
@@ -376,14 +133,6 @@ mod tests {
 
     #[test]
     fn cloudy_ignores_is_day() {
-        assert_eq!(
-            WeatherConditionIcon::Cloudy.icon(true),
-            WeatherConditionIcon::Cloudy.icon(false)
-        );
-        assert_eq!(
-            WeatherConditionIcon::Cloudy.colored_icon(true),
-            WeatherConditionIcon::Cloudy.colored_icon(false)
-        );
         assert_eq!(
             WeatherConditionIcon::Cloudy.emoji(true),
             WeatherConditionIcon::Cloudy.emoji(false)
@@ -399,21 +148,12 @@ mod tests {
             WeatherConditionIcon::Thunderstorm,
             WeatherConditionIcon::Fog,
         ] {
-            assert_eq!(variant.icon(true), variant.icon(false), "{variant:?}");
             assert_eq!(variant.emoji(true), variant.emoji(false), "{variant:?}");
         }
     }
 
     #[test]
     fn light_showers_day_and_night_differ() {
-        assert_ne!(
-            WeatherConditionIcon::LightShowers.icon(true),
-            WeatherConditionIcon::LightShowers.icon(false)
-        );
-        assert_ne!(
-            WeatherConditionIcon::LightShowers.colored_icon(true),
-            WeatherConditionIcon::LightShowers.colored_icon(false)
-        );
         assert_ne!(
             WeatherConditionIcon::LightShowers.emoji(true),
             WeatherConditionIcon::LightShowers.emoji(false)
@@ -421,18 +161,73 @@ mod tests {
     }
 
     #[test]
-    fn heavy_showers_day_and_night_differ() {
-        assert_ne!(
-            WeatherConditionIcon::HeavyShowers.icon(true),
-            WeatherConditionIcon::HeavyShowers.icon(false)
-        );
-        assert_ne!(
-            WeatherConditionIcon::HeavyShowers.colored_icon(true),
-            WeatherConditionIcon::HeavyShowers.colored_icon(false)
-        );
+    fn heavy_showers_emoji_ignores_is_day() {
         assert_eq!(
             WeatherConditionIcon::HeavyShowers.emoji(true),
             WeatherConditionIcon::HeavyShowers.emoji(false)
         );
     }
+
+    #[test]
+    fn fit_rows_keeps_all_rows_for_seven_lines() {
+        let icon = IconPack::default().plain(WeatherConditionIcon::Clear, true);
+        let rows: Vec<_> = (0..7).map(Some).collect();
+        assert_eq!(fit_rows(&icon, 7), rows);
+    }
+
+    #[test]
+    fn fit_rows_drops_blank_rows_before_cutting() {
+        // Clear day icon has blank first and last rows
+        let icon = IconPack::default().plain(WeatherConditionIcon::Clear, true);
+        assert_eq!(
+            fit_rows(&icon, 5),
+            vec![Some(1), Some(2), Some(3), Some(4), Some(5)]
+        );
+        assert_eq!(
+            fit_rows(&icon, 6),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4), Some(5)]
+        );
+        assert_eq!(fit_rows(&icon, 2), vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn fit_rows_pads_extra_lines() {
+        let icon = IconPack::default().plain(WeatherConditionIcon::Clear, true);
+        let rows = fit_rows(&icon, 9);
+        assert_eq!(rows.len(), 9);
+        assert_eq!(rows[6], Some(6));
+        assert_eq!(rows[7..], [None, None]);
+    }
+
+    #[test]
+    fn symbols_take_two_cells() {
+        for icon in [
+            WeatherConditionIcon::Unknown,
+            WeatherConditionIcon::Clear,
+            WeatherConditionIcon::Thunderstorm,
+        ] {
+            for is_day in [true, false] {
+                let glyph = icon.symbol(is_day, IconStyle::Nerdfont);
+                assert_eq!(glyph.chars().count(), 2, "{icon:?}");
+                assert!(glyph.ends_with(' '));
+            }
+        }
+        assert_eq!(field_glyph(TextField::Humidity), "\u{e373} ");
+        assert_eq!(
+            WeatherConditionIcon::Clear.symbol(true, IconStyle::Emoji),
+            WeatherConditionIcon::Clear.emoji(true)
+        );
+        assert_eq!(
+            WeatherConditionIcon::Clear.symbol(true, IconStyle::None),
+            ""
+        );
+    }
+
+    #[test]
+    fn nerd_font_clear_day_and_night_differ() {
+        assert_ne!(
+            WeatherConditionIcon::Clear.nerd_font(true),
+            WeatherConditionIcon::Clear.nerd_font(false)
+        );
+    }
 }
@@ -1,8 +1,17 @@
+mod chart;
 mod color;
 pub mod footer;
 pub mod formatter;
+mod html;
+pub mod icon_pack;
 pub mod icons;
+mod prometheus;
+mod records;
+pub mod status_bar;
+mod svg;
+pub mod template;
 mod theme;
 pub mod translations;
+mod width;
 
 pub use color::colored_text;
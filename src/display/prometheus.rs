@@ -0,0 +1,249 @@
+use crate::display::records::value_name;
+use crate::models::{Units, Weather};
+use std::fmt::Write;
+
+/// Escape a label value as required by the text exposition format
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_gauge(output: &mut String, name: &str, help: &str, labels: &str, value: f64) {
+    let _ = writeln!(output, "# HELP rustormy_{name} {help}");
+    let _ = writeln!(output, "# TYPE rustormy_{name} gauge");
+    let _ = writeln!(output, "rustormy_{name}{{{labels}}} {value}");
+}
+
+fn fetch_success_labels(location: &str, units: Units) -> String {
+    format!(
+        "location=\"{}\",units=\"{}\"",
+        escape_label(location),
+        value_name(&units)
+    )
+}
+
+/// Every numeric weather value as a gauge, for the node exporter textfile collector.
+/// `location` is the configured location, so the series don't change with the geocoder's naming.
+pub fn render_metrics(weather: &Weather, location: &str, units: Units) -> String {
+    let provider = weather
+        .provider
+        .as_ref()
+        .map(value_name)
+        .unwrap_or_default();
+    let labels = format!(
+        "location=\"{}\",provider=\"{provider}\",units=\"{}\"",
+        escape_label(location),
+        value_name(&units)
+    );
+    let (temperature, speed, precipitation) = match units {
+        Units::Metric => ("degrees Celsius", "m/s", "mm"),
+        Units::Imperial => ("degrees Fahrenheit", "mph", "inches"),
+    };
+
+    let mut output = String::new();
+    let gauges = [
+        (
+            "temperature",
+            format!("Air temperature in {temperature}"),
+            Some(weather.temperature),
+        ),
+        (
+            "feels_like",
+            format!("Apparent temperature in {temperature}"),
+            Some(weather.feels_like),
+        ),
+        (
+            "humidity",
+            "Relative humidity in percent".to_string(),
+            Some(f64::from(weather.humidity)),
+        ),
+        (
+            "dew_point",
+            format!("Dew point in {temperature}"),
+            Some(weather.dew_point),
+        ),
+        (
+            "pressure",
+            "Atmospheric pressure in hPa".to_string(),
+            Some(f64::from(weather.pressure)),
+        ),
+        (
+            "wind_speed",
+            format!("Wind speed in {speed}"),
+            Some(weather.wind_speed),
+        ),
+        (
+            "wind_direction",
+            "Direction the wind comes from in degrees".to_string(),
+            Some(f64::from(weather.wind_direction)),
+        ),
+        (
+            "precipitation",
+            format!("Precipitation in {precipitation}"),
+            Some(weather.precipitation),
+        ),
+        ("uv_index", "UV index".to_string(), weather.uv_index),
+        (
+            "aqi",
+            "US air quality index".to_string(),
+            weather.aqi.map(f64::from),
+        ),
+    ];
+    for (name, help, value) in gauges {
+        if let Some(value) = value {
+            write_gauge(&mut output, name, &help, &labels, value);
+        }
+    }
+
+    // Cached weather shown after every provider failed still counts as a failed fetch
+    let success = if weather.stale_since.is_some() {
+        0.0
+    } else {
+        1.0
+    };
+    write_gauge(
+        &mut output,
+        "fetch_success",
+        "Whether the last fetch of weather data succeeded",
+        &fetch_success_labels(location, units),
+        success,
+    );
+    output
+}
+
+/// Only the `rustormy_fetch_success` gauge, set to 0, for when no weather data is available
+pub fn render_failure(location: &str, units: Units) -> String {
+    let mut output = String::new();
+    write_gauge(
+        &mut output,
+        "fetch_success",
+        "Whether the last fetch of weather data succeeded",
+        &fetch_success_labels(location, units),
+        0.0,
+    );
+    output
+}
+
+/// Metrics of several locations with the samples of each metric under one `# HELP`/`# TYPE`
+/// header, as the exposition format requires
+pub fn merge<'a>(outputs: impl IntoIterator<Item = &'a str>) -> String {
+    // Name, header lines and samples of every metric, in order of appearance
+    let mut metrics: Vec<(&str, Vec<&str>, Vec<&str>)> = Vec::new();
+    for line in outputs.into_iter().flat_map(str::lines) {
+        let header = line
+            .strip_prefix("# HELP ")
+            .or_else(|| line.strip_prefix("# TYPE "));
+        let name = header
+            .unwrap_or(line)
+            .split(['{', ' '])
+            .next()
+            .unwrap_or_default();
+        let index = metrics
+            .iter()
+            .position(|(metric, _, _)| *metric == name)
+            .unwrap_or_else(|| {
+                metrics.push((name, Vec::new(), Vec::new()));
+                metrics.len() - 1
+            });
+        let (_, headers, samples) = &mut metrics[index];
+        match header {
+            Some(_) if !headers.contains(&line) => headers.push(line),
+            Some(_) => {}
+            None => samples.push(line),
+        }
+    }
+    let mut output = String::new();
+    for line in metrics
+        .into_iter()
+        .flat_map(|(_, headers, samples)| headers.into_iter().chain(samples))
+    {
+        let _ = writeln!(output, "{line}");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Location, Provider, WeatherConditionIcon};
+
+    fn sample_weather() -> Weather {
+        Weather {
+            temperature: 22.5,
+            feels_like: 21.5,
+            humidity: 60,
+            dew_point: 14.3,
+            precipitation: 0.0,
+            pressure: 1013,
+            wind_speed: 5.0,
+            wind_direction: 90,
+            uv_index: None,
+            aqi: Some(42),
+            is_day: Some(true),
+            description: "Partly cloudy".to_string(),
+            icon: WeatherConditionIcon::PartlyCloudy,
+            location: Location::new("Saint Petersburg, Russia".to_string(), 59.94, 30.31),
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::OpenMeteo),
+            hourly: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_metrics() {
+        let output = render_metrics(&sample_weather(), "Saint \"Petersburg\"", Units::Metric);
+        let labels = r#"{location="Saint \"Petersburg\"",provider="open_meteo",units="metric"}"#;
+
+        assert!(output.contains("# TYPE rustormy_temperature gauge\n"));
+        assert!(
+            output.contains("# HELP rustormy_temperature Air temperature in degrees Celsius\n")
+        );
+        assert!(output.contains(&format!("rustormy_temperature{labels} 22.5\n")));
+        assert!(output.contains(&format!("rustormy_humidity{labels} 60\n")));
+        assert!(output.contains(&format!("rustormy_wind_direction{labels} 90\n")));
+        assert!(output.contains(&format!("rustormy_aqi{labels} 42\n")));
+        assert!(!output.contains("rustormy_uv_index"), "{output}");
+        assert!(output.contains(
+            "rustormy_fetch_success{location=\"Saint \\\"Petersburg\\\"\",units=\"metric\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_metrics_stale_is_failed_fetch() {
+        let mut weather = sample_weather();
+        weather.stale_since = Some(chrono::Utc::now());
+        let output = render_metrics(&weather, "Saint \"Petersburg\"", Units::Imperial);
+        assert!(output.contains("Air temperature in degrees Fahrenheit"));
+        assert!(output.ends_with(
+            "rustormy_fetch_success{location=\"Saint \\\"Petersburg\\\"\",units=\"imperial\"} 0\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_failure() {
+        assert_eq!(
+            render_failure("London", Units::Metric),
+            "# HELP rustormy_fetch_success Whether the last fetch of weather data succeeded\n\
+             # TYPE rustormy_fetch_success gauge\n\
+             rustormy_fetch_success{location=\"London\",units=\"metric\"} 0\n"
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let output = merge([
+            render_metrics(&sample_weather(), "London", Units::Metric).as_str(),
+            render_failure("Lisbon", Units::Metric).as_str(),
+        ]);
+        assert_eq!(output.matches("# HELP rustormy_fetch_success ").count(), 1);
+        assert!(output.ends_with(
+            "# TYPE rustormy_fetch_success gauge\n\
+             rustormy_fetch_success{location=\"London\",units=\"metric\"} 1\n\
+             rustormy_fetch_success{location=\"Lisbon\",units=\"metric\"} 0\n"
+        ));
+        assert!(output.starts_with("# HELP rustormy_temperature "));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::display::status_bar::condition_class;
+use crate::models::{Units, Weather};
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// CSV columns, in order. New columns are only ever appended so existing logs stay readable.
+pub const CSV_HEADER: &str = "timestamp,location,latitude,longitude,provider,units,temperature,\
+feels_like,humidity,dew_point,precipitation,pressure,wind_speed,wind_direction,uv_index,aqi,\
+is_day,condition,description,stale";
+
+/// Name of an enum value as accepted on the command line, e.g. `open_meteo` or `metric`
+pub(super) fn value_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// Quote a CSV field if it contains a separator, a quote or a line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// One CSV row (without line break) for a reading taken at `timestamp`
+pub fn csv_row(weather: &Weather, units: Units, timestamp: DateTime<Utc>) -> String {
+    [
+        timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+        csv_field(&weather.location.name),
+        weather.location.latitude.to_string(),
+        weather.location.longitude.to_string(),
+        weather
+            .provider
+            .as_ref()
+            .map(value_name)
+            .unwrap_or_default(),
+        value_name(&units),
+        weather.temperature.to_string(),
+        weather.feels_like.to_string(),
+        weather.humidity.to_string(),
+        weather.dew_point.to_string(),
+        weather.precipitation.to_string(),
+        weather.pressure.to_string(),
+        weather.wind_speed.to_string(),
+        weather.wind_direction.to_string(),
+        optional(weather.uv_index),
+        optional(weather.aqi),
+        optional(weather.is_day),
+        condition_class(weather.icon).to_string(),
+        csv_field(&weather.description),
+        weather.stale_since.is_some().to_string(),
+    ]
+    .join(",")
+}
+
+#[derive(Serialize)]
+struct NdjsonRecord<'a> {
+    timestamp: String,
+    units: Units,
+    #[serde(flatten)]
+    weather: &'a Weather,
+}
+
+/// The JSON object of `--format json` on a single line, with the time of the reading and the units
+pub fn ndjson_line(
+    weather: &Weather,
+    units: Units,
+    timestamp: DateTime<Utc>,
+) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&NdjsonRecord {
+        timestamp: timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+        units,
+        weather,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Location, Provider, WeatherConditionIcon};
+    use chrono::TimeZone;
+
+    fn sample_weather() -> Weather {
+        Weather {
+            temperature: 22.5,
+            feels_like: 21.5,
+            humidity: 60,
+            dew_point: 14.3,
+            precipitation: 0.0,
+            pressure: 1013,
+            wind_speed: 5.0,
+            wind_direction: 90,
+            uv_index: Some(3.2),
+            aqi: None,
+            is_day: Some(true),
+            description: "Cloudy, \"light\" rain".to_string(),
+            icon: WeatherConditionIcon::LightShowers,
+            location: Location::new("Lisbon".to_string(), 38.72, -9.14),
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::OpenMeteo),
+            hourly: Vec::new(),
+        }
+    }
+
+    fn timestamp() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 18, 9, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_csv_row() {
+        let row = csv_row(&sample_weather(), Units::Metric, timestamp());
+        assert_eq!(
+            row,
+            "2026-10-18T09:30:00Z,Lisbon,38.72,-9.14,open_meteo,metric,22.5,21.5,60,14.3,0,1013,5,90,\
+             3.2,,true,light_showers,\"Cloudy, \"\"light\"\" rain\",false"
+        );
+        assert_eq!(
+            row.matches(',').count() - 1,
+            CSV_HEADER.matches(',').count(),
+            "every column but the quoted description has no commas"
+        );
+    }
+
+    #[test]
+    fn test_ndjson_line() {
+        let line = ndjson_line(&sample_weather(), Units::Imperial, timestamp()).unwrap();
+        assert!(!line.contains('\n'));
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["timestamp"], "2026-10-18T09:30:00Z");
+        assert_eq!(json["units"], "imperial");
+        assert_eq!(json["temperature"], 22.5);
+        assert_eq!(json["location"]["name"], "Lisbon");
+        assert_eq!(json["provider"], "open_meteo");
+    }
+}
@@ -0,0 +1,51 @@
+use crate::models::WeatherConditionIcon;
+use serde::Serialize;
+
+/// Header and opening of the endless array that i3bar expects on a status command's stdout
+pub const I3BAR_HEADER: &str = "{\"version\":1}\n[\n";
+
+/// CSS class for the weather condition, for styling status bar modules
+pub const fn condition_class(icon: WeatherConditionIcon) -> &'static str {
+    match icon {
+        WeatherConditionIcon::Unknown => "unknown",
+        WeatherConditionIcon::Clear => "clear",
+        WeatherConditionIcon::PartlyCloudy => "partly_cloudy",
+        WeatherConditionIcon::Cloudy => "cloudy",
+        WeatherConditionIcon::LightShowers => "light_showers",
+        WeatherConditionIcon::HeavyShowers => "heavy_showers",
+        WeatherConditionIcon::LightSnow => "light_snow",
+        WeatherConditionIcon::HeavySnow => "heavy_snow",
+        WeatherConditionIcon::Thunderstorm => "thunderstorm",
+        WeatherConditionIcon::Fog => "fog",
+        WeatherConditionIcon::Drizzle => "drizzle",
+        WeatherConditionIcon::FreezingRain => "freezing_rain",
+        WeatherConditionIcon::Sleet => "sleet",
+        WeatherConditionIcon::Hail => "hail",
+        WeatherConditionIcon::Haze => "haze",
+        WeatherConditionIcon::Dust => "dust",
+        WeatherConditionIcon::Smoke => "smoke",
+        WeatherConditionIcon::Windy => "windy",
+        WeatherConditionIcon::Tornado => "tornado",
+    }
+}
+
+/// Output of a Waybar custom module with `return-type = "json"`
+#[derive(Debug, Serialize)]
+pub struct WaybarOutput {
+    pub text: String,
+    pub tooltip: String,
+    /// Condition class, plus `stale` when showing cached weather
+    pub class: Vec<&'static str>,
+    /// Condition class, for `format-icons`
+    pub alt: &'static str,
+}
+
+/// A block of the i3bar protocol
+#[derive(Debug, Serialize)]
+pub struct I3barBlock {
+    pub name: &'static str,
+    pub full_text: String,
+    pub short_text: String,
+    /// `#rrggbb` text color
+    pub color: &'static str,
+}
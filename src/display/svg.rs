@@ -0,0 +1,160 @@
+use crate::display::color::xterm_color;
+use crate::display::html::escape_html;
+use std::fmt::Write;
+
+const FONT_SIZE: f64 = 14.0;
+/// Advance of a monospace character, as a fraction of the font size
+const CHAR_WIDTH: f64 = FONT_SIZE * 0.6;
+const LINE_HEIGHT: f64 = FONT_SIZE * 1.3;
+const PADDING: f64 = 12.0;
+const BACKGROUND: &str = "#1e1e1e";
+const FOREGROUND: &str = "#e5e5e5";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Style {
+    fill: Option<String>,
+    bold: bool,
+}
+
+impl Style {
+    /// Apply the parameters of an SGR (`ESC [ ... m`) escape sequence
+    fn apply_sgr(&mut self, params: &str) {
+        let codes: Vec<u8> = params
+            .split(';')
+            .map(|code| code.parse().unwrap_or(0))
+            .collect();
+        let mut codes = codes.into_iter();
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                22 => self.bold = false,
+                30..=37 => self.fill = Some(xterm_color(code - 30)),
+                90..=97 => self.fill = Some(xterm_color(code - 90 + 8)),
+                39 => self.fill = None,
+                38 => match (codes.next(), codes.next()) {
+                    (Some(5), Some(index)) => self.fill = Some(xterm_color(index)),
+                    (Some(2), Some(r)) => {
+                        let (g, b) = (codes.next().unwrap_or(0), codes.next().unwrap_or(0));
+                        self.fill = Some(format!("#{r:02x}{g:02x}{b:02x}"));
+                    }
+                    _ => {}
+                },
+                // Blinking and other attributes have no SVG equivalent
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Split a line with ANSI color escapes into styled runs of text
+fn parse_ansi(line: &str) -> Vec<(Style, String)> {
+    let mut runs: Vec<(Style, String)> = Vec::new();
+    let mut style = Style::default();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+            let params: String = chars.by_ref().take_while(|&c| c != 'm').collect();
+            style.apply_sgr(&params);
+            continue;
+        }
+        match runs.last_mut() {
+            Some((last, text)) if *last == style => text.push(c),
+            _ => runs.push((style.clone(), c.to_string())),
+        }
+    }
+    runs
+}
+
+/// Render terminal output lines, with their ANSI colors, as a standalone SVG image
+pub fn render_svg(lines: &[&str]) -> String {
+    let parsed: Vec<_> = lines.iter().map(|line| parse_ansi(line)).collect();
+    let columns = parsed
+        .iter()
+        .map(|runs| runs.iter().map(|(_, text)| text.chars().count()).sum())
+        .max()
+        .unwrap_or(0);
+    let width = (PADDING * 2.0 + columns as f64 * CHAR_WIDTH).ceil();
+    let height = (PADDING * 2.0 + lines.len() as f64 * LINE_HEIGHT).ceil();
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">"
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" rx=\"8\" fill=\"{BACKGROUND}\"/>"
+    );
+    let _ = writeln!(
+        svg,
+        "<text font-family=\"ui-monospace,Menlo,Consolas,monospace\" font-size=\"{FONT_SIZE}\" \
+         fill=\"{FOREGROUND}\" xml:space=\"preserve\">"
+    );
+    for (i, runs) in parsed.iter().enumerate() {
+        let y = PADDING + FONT_SIZE + i as f64 * LINE_HEIGHT;
+        let _ = write!(svg, "<tspan x=\"{PADDING}\" y=\"{y}\">");
+        for (style, text) in runs {
+            let text = escape_html(text);
+            if *style == Style::default() {
+                svg.push_str(&text);
+                continue;
+            }
+            svg.push_str("<tspan");
+            if let Some(fill) = &style.fill {
+                let _ = write!(svg, " fill=\"{fill}\"");
+            }
+            if style.bold {
+                svg.push_str(" font-weight=\"bold\"");
+            }
+            let _ = write!(svg, ">{text}</tspan>");
+        }
+        svg.push_str("</tspan>\n");
+    }
+    let _ = writeln!(svg, "</text>");
+    let _ = writeln!(svg, "</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ansi() {
+        let runs = parse_ansi("a\x1b[38;5;226;1mb\x1b[0mc\x1b[94md\x1b[0m");
+        assert_eq!(
+            runs,
+            vec![
+                (Style::default(), "a".to_string()),
+                (
+                    Style {
+                        fill: Some("#ffff00".to_string()),
+                        bold: true
+                    },
+                    "b".to_string()
+                ),
+                (Style::default(), "c".to_string()),
+                (
+                    Style {
+                        fill: Some("#5c5cff".to_string()),
+                        bold: false
+                    },
+                    "d".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let svg = render_svg(&["\x1b[33m<hot>\x1b[0m & dry", "two"]);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<tspan fill=\"#cdcd00\">&lt;hot&gt;</tspan> &amp; dry</tspan>"));
+        assert!(svg.contains(">two</tspan>"));
+        assert!(!svg.contains('\x1b'));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
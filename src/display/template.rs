@@ -0,0 +1,307 @@
+use crate::display::color::ColorSyntax;
+use crate::display::width::pad;
+use crate::errors::RustormyError;
+use crate::models::Color;
+
+/// Widest padding of a placeholder, in columns
+const MAX_WIDTH: usize = 255;
+
+/// Weather value referenced by a `%x` placeholder in an output template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// `%c` condition emoji
+    ConditionIcon,
+    /// `%C` condition description
+    Condition,
+    /// `%t` temperature
+    Temperature,
+    /// `%f` feels-like temperature
+    FeelsLike,
+    /// `%h` humidity
+    Humidity,
+    /// `%w` wind speed and direction
+    Wind,
+    /// `%p` precipitation
+    Precipitation,
+    /// `%P` pressure
+    Pressure,
+    /// `%u` UV index
+    UvIndex,
+    /// `%l` location name
+    Location,
+    /// `%T` sparkline of the temperature in the coming hours, with its range
+    TemperatureTrend,
+    /// `%R` sparkline of the chance of precipitation in the coming hours, with its range
+    PrecipitationTrend,
+}
+
+impl Field {
+    fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            'c' => Self::ConditionIcon,
+            'C' => Self::Condition,
+            't' => Self::Temperature,
+            'f' => Self::FeelsLike,
+            'h' => Self::Humidity,
+            'w' => Self::Wind,
+            'p' => Self::Precipitation,
+            'P' => Self::Pressure,
+            'u' => Self::UvIndex,
+            'l' => Self::Location,
+            'T' => Self::TemperatureTrend,
+            'R' => Self::PrecipitationTrend,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    /// Placeholder padded to `width` columns, left-aligned if `left` is set
+    Field {
+        field: Field,
+        width: usize,
+        left: bool,
+    },
+    /// `%{ ... %}` group, rendered only if every placeholder inside has a value
+    Optional(Vec<Segment>),
+}
+
+/// Parsed output template, e.g. `"%l: %c %t %w%{ UV %u%}"`.
+///
+/// `%x` inserts a field, `%8t` / `%-8t` pads it to 8 columns (right / left aligned),
+/// `%{ ... %}` is dropped entirely when a field inside has no value, and `%%` is a literal `%`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self, RustormyError> {
+        let mut chars = template.chars().peekable();
+        // Stack of open groups, the bottom one is the template itself
+        let mut groups: Vec<Vec<Segment>> = vec![Vec::new()];
+        let mut literal = String::new();
+
+        let invalid = |reason: String| RustormyError::InvalidTemplate(reason);
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                literal.push(c);
+                continue;
+            }
+            let current = groups
+                .last_mut()
+                .expect("template group stack is never empty");
+            match chars.peek().copied() {
+                Some('%') => {
+                    chars.next();
+                    literal.push('%');
+                    continue;
+                }
+                Some('{') => {
+                    chars.next();
+                    flush_literal(&mut literal, current);
+                    groups.push(Vec::new());
+                    continue;
+                }
+                Some('}') => {
+                    chars.next();
+                    flush_literal(&mut literal, current);
+                    if groups.len() == 1 {
+                        return Err(invalid("unexpected `%}` without matching `%{`".into()));
+                    }
+                    let group = groups.pop().unwrap_or_default();
+                    if let Some(parent) = groups.last_mut() {
+                        parent.push(Segment::Optional(group));
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let left = chars.next_if_eq(&'-').is_some();
+            let mut width = String::new();
+            while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                width.push(digit);
+            }
+            let width = if width.is_empty() {
+                0
+            } else {
+                width
+                    .parse()
+                    .ok()
+                    .filter(|&width| width <= MAX_WIDTH)
+                    .ok_or_else(|| {
+                        invalid(format!("padding `{width}` is larger than {MAX_WIDTH}"))
+                    })?
+            };
+            let field = match chars.next() {
+                Some(c) => Field::from_char(c)
+                    .ok_or_else(|| invalid(format!("unknown placeholder `%{c}`")))?,
+                None => return Err(invalid("template ends with `%`".into())),
+            };
+            flush_literal(&mut literal, current);
+            current.push(Segment::Field { field, width, left });
+        }
+
+        if groups.len() > 1 {
+            return Err(invalid("`%{` is never closed with `%}`".into()));
+        }
+        let mut segments = groups.pop().unwrap_or_default();
+        flush_literal(&mut literal, &mut segments);
+        Ok(Self { segments })
+    }
+
+    /// Render the template, asking `resolve` for the text and color of every placeholder
+    pub fn render<F>(&self, colors: ColorSyntax, resolve: F) -> String
+    where
+        F: Fn(Field) -> Option<(String, Option<Color>)>,
+    {
+        render_segments(&self.segments, colors, false, &resolve).unwrap_or_default()
+    }
+
+    /// Whether the template shows one of `fields`
+    pub fn contains_any(&self, fields: &[Field]) -> bool {
+        segments_contain(&self.segments, fields)
+    }
+}
+
+fn segments_contain(segments: &[Segment], fields: &[Field]) -> bool {
+    segments.iter().any(|segment| match segment {
+        Segment::Literal(_) => false,
+        Segment::Field { field, .. } => fields.contains(field),
+        Segment::Optional(group) => segments_contain(group, fields),
+    })
+}
+
+fn flush_literal(literal: &mut String, segments: &mut Vec<Segment>) {
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(std::mem::take(literal)));
+    }
+}
+
+/// Placeholders without a value render as nothing, except inside an optional group,
+/// where `None` is returned so the whole group is dropped
+fn render_segments<F>(
+    segments: &[Segment],
+    colors: ColorSyntax,
+    in_group: bool,
+    resolve: &F,
+) -> Option<String>
+where
+    F: Fn(Field) -> Option<(String, Option<Color>)>,
+{
+    let mut output = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => output.push_str(&colors.escape(text)),
+            Segment::Field { field, width, left } => {
+                let Some((value, color)) = resolve(*field) else {
+                    if in_group {
+                        return None;
+                    }
+                    continue;
+                };
+                let padded = pad(&value, *width, !*left);
+                match color {
+                    Some(color) => output.push_str(&colors.paint(padded, color)),
+                    None => output.push_str(&colors.escape(&padded)),
+                }
+            }
+            Segment::Optional(group) => {
+                if let Some(text) = render_segments(group, colors, true, resolve) {
+                    output.push_str(&text);
+                }
+            }
+        }
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::color::ColorDepth;
+    use crate::models::AnsiColor;
+    use test_case::test_case;
+
+    fn resolve(field: Field) -> Option<(String, Option<Color>)> {
+        match field {
+            Field::Temperature => Some(("22.5°C".to_string(), Some(AnsiColor::Yellow.into()))),
+            Field::Wind => Some(("5.0 m/s ←".to_string(), None)),
+            Field::Location => Some(("Lisbon".to_string(), None)),
+            Field::Condition => Some(("맑음".to_string(), None)),
+            _ => None,
+        }
+    }
+
+    #[test_case("%t", "22.5°C" ; "single field")]
+    #[test_case("%l: %t %w", "Lisbon: 22.5°C 5.0 m/s ←" ; "several fields")]
+    #[test_case("100%%", "100%" ; "escaped percent")]
+    #[test_case("[%8t]", "[  22.5°C]" ; "right padding")]
+    #[test_case("[%-8t]", "[22.5°C  ]" ; "left padding")]
+    #[test_case("[%6C]", "[  맑음]" ; "padding of wide characters")]
+    #[test_case("%t%{ UV %u%}", "22.5°C" ; "group with missing field")]
+    #[test_case("%t%{ (%l)%}", "22.5°C (Lisbon)" ; "group with present field")]
+    #[test_case("%{%l%{ UV %u%}%}!", "Lisbon!" ; "nested groups")]
+    fn test_render(template: &str, expected: &str) {
+        let template = Template::parse(template).unwrap();
+        assert_eq!(template.render(ColorSyntax::Plain, resolve), expected);
+    }
+
+    #[test]
+    fn test_missing_field_outside_group_renders_nothing() {
+        let template = Template::parse("%t UV %u").unwrap();
+        assert_eq!(template.render(ColorSyntax::Plain, resolve), "22.5°C UV ");
+    }
+
+    #[test]
+    fn test_colors_are_applied_after_padding() {
+        let template = Template::parse("%7t %w").unwrap();
+        assert_eq!(
+            template.render(ColorSyntax::Ansi(ColorDepth::Ansi16), resolve),
+            "\x1b[33m 22.5°C\x1b[0m 5.0 m/s ←"
+        );
+    }
+
+    #[test_case(ColorSyntax::Polybar, "100%% %t", "100%% %{F#cdcd00}22.5°C%{F-}" ; "polybar")]
+    #[test_case(ColorSyntax::Tmux, "#1: %t", "##1: #[fg=yellow]22.5°C#[default]" ; "tmux")]
+    fn test_literals_are_escaped(colors: ColorSyntax, template: &str, expected: &str) {
+        let template = Template::parse(template).unwrap();
+        assert_eq!(template.render(colors, resolve), expected);
+    }
+
+    #[test_case(ColorSyntax::Polybar, "%{F-}", "%%{F-}" ; "polybar")]
+    #[test_case(ColorSyntax::Tmux, "#[fg=red]", "##[fg=red]" ; "tmux")]
+    fn test_uncolored_values_are_escaped(colors: ColorSyntax, city: &str, expected: &str) {
+        let template = Template::parse("%l").unwrap();
+        let output = template.render(colors, |_| Some((city.to_string(), None)));
+        assert_eq!(output, expected);
+    }
+
+    #[test_case("%t %T", true ; "top level")]
+    #[test_case("%t%{ %R%}", true ; "in group")]
+    #[test_case("%t %w", false ; "without trends")]
+    fn test_contains_any(template: &str, expected: bool) {
+        let template = Template::parse(template).unwrap();
+        assert_eq!(
+            template.contains_any(&[Field::TemperatureTrend, Field::PrecipitationTrend]),
+            expected
+        );
+    }
+
+    #[test_case("%x" ; "unknown placeholder")]
+    #[test_case("%t %" ; "trailing percent")]
+    #[test_case("%{%t" ; "unclosed group")]
+    #[test_case("%t%}" ; "unopened group")]
+    #[test_case("%99999999999999999999999t" ; "huge padding")]
+    #[test_case("%99999999999t" ; "padding larger than the limit")]
+    fn test_parse_errors(template: &str) {
+        assert!(matches!(
+            Template::parse(template),
+            Err(RustormyError::InvalidTemplate(_))
+        ));
+    }
+}
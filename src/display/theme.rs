@@ -1,4 +1,6 @@
-use crate::models::{AnsiColor, ColorTheme, WeatherConditionIcon};
+use crate::models::{
+    AnsiColor, Color, ColorTheme, ColorThemeOverrides, ThemeName, WeatherConditionIcon,
+};
 
 pub fn condition_color(icon: WeatherConditionIcon) -> AnsiColor {
     match icon {
@@ -6,15 +8,102 @@ pub fn condition_color(icon: WeatherConditionIcon) -> AnsiColor {
         | WeatherConditionIcon::Fog
         | WeatherConditionIcon::Cloudy => AnsiColor::White,
         WeatherConditionIcon::Clear => AnsiColor::BrightYellow,
-        WeatherConditionIcon::PartlyCloudy => AnsiColor::Yellow,
+        WeatherConditionIcon::PartlyCloudy
+        | WeatherConditionIcon::Haze
+        | WeatherConditionIcon::Dust => AnsiColor::Yellow,
         WeatherConditionIcon::LightShowers | WeatherConditionIcon::HeavyShowers => {
             AnsiColor::BrightBlue
         }
-        WeatherConditionIcon::LightSnow | WeatherConditionIcon::HeavySnow => AnsiColor::Cyan,
+        WeatherConditionIcon::Drizzle => AnsiColor::Blue,
+        WeatherConditionIcon::LightSnow
+        | WeatherConditionIcon::HeavySnow
+        | WeatherConditionIcon::Sleet => AnsiColor::Cyan,
+        WeatherConditionIcon::FreezingRain => AnsiColor::BrightCyan,
+        WeatherConditionIcon::Hail | WeatherConditionIcon::Windy => AnsiColor::BrightWhite,
+        WeatherConditionIcon::Smoke => AnsiColor::BrightBlack,
         WeatherConditionIcon::Thunderstorm => AnsiColor::BrightRed,
+        WeatherConditionIcon::Tornado => AnsiColor::Magenta,
     }
 }
 
+/// Color of the WHO UV index exposure category
+pub fn uv_index_color(uv: f64) -> AnsiColor {
+    match uv {
+        ..3.0 => AnsiColor::Green,
+        ..6.0 => AnsiColor::Yellow,
+        ..8.0 => AnsiColor::BrightRed,
+        ..11.0 => AnsiColor::Red,
+        _ => AnsiColor::Magenta,
+    }
+}
+
+/// Color of the US AQI category
+pub fn aqi_color(aqi: u16) -> AnsiColor {
+    match aqi {
+        ..=50 => AnsiColor::Green,
+        51..=100 => AnsiColor::Yellow,
+        101..=150 => AnsiColor::BrightRed,
+        151..=200 => AnsiColor::Red,
+        _ => AnsiColor::Magenta,
+    }
+}
+
+/// A gradient stop: the value and its color
+type Stop = (f64, (u8, u8, u8));
+
+const TEMPERATURE_GRADIENT: [Stop; 5] = [
+    (-20.0, (0x3b, 0x4c, 0xc0)),
+    (0.0, (0x00, 0xb4, 0xd8)),
+    (15.0, (0x2e, 0xcc, 0x71)),
+    (25.0, (0xf1, 0xc4, 0x0f)),
+    (35.0, (0xe7, 0x4c, 0x3c)),
+];
+const WIND_GRADIENT: [Stop; 4] = [
+    (0.0, (0x2e, 0xcc, 0x71)),
+    (10.0, (0xf1, 0xc4, 0x0f)),
+    (20.0, (0xe7, 0x4c, 0x3c)),
+    (30.0, (0x8e, 0x44, 0xad)),
+];
+const UV_GRADIENT: [Stop; 5] = [
+    (0.0, (0x2e, 0xcc, 0x71)),
+    (3.0, (0xf1, 0xc4, 0x0f)),
+    (6.0, (0xe6, 0x7e, 0x22)),
+    (8.0, (0xe7, 0x4c, 0x3c)),
+    (11.0, (0x8e, 0x44, 0xad)),
+];
+
+/// Color of `value` interpolated between the stops, clamped to the first and last one
+fn gradient(stops: &[Stop], value: f64) -> Color {
+    let (first, last) = (stops[0], stops[stops.len() - 1]);
+    let (r, g, b) = if value <= first.0 {
+        first.1
+    } else if value >= last.0 {
+        last.1
+    } else {
+        let i = stops.iter().position(|stop| stop.0 > value).unwrap_or(1);
+        let ((from, a), (to, b)) = (stops[i - 1], stops[i]);
+        let t = (value - from) / (to - from);
+        let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        (mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+    };
+    Color::Rgb(r, g, b)
+}
+
+/// Blue for frost to red for heat
+pub fn temperature_gradient(celsius: f64) -> Color {
+    gradient(&TEMPERATURE_GRADIENT, celsius)
+}
+
+/// Green for calm to purple for storm
+pub fn wind_gradient(meters_per_second: f64) -> Color {
+    gradient(&WIND_GRADIENT, meters_per_second)
+}
+
+/// Green for low to purple for extreme UV index
+pub fn uv_index_gradient(uv: f64) -> Color {
+    gradient(&UV_GRADIENT, uv)
+}
+
 impl Default for ColorTheme {
     fn default() -> Self {
         Self::simple()
@@ -23,13 +112,136 @@ impl Default for ColorTheme {
 impl ColorTheme {
     pub fn simple() -> Self {
         Self {
-            label: AnsiColor::BrightBlue,
-            location: AnsiColor::BrightWhite,
-            temperature: AnsiColor::BrightYellow,
-            wind: AnsiColor::BrightRed,
-            precipitation: AnsiColor::BrightCyan,
-            pressure: AnsiColor::BrightGreen,
-            humidity: AnsiColor::Blue,
+            label: AnsiColor::BrightBlue.into(),
+            location: AnsiColor::BrightWhite.into(),
+            temperature: AnsiColor::BrightYellow.into(),
+            wind: AnsiColor::BrightRed.into(),
+            precipitation: AnsiColor::BrightCyan.into(),
+            pressure: AnsiColor::BrightGreen.into(),
+            humidity: AnsiColor::Blue.into(),
+            sunrise: AnsiColor::Yellow.into(),
+            sunset: AnsiColor::Yellow.into(),
+        }
+    }
+
+    pub const fn solarized() -> Self {
+        Self {
+            label: Color::Rgb(0x26, 0x8b, 0xd2),
+            location: Color::Rgb(0x93, 0xa1, 0xa1),
+            temperature: Color::Rgb(0xb5, 0x89, 0x00),
+            wind: Color::Rgb(0xdc, 0x32, 0x2f),
+            precipitation: Color::Rgb(0x2a, 0xa1, 0x98),
+            pressure: Color::Rgb(0x85, 0x99, 0x00),
+            humidity: Color::Rgb(0x6c, 0x71, 0xc4),
+            sunrise: Color::Rgb(0xb5, 0x89, 0x00),
+            sunset: Color::Rgb(0xcb, 0x4b, 0x16),
         }
     }
+
+    pub const fn gruvbox() -> Self {
+        Self {
+            label: Color::Rgb(0x83, 0xa5, 0x98),
+            location: Color::Rgb(0xeb, 0xdb, 0xb2),
+            temperature: Color::Rgb(0xfa, 0xbd, 0x2f),
+            wind: Color::Rgb(0xfb, 0x49, 0x34),
+            precipitation: Color::Rgb(0x8e, 0xc0, 0x7c),
+            pressure: Color::Rgb(0xb8, 0xbb, 0x26),
+            humidity: Color::Rgb(0xd3, 0x86, 0x9b),
+            sunrise: Color::Rgb(0xd7, 0x99, 0x21),
+            sunset: Color::Rgb(0xfe, 0x80, 0x19),
+        }
+    }
+
+    pub const fn nord() -> Self {
+        Self {
+            label: Color::Rgb(0x81, 0xa1, 0xc1),
+            location: Color::Rgb(0xec, 0xef, 0xf4),
+            temperature: Color::Rgb(0xeb, 0xcb, 0x8b),
+            wind: Color::Rgb(0xbf, 0x61, 0x6a),
+            precipitation: Color::Rgb(0x88, 0xc0, 0xd0),
+            pressure: Color::Rgb(0xa3, 0xbe, 0x8c),
+            humidity: Color::Rgb(0x5e, 0x81, 0xac),
+            sunrise: Color::Rgb(0xeb, 0xcb, 0x8b),
+            sunset: Color::Rgb(0xd0, 0x87, 0x70),
+        }
+    }
+
+    pub fn named(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Simple => Self::simple(),
+            ThemeName::Solarized => Self::solarized(),
+            ThemeName::Gruvbox => Self::gruvbox(),
+            ThemeName::Nord => Self::nord(),
+        }
+    }
+
+    /// The theme with the colors set in `overrides` replaced
+    pub fn with_overrides(self, overrides: &ColorThemeOverrides) -> Self {
+        Self {
+            label: overrides.label.unwrap_or(self.label),
+            location: overrides.location.unwrap_or(self.location),
+            temperature: overrides.temperature.unwrap_or(self.temperature),
+            wind: overrides.wind.unwrap_or(self.wind),
+            precipitation: overrides.precipitation.unwrap_or(self.precipitation),
+            pressure: overrides.pressure.unwrap_or(self.pressure),
+            humidity: overrides.humidity.unwrap_or(self.humidity),
+            sunrise: overrides.sunrise.unwrap_or(self.sunrise),
+            sunset: overrides.sunset.unwrap_or(self.sunset),
+        }
+    }
+}
+
+impl From<ColorTheme> for ColorThemeOverrides {
+    fn from(theme: ColorTheme) -> Self {
+        Self {
+            label: Some(theme.label),
+            location: Some(theme.location),
+            temperature: Some(theme.temperature),
+            wind: Some(theme.wind),
+            precipitation: Some(theme.precipitation),
+            pressure: Some(theme.pressure),
+            humidity: Some(theme.humidity),
+            sunrise: Some(theme.sunrise),
+            sunset: Some(theme.sunset),
+        }
+    }
+}
+
+impl ColorThemeOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(-40.0, Color::Rgb(0x3b, 0x4c, 0xc0) ; "below first stop")]
+    #[test_case(0.0, Color::Rgb(0x00, 0xb4, 0xd8) ; "on a stop")]
+    #[test_case(30.0, Color::Rgb(0xec, 0x88, 0x26) ; "between stops")]
+    #[test_case(50.0, Color::Rgb(0xe7, 0x4c, 0x3c) ; "above last stop")]
+    fn test_temperature_gradient(celsius: f64, expected: Color) {
+        assert_eq!(temperature_gradient(celsius), expected);
+    }
+
+    #[test]
+    fn test_uv_index_gradient_ends() {
+        assert_eq!(uv_index_gradient(0.0), Color::Rgb(0x2e, 0xcc, 0x71));
+        assert_eq!(uv_index_gradient(12.0), Color::Rgb(0x8e, 0x44, 0xad));
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        let theme = ColorTheme::nord().with_overrides(&ColorThemeOverrides {
+            wind: Some(AnsiColor::Red.into()),
+            sunset: Some(AnsiColor::Magenta.into()),
+            ..ColorThemeOverrides::default()
+        });
+        assert_eq!(theme.wind, Color::Ansi(AnsiColor::Red));
+        assert_eq!(theme.sunset, Color::Ansi(AnsiColor::Magenta));
+        assert_eq!(theme.sunrise, ColorTheme::nord().sunrise);
+        assert_eq!(theme.label, ColorTheme::nord().label);
+    }
 }
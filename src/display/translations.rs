@@ -69,6 +69,43 @@ static TRANSLATIONS: LazyLock<HashMap<&'static str, HashMap<&'static str, &'stat
         ["es"] => "Índice UV",
         ["ko"] => "자외선 지수",
     },
+    "UV" => {
+        ["en"] => "UV",
+        ["ru"] => "УФ",
+        ["es"] => "UV",
+        ["ko"] => "자외선",
+    },
+    "Sunrise" => {
+        ["en"] => "Sunrise",
+        ["ru"] => "Восход",
+        ["es"] => "Amanecer",
+        ["ko"] => "일출",
+    },
+    "Sunset" => {
+        ["en"] => "Sunset",
+        ["ru"] => "Закат",
+        ["es"] => "Atardecer",
+        ["ko"] => "일몰",
+    },
+    "Air quality" => {
+        ["en"] => "Air quality",
+        ["ru"] => "Воздух",
+        ["es"] => "Aire",
+        ["ko"] => "대기질",
+    },
+    // Markdown table header
+    "Field" => {
+        ["en"] => "Field",
+        ["ru"] => "Параметр",
+        ["es"] => "Campo",
+        ["ko"] => "항목",
+    },
+    "Value" => {
+        ["en"] => "Value",
+        ["ru"] => "Значение",
+        ["es"] => "Valor",
+        ["ko"] => "값",
+    },
     "dew point" => {
         ["en"] => "dew point",
         ["ru"] => "точка росы",
@@ -413,6 +450,76 @@ static TRANSLATIONS: LazyLock<HashMap<&'static str, HashMap<&'static str, &'stat
         ["es"] => "hPa",
         ["ko"] => "hPa",
     },
+    // Status markers
+    "stale" => {
+        ["en"] => "stale",
+        ["ru"] => "устарело",
+        ["es"] => "desactualizado",
+        ["ko"] => "이전 데이터",
+    },
+    // Sentence mode: `{location}`, `{temperature}`, `{condition}`, `{feels_like}`, `{wind}` and
+    // `{direction}` are replaced with values. Phrasing avoids declining the location name.
+    "sentence" => {
+        ["en"] => "It is {temperature} and {condition}, feels like {feels_like}, wind {wind} {direction}.",
+        ["ru"] => "Сейчас {temperature}, {condition}, ощущается как {feels_like}, ветер {wind} {direction}.",
+        ["es"] => "Hace {temperature} ({condition}), con sensación térmica de {feels_like} y viento de {wind} {direction}.",
+        ["ko"] => "현재 날씨는 {condition}, 기온 {temperature}(체감 {feels_like}), {direction} {wind}입니다.",
+    },
+    "sentence with location" => {
+        ["en"] => "In {location} it is {temperature} and {condition}, feels like {feels_like}, wind {wind} {direction}.",
+        ["ru"] => "{location}: сейчас {temperature}, {condition}, ощущается как {feels_like}, ветер {wind} {direction}.",
+        ["es"] => "En {location} hace {temperature} ({condition}), con sensación térmica de {feels_like} y viento de {wind} {direction}.",
+        ["ko"] => "{location}의 현재 날씨는 {condition}, 기온 {temperature}(체감 {feels_like}), {direction} {wind}입니다.",
+    },
+    // Wind direction in sentence mode, where the wind comes from
+    "from N" => {
+        ["en"] => "from the north",
+        ["ru"] => "с севера",
+        ["es"] => "del norte",
+        ["ko"] => "북풍",
+    },
+    "from NE" => {
+        ["en"] => "from the north-east",
+        ["ru"] => "с северо-востока",
+        ["es"] => "del noreste",
+        ["ko"] => "북동풍",
+    },
+    "from E" => {
+        ["en"] => "from the east",
+        ["ru"] => "с востока",
+        ["es"] => "del este",
+        ["ko"] => "동풍",
+    },
+    "from SE" => {
+        ["en"] => "from the south-east",
+        ["ru"] => "с юго-востока",
+        ["es"] => "del sureste",
+        ["ko"] => "남동풍",
+    },
+    "from S" => {
+        ["en"] => "from the south",
+        ["ru"] => "с юга",
+        ["es"] => "del sur",
+        ["ko"] => "남풍",
+    },
+    "from SW" => {
+        ["en"] => "from the south-west",
+        ["ru"] => "с юго-запада",
+        ["es"] => "del suroeste",
+        ["ko"] => "남서풍",
+    },
+    "from W" => {
+        ["en"] => "from the west",
+        ["ru"] => "с запада",
+        ["es"] => "del oeste",
+        ["ko"] => "서풍",
+    },
+    "from NW" => {
+        ["en"] => "from the north-west",
+        ["ru"] => "с северо-запада",
+        ["es"] => "del noroeste",
+        ["ko"] => "북서풍",
+    },
 };
 
 fn missing_keys_set() -> &'static Mutex<HashSet<&'static str>> {
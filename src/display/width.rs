@@ -0,0 +1,213 @@
+use crate::environment;
+
+/// Zero width joiner, gluing emoji into a single picture
+const ZWJ: char = '\u{200d}';
+
+/// Variation selector asking for the emoji presentation of the character before it
+const EMOJI_PRESENTATION: char = '\u{fe0f}';
+
+/// Terminal columns taken by `text`, following the East Asian Width of its characters. Combining
+/// marks take no column, emoji presentation makes a character wide, and emoji joined by a
+/// zero width joiner take the columns of the first one.
+pub fn text_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        width += char_width(c);
+        if chars.peek() == Some(&EMOJI_PRESENTATION) && char_width(c) == 1 {
+            width += 1;
+        }
+        while chars.peek() == Some(&ZWJ) {
+            chars.next();
+            chars.next();
+        }
+    }
+    width
+}
+
+/// Terminal columns taken by `text`, not counting its ANSI color escapes
+pub fn visible_width(text: &str) -> usize {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            plain.push(c);
+        }
+    }
+    text_width(&plain)
+}
+
+/// `text` padded with spaces to `width` columns, on the right or, aligned right, on the left
+pub fn pad(text: &str, width: usize, align_right: bool) -> String {
+    let padding = " ".repeat(width.saturating_sub(visible_width(text)));
+    if align_right {
+        format!("{padding}{text}")
+    } else {
+        format!("{text}{padding}")
+    }
+}
+
+/// Columns of the terminal, or `COLUMNS` when the output is not a terminal
+pub fn terminal_width() -> Option<usize> {
+    if environment::stdout_is_terminal()
+        && let Ok((columns, _)) = crossterm::terminal::size()
+    {
+        return Some(columns.into());
+    }
+    environment::var("COLUMNS")?
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&columns| columns > 0)
+}
+
+/// Terminal columns taken by a character on its own: two for East Asian wide and fullwidth
+/// characters and emoji shown as emoji by default, none for combining and invisible characters
+#[allow(clippy::too_many_lines)]
+pub const fn char_width(c: char) -> usize {
+    match c {
+        '\u{0300}'..='\u{036f}'
+        | '\u{0483}'..='\u{0489}'
+        | '\u{0591}'..='\u{05bd}'
+        | '\u{0610}'..='\u{061a}'
+        | '\u{064b}'..='\u{065f}'
+        | '\u{0e31}'
+        | '\u{0e34}'..='\u{0e3a}'
+        | '\u{0e47}'..='\u{0e4e}'
+        | '\u{1160}'..='\u{11ff}'
+        | '\u{1ab0}'..='\u{1aff}'
+        | '\u{1dc0}'..='\u{1dff}'
+        | '\u{200b}'..='\u{200f}'
+        | '\u{2060}'..='\u{2064}'
+        | '\u{20d0}'..='\u{20ff}'
+        | '\u{fe00}'..='\u{fe0f}'
+        | '\u{fe20}'..='\u{fe2f}'
+        | '\u{feff}'
+        | '\u{1f3fb}'..='\u{1f3ff}'
+        | '\u{e0000}'..='\u{e007f}' => 0,
+        '\u{1100}'..='\u{115f}'
+        | '\u{231a}'..='\u{231b}'
+        | '\u{2329}'..='\u{232a}'
+        | '\u{23e9}'..='\u{23ec}'
+        | '\u{23f0}'
+        | '\u{23f3}'
+        | '\u{25fd}'..='\u{25fe}'
+        | '\u{2614}'..='\u{2615}'
+        | '\u{2648}'..='\u{2653}'
+        | '\u{267f}'
+        | '\u{2693}'
+        | '\u{26a1}'
+        | '\u{26aa}'..='\u{26ab}'
+        | '\u{26bd}'..='\u{26be}'
+        | '\u{26c4}'..='\u{26c5}'
+        | '\u{26ce}'
+        | '\u{26d4}'
+        | '\u{26ea}'
+        | '\u{26f2}'..='\u{26f3}'
+        | '\u{26f5}'
+        | '\u{26fa}'
+        | '\u{26fd}'
+        | '\u{2705}'
+        | '\u{270a}'..='\u{270b}'
+        | '\u{2728}'
+        | '\u{274c}'
+        | '\u{274e}'
+        | '\u{2753}'..='\u{2755}'
+        | '\u{2757}'
+        | '\u{2795}'..='\u{2797}'
+        | '\u{27b0}'
+        | '\u{27bf}'
+        | '\u{2b1b}'..='\u{2b1c}'
+        | '\u{2b50}'
+        | '\u{2b55}'
+        | '\u{2e80}'..='\u{303e}'
+        | '\u{3041}'..='\u{33ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{a000}'..='\u{a4cf}'
+        | '\u{a960}'..='\u{a97f}'
+        | '\u{ac00}'..='\u{d7a3}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{fe10}'..='\u{fe19}'
+        | '\u{fe30}'..='\u{fe6f}'
+        | '\u{ff00}'..='\u{ff60}'
+        | '\u{ffe0}'..='\u{ffe6}'
+        | '\u{1f004}'
+        | '\u{1f0cf}'
+        | '\u{1f18e}'
+        | '\u{1f191}'..='\u{1f19a}'
+        | '\u{1f200}'..='\u{1f320}'
+        | '\u{1f32d}'..='\u{1f335}'
+        | '\u{1f337}'..='\u{1f37c}'
+        | '\u{1f37e}'..='\u{1f393}'
+        | '\u{1f3a0}'..='\u{1f3ca}'
+        | '\u{1f3cf}'..='\u{1f3d3}'
+        | '\u{1f3e0}'..='\u{1f3f0}'
+        | '\u{1f3f4}'
+        | '\u{1f3f8}'..='\u{1f43e}'
+        | '\u{1f440}'
+        | '\u{1f442}'..='\u{1f4fc}'
+        | '\u{1f4ff}'..='\u{1f53d}'
+        | '\u{1f54b}'..='\u{1f54e}'
+        | '\u{1f550}'..='\u{1f567}'
+        | '\u{1f57a}'
+        | '\u{1f595}'..='\u{1f596}'
+        | '\u{1f5a4}'
+        | '\u{1f5fb}'..='\u{1f64f}'
+        | '\u{1f680}'..='\u{1f6c5}'
+        | '\u{1f6cc}'
+        | '\u{1f6d0}'..='\u{1f6d2}'
+        | '\u{1f6d5}'..='\u{1f6d7}'
+        | '\u{1f6dc}'..='\u{1f6df}'
+        | '\u{1f6eb}'..='\u{1f6ec}'
+        | '\u{1f6f4}'..='\u{1f6fc}'
+        | '\u{1f7e0}'..='\u{1f7eb}'
+        | '\u{1f7f0}'
+        | '\u{1f90c}'..='\u{1f93a}'
+        | '\u{1f93c}'..='\u{1f945}'
+        | '\u{1f947}'..='\u{1f9ff}'
+        | '\u{1fa70}'..='\u{1faff}'
+        | '\u{20000}'..='\u{2fffd}'
+        | '\u{30000}'..='\u{3fffd}' => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("   .-.   ", 9 ; "ascii")]
+    #[test_case("―(   )―", 7 ; "box drawing")]
+    #[test_case("⚡\"\"⚡", 6 ; "emoji")]
+    #[test_case("☁", 1 ; "text presentation")]
+    #[test_case("☁️", 2 ; "emoji presentation")]
+    #[test_case("🌙", 2 ; "wide emoji")]
+    #[test_case("🌫 ", 2 ; "narrow emoji")]
+    #[test_case("👩‍👩‍👧", 2 ; "zwj sequence")]
+    #[test_case("👍🏽", 2 ; "skin tone")]
+    #[test_case("東京", 4 ; "cjk")]
+    #[test_case("강수량:", 7 ; "hangul")]
+    #[test_case("Ubicación:", 10 ; "precomposed")]
+    #[test_case("Ubicacio\u{301}n:", 10 ; "combining mark")]
+    #[test_case("\u{e30d} ", 2 ; "nerd font glyph")]
+    fn test_text_width(text: &str, width: usize) {
+        assert_eq!(text_width(text), width);
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width("\x1b[38;5;226m  ― (   ) ―\x1b[0m  "), 13);
+        assert_eq!(visible_width("\x1b[33m☀\x1b[0m\u{fe0f}"), 2);
+    }
+
+    #[test_case("温度:", false, "温度:  " ; "left")]
+    #[test_case("\x1b[94m温度:\x1b[0m", true, "  \x1b[94m温度:\x1b[0m" ; "right with colors")]
+    #[test_case("Temperature:", false, "Temperature:" ; "too wide")]
+    fn test_pad(text: &str, align_right: bool, padded: &str) {
+        assert_eq!(pad(text, 7, align_right), padded);
+    }
+}
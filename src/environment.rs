@@ -0,0 +1,49 @@
+//! Environment variables and terminal of the process. Unit tests see no variables and no
+//! terminal, so that their output doesn't depend on the machine they run on.
+
+#[cfg(not(test))]
+use std::io::IsTerminal;
+
+/// Environment variable `name`, if set
+#[cfg(not(test))]
+pub fn var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+#[cfg(test)]
+pub fn var(_name: &str) -> Option<String> {
+    None
+}
+
+/// Every environment variable
+#[cfg(not(test))]
+pub fn vars() -> Vec<(String, String)> {
+    std::env::vars().collect()
+}
+
+#[cfg(test)]
+pub fn vars() -> Vec<(String, String)> {
+    Vec::new()
+}
+
+/// Whether standard output is a terminal
+#[cfg(not(test))]
+pub fn stdout_is_terminal() -> bool {
+    std::io::stdout().is_terminal()
+}
+
+#[cfg(test)]
+pub fn stdout_is_terminal() -> bool {
+    false
+}
+
+/// Whether standard error, where logs are written, is a terminal
+#[cfg(not(test))]
+pub fn stderr_is_terminal() -> bool {
+    std::io::stderr().is_terminal()
+}
+
+#[cfg(test)]
+pub fn stderr_is_terminal() -> bool {
+    false
+}
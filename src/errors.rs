@@ -18,12 +18,34 @@ pub enum RustormyError {
     InvalidCoordinates { lat: f64, lon: f64 },
     #[error("No location provided. Please specify a city or coordinates.")]
     NoLocationProvided,
-    #[error("Missing API key for selected weather provider {0:?}")]
-    MissingApiKey(Provider),
+    #[error("Unknown location \"{name}\", known locations: {known}")]
+    UnknownLocation { name: String, known: String },
+    #[error("Unknown profile \"{name}\", known profiles: {known}")]
+    UnknownProfile { name: String, known: String },
+    #[error("Invalid profile \"{name}\": {reason}")]
+    InvalidProfile { name: String, reason: String },
+    #[error("Invalid environment variable {name}: {reason}")]
+    InvalidEnvironmentVariable { name: String, reason: String },
+    #[error("Missing API key for selected weather provider {provider:?}: {reason}")]
+    MissingApiKey { provider: Provider, reason: String },
     #[error("Invalid configuration: {0}")]
     InvalidConfiguration(&'static str),
+    #[error("Invalid proxy URL \"{url}\": {source}")]
+    InvalidProxy { url: String, source: reqwest::Error },
+    #[error("Failed to load CA certificate {path}: {reason}")]
+    InvalidCertificate { path: String, reason: String },
+    #[error(
+        "Icon pack \"{name}\" not found: it is not built in and there is no {name}.toml or {name}.txt in {dir}"
+    )]
+    IconPackNotFound { name: String, dir: String },
+    #[error("Invalid icon pack \"{name}\": {reason}")]
+    InvalidIconPack { name: String, reason: String },
+    #[error("Invalid output template: {0}")]
+    InvalidTemplate(String),
     #[error("HTTP request failed: {0}")]
     HttpRequestFailed(#[from] reqwest::Error),
+    #[error("No recorded response for {0}")]
+    NoRecordedResponse(String),
     #[error("City not found: {0}")]
     CityNotFound(String),
     #[error("API returned an error: {0}")]
@@ -3,7 +3,7 @@ use crate::display::footer::format_footer;
 use crate::display::formatter::WeatherFormatter;
 use crate::errors::RustormyError;
 use crate::models::Weather;
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, Utc};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::{cursor, execute, terminal};
 use std::io::{self, Write};
@@ -65,9 +65,43 @@ pub fn render(
     Ok(())
 }
 
+/// The next refresh happens after the configured interval, but never before the
+/// provider's `Expires` time
+fn next_fetch_deadline(interval: Duration, expires: Option<DateTime<Utc>>) -> Instant {
+    let until_expires = expires
+        .and_then(|expires| (expires - Utc::now()).to_std().ok())
+        .unwrap_or_default();
+    Instant::now() + interval.max(until_expires)
+}
+
+/// Status bars and logs read updates from stdout, so instead of redrawing the terminal
+/// every refresh is written as a new line
+fn run_stream(app: &mut App) -> Result<(), RustormyError> {
+    crate::logging::init(
+        app.config().verbose(),
+        app.config().format().colors.for_stderr(),
+    );
+    let mut stdout = io::stdout();
+    stdout.write_all(app.formatter().stream_header().as_bytes())?;
+    loop {
+        let weather = app.fetch_with_fallback()?;
+        stdout.write_all(app.formatter().render_stream_update(&weather).as_bytes())?;
+        stdout.flush()?;
+
+        let deadline = next_fetch_deadline(
+            Duration::from_secs(app.config().live_mode_interval()),
+            weather.expires,
+        );
+        std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+    }
+}
+
 pub fn run(app: &mut App) -> Result<(), RustormyError> {
+    if app.config().format().output_format.is_streamed() {
+        return run_stream(app);
+    }
     let level = app.config().verbose();
-    let use_colors = app.config().format().use_colors;
+    let use_colors = app.config().format().colors.for_stderr();
     let _capture = crate::logging::init_with_capture(level, use_colors);
 
     // First fetch runs before entering the alt screen so the user's
@@ -84,7 +118,7 @@ pub fn run(app: &mut App) -> Result<(), RustormyError> {
 
     loop {
         let show_footer = app.config().live_mode_footer();
-        let use_colors = app.config().format().use_colors;
+        let use_colors = app.config().format().use_colors();
         render(
             &mut stdout,
             app.formatter(),
@@ -94,8 +128,10 @@ pub fn run(app: &mut App) -> Result<(), RustormyError> {
             use_colors,
         )?;
 
-        let interval = Duration::from_secs(app.config().live_mode_interval());
-        let deadline = Instant::now() + interval;
+        let deadline = next_fetch_deadline(
+            Duration::from_secs(app.config().live_mode_interval()),
+            weather.expires,
+        );
 
         loop {
             let remaining_wait = deadline.saturating_duration_since(Instant::now());
@@ -115,7 +151,8 @@ pub fn run(app: &mut App) -> Result<(), RustormyError> {
                         (KeyCode::Char('r'), _) => break,
                         _ => {}
                     },
-                    Event::Resize(_, _) => {
+                    Event::Resize(columns, _) => {
+                        app.formatter_mut().set_width(columns.into());
                         render(
                             &mut stdout,
                             app.formatter(),
@@ -145,6 +182,22 @@ mod tests {
         buf
     }
 
+    #[test]
+    fn test_next_fetch_deadline_respects_expires() {
+        let interval = Duration::from_mins(1);
+        let start = Instant::now();
+
+        let deadline = next_fetch_deadline(interval, None);
+        assert!(deadline >= start + interval);
+        assert!(deadline < start + Duration::from_mins(2));
+
+        let past = Utc::now() - chrono::Duration::minutes(5);
+        assert!(next_fetch_deadline(interval, Some(past)) < start + Duration::from_mins(2));
+
+        let future = Utc::now() + chrono::Duration::minutes(30);
+        assert!(next_fetch_deadline(interval, Some(future)) >= start + Duration::from_secs(1700));
+    }
+
     #[test]
     fn test_write_payload_empty() {
         assert_eq!(capture(""), b"");
@@ -159,6 +159,17 @@ pub(crate) fn format_line(state: &LoggerState, level: Level, message: &str) -> S
     }
 }
 
+/// Print a warning at any verbosity, for mistakes that would go unnoticed otherwise, e.g. a
+/// misspelled option
+pub fn warn_always(message: &str) {
+    match state() {
+        Some(state) => emit(state, Level::Warn, message),
+        None => {
+            let _ = writeln!(io::stderr().lock(), "[{}] {message}", Level::Warn.label());
+        }
+    }
+}
+
 pub(crate) fn emit(state: &'static LoggerState, level: Level, message: &str) {
     let line = format_line(state, level, message);
     if let Some(mutex) = state.capture.as_ref()
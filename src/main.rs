@@ -2,6 +2,7 @@ mod app;
 mod cache;
 mod config;
 mod display;
+mod environment;
 mod errors;
 mod live;
 mod logging;
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result as FmtResult};
@@ -46,6 +47,53 @@ pub enum OutputFormat {
     #[default]
     Text,
     Json,
+    /// Waybar custom module JSON (`text`, `tooltip`, `class`)
+    Waybar,
+    /// i3bar protocol block, for i3blocks, i3status-rs and i3bar itself in live mode
+    I3bar,
+    /// One line with Polybar color tags
+    Polybar,
+    /// One line with tmux color tags
+    Tmux,
+    /// Short plain line for a Starship custom module
+    Starship,
+    /// Prometheus text exposition format, e.g. for the node exporter textfile collector
+    Prometheus,
+    /// A CSV row per reading, after a header line
+    Csv,
+    /// A single-line JSON object per reading
+    Ndjson,
+    /// Self-contained HTML page with a weather card
+    Html,
+    /// The text output, with its colors, as an SVG image
+    Svg,
+    /// Markdown table of every available value
+    Markdown,
+}
+
+impl OutputFormat {
+    /// Status bar and log outputs are streamed line by line in live mode instead of redrawing the terminal
+    pub const fn is_streamed(self) -> bool {
+        matches!(
+            self,
+            Self::Waybar
+                | Self::I3bar
+                | Self::Polybar
+                | Self::Tmux
+                | Self::Starship
+                | Self::Csv
+                | Self::Ndjson
+        )
+    }
+
+    /// Formats that can show several locations in one output; the others make a single status
+    /// bar entry or a single document
+    pub const fn shows_many_locations(self) -> bool {
+        matches!(
+            self,
+            Self::Text | Self::Json | Self::Prometheus | Self::Csv | Self::Ndjson | Self::Markdown
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
@@ -56,10 +104,55 @@ pub enum TextMode {
     Full,
     Compact,
     OneLine,
+    /// Natural-language summary for chat bots and screen readers
+    Sentence,
+}
+
+/// When to color the output
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum ColorMode {
+    /// Only when writing to a terminal, honoring `NO_COLOR`, `CLICOLOR_FORCE` and `TERM=dumb`
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// How the weather condition and the values are pictured
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum IconStyle {
+    /// ASCII art in full and compact modes, emoji in one-line and status bar output
+    #[default]
+    Ascii,
+    Emoji,
+    /// Weather Icons glyphs of Nerd Fonts, also in front of every value
+    Nerdfont,
+    None,
 }
 
+/// A line of the full/compact text output, in the order given by `format.fields`
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
+pub enum TextField {
+    Location,
+    Condition,
+    Temperature,
+    Wind,
+    Precipitation,
+    Pressure,
+    Humidity,
+    UvIndex,
+    Sunrise,
+    Sunset,
+    Aqi,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WeatherConditionIcon {
     Unknown,
     Clear,
@@ -71,9 +164,22 @@ pub enum WeatherConditionIcon {
     HeavySnow,
     Thunderstorm,
     Fog,
+    Drizzle,
+    FreezingRain,
+    /// Rain and snow mixed, or ice pellets
+    Sleet,
+    Hail,
+    Haze,
+    /// Dust or sand
+    Dust,
+    /// Smoke or volcanic ash
+    Smoke,
+    /// Strong wind or squalls
+    Windy,
+    Tornado,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Weather {
     pub temperature: f64,
     pub feels_like: f64,
@@ -84,10 +190,37 @@ pub struct Weather {
     pub wind_speed: f64,
     pub wind_direction: u16,
     pub uv_index: Option<f64>,
+    /// US air quality index, fetched only when the `aqi` field is shown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aqi: Option<u16>,
     pub is_day: Option<bool>,
     pub description: String,
     pub icon: WeatherConditionIcon,
     pub location: Location,
+    /// Time the reading was originally fetched, set only when a stale reading
+    /// is served from the weather cache because every provider failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_since: Option<DateTime<Utc>>,
+    /// Time before which the provider asks not to be polled again (HTTP `Expires`)
+    #[serde(skip)]
+    pub expires: Option<DateTime<Utc>>,
+    /// Provider the reading came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<Provider>,
+    /// Forecast for the next hours, fetched only when a chart is shown
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hourly: Vec<ForecastHour>,
+}
+
+/// Forecast for an hour, in the configured units
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForecastHour {
+    pub time: DateTime<Utc>,
+    pub temperature: f64,
+    pub precipitation: f64,
+    /// Chance of precipitation in percent, if the forecast has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precipitation_probability: Option<u8>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,13 +267,6 @@ impl Language {
             Self::Korean => "ko",
         }
     }
-
-    pub fn label_width(self) -> usize {
-        match self {
-            Self::Korean => 4,
-            _ => 12,
-        }
-    }
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
@@ -170,13 +296,112 @@ impl Display for AnsiColor {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A color of the theme: one of the 16 ANSI colors, or a `#rrggbb` value for 256-color and
+/// truecolor terminals
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Color {
+    Ansi(AnsiColor),
+    Rgb(u8, u8, u8),
+}
+
+impl From<AnsiColor> for Color {
+    fn from(color: AnsiColor) -> Self {
+        Self::Ansi(color)
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if let Some(hex) = value.strip_prefix('#') {
+            let channel = |i: usize| {
+                hex.get(i..i + 2)
+                    .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+            };
+            return match (hex.len(), channel(0), channel(2), channel(4)) {
+                (6, Some(r), Some(g), Some(b)) => Ok(Self::Rgb(r, g, b)),
+                _ => Err(format!("invalid color `{value}`, expected `#rrggbb`")),
+            };
+        }
+        AnsiColor::deserialize(
+            serde::de::value::StrDeserializer::<serde::de::value::Error>::new(&value),
+        )
+        .map(Self::Ansi)
+        .map_err(|_| format!("unknown color `{value}`, expected an ANSI color name or `#rrggbb`"))
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Ansi(color) => format!("{color:?}"),
+            Color::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ColorTheme {
-    pub label: AnsiColor,
-    pub location: AnsiColor,
-    pub temperature: AnsiColor,
-    pub wind: AnsiColor,
-    pub precipitation: AnsiColor,
-    pub pressure: AnsiColor,
-    pub humidity: AnsiColor,
+    pub label: Color,
+    pub location: Color,
+    pub temperature: Color,
+    pub wind: Color,
+    pub precipitation: Color,
+    pub pressure: Color,
+    pub humidity: Color,
+    pub sunrise: Color,
+    pub sunset: Color,
+}
+
+/// Colors set in `[format.color_theme]`, replacing those of the named theme
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColorThemeOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wind: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precipitation: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pressure: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub humidity: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunrise: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<Color>,
+}
+
+/// Built-in color themes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum ThemeName {
+    /// The 16 ANSI colors, following the terminal's palette
+    #[default]
+    Simple,
+    Solarized,
+    Gruvbox,
+    Nord,
+}
+
+/// How the weather of several locations is arranged in text output
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum Layout {
+    /// One after another, separated by a blank line
+    #[default]
+    Stacked,
+    /// Full views side by side
+    Columns,
+    /// One row per location
+    Table,
 }
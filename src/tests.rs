@@ -33,10 +33,15 @@ impl TestProvider {
             wind_speed: 5.0,
             wind_direction: 180,
             uv_index: None,
+            aqi: None,
             is_day: Some(true),
             description: "Clear sky".to_string(),
             icon: WeatherConditionIcon::Clear,
             location,
+            stale_since: None,
+            expires: None,
+            provider: None,
+            hourly: Vec::new(),
         }
     }
 }
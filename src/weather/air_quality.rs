@@ -0,0 +1,86 @@
+use super::http;
+use crate::config::{BaseUrls, Config};
+use crate::errors::RustormyError;
+use crate::models::Location;
+use reqwest::blocking::Client;
+
+const BASE_URL: &str = "https://air-quality-api.open-meteo.com";
+const AIR_QUALITY_API_PATH: &str = "/v1/air-quality";
+
+#[derive(Debug, serde::Serialize)]
+struct AirQualityRequestParams {
+    latitude: f64,
+    longitude: f64,
+    current: &'static str,
+}
+
+impl AirQualityRequestParams {
+    pub fn new(location: &Location) -> Self {
+        Self {
+            latitude: location.latitude,
+            longitude: location.longitude,
+            current: "us_aqi",
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct AirQualityCurrent {
+    us_aqi: Option<f64>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum AirQualityResponse {
+    Ok { current: AirQualityCurrent },
+    Err { reason: String },
+}
+
+impl AirQualityResponse {
+    fn into_aqi(self) -> Option<u16> {
+        match self {
+            Self::Ok { current } => current
+                .us_aqi
+                .map(|aqi| aqi.round().clamp(0.0, 500.0) as u16),
+            Self::Err { reason } => {
+                crate::warn!("Open-Meteo air quality API error: {reason}");
+                None
+            }
+        }
+    }
+}
+
+/// Current US air quality index from the Open-Meteo air quality API (no API key required)
+pub fn get_aqi(
+    client: &Client,
+    config: &Config,
+    location: &Location,
+) -> Result<Option<u16>, RustormyError> {
+    let params = AirQualityRequestParams::new(location);
+    let response: AirQualityResponse = http::get_json(
+        client
+            .get(BaseUrls::endpoint(
+                config.base_urls().open_meteo_air_quality.as_deref(),
+                BASE_URL,
+                AIR_QUALITY_API_PATH,
+            ))
+            .query(&params),
+        http::Op::air_quality_at(location),
+    )?;
+    Ok(response.into_aqi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AirQualityResponse;
+    use test_case::test_case;
+
+    #[test_case(r#"{"latitude":52.5,"longitude":13.4,"current":{"time":"2026-10-18T12:00","interval":3600,"us_aqi":41.6}}"#, Some(42) ; "valid response")]
+    #[test_case(r#"{"current":{"time":"2026-10-18T12:00","us_aqi":null}}"#, None ; "missing value")]
+    #[test_case(r#"{"error":true,"reason":"Latitude must be in range of -90 to 90°."}"#, None ; "error response")]
+    fn test_into_aqi(json: &str, expected: Option<u16>) {
+        let response: AirQualityResponse =
+            serde_json::from_str(json).expect("Failed to parse JSON");
+        assert_eq!(response.into_aqi(), expected);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::config::Config;
-use crate::models::Weather;
+use crate::models::{TextField, Weather};
+use crate::weather::air_quality::get_aqi;
+use crate::weather::hourly::get_hourly;
 use crate::weather::openuv::get_uv_index;
 use crate::weather::sun;
 use chrono::Utc;
@@ -12,7 +14,12 @@ pub fn enrich(weather: &mut Weather, client: &Client, config: &Config) {
     }
     if weather.uv_index.is_none() {
         if config.api_keys().open_uv.is_empty() {
-            crate::info!("enrich: OpenUV skipped (no api key)");
+            match config.api_keys().resolve_error("open_uv") {
+                Some(error) => {
+                    crate::warn!("OpenUV skipped, failed to resolve its API key: {error}");
+                }
+                None => crate::info!("enrich: OpenUV skipped (no api key)"),
+            }
         } else {
             match get_uv_index(client, config, &weather.location) {
                 Ok(Some(uv)) => {
@@ -28,6 +35,39 @@ pub fn enrich(weather: &mut Weather, client: &Client, config: &Config) {
             }
         }
     }
+    // Air quality takes an extra request, so only fetch it when it is displayed
+    if weather.aqi.is_none() && config.format().fields.contains(&TextField::Aqi) {
+        match get_aqi(client, config, &weather.location) {
+            Ok(Some(aqi)) => {
+                weather.aqi = Some(aqi);
+                crate::info!("enrich: fetched air quality index from Open-Meteo");
+            }
+            Ok(None) => {
+                crate::info!("enrich: Open-Meteo returned no air quality value");
+            }
+            Err(error) => {
+                crate::warn!("Failed to fetch air quality index: {error}");
+            }
+        }
+    }
+    enrich_hourly(weather, client, config);
+}
+
+/// Fetch the hourly forecast shown by charts and sparklines, if the reading has none
+pub fn enrich_hourly(weather: &mut Weather, client: &Client, config: &Config) {
+    if weather.hourly.is_empty()
+        && let Some(hours) = config.format().forecast_hours()
+    {
+        match get_hourly(client, config, &weather.location, hours) {
+            Ok(hours) => {
+                weather.hourly = hours;
+                crate::info!("enrich: fetched hourly forecast from Open-Meteo");
+            }
+            Err(error) => {
+                crate::warn!("Failed to fetch hourly forecast: {error}");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +86,7 @@ mod tests {
             wind_speed: 0.0,
             wind_direction: 0,
             uv_index: None,
+            aqi: None,
             is_day: None,
             description: String::new(),
             icon: WeatherConditionIcon::Clear,
@@ -54,6 +95,10 @@ mod tests {
                 latitude: 0.0,
                 longitude: 0.0,
             },
+            stale_since: None,
+            expires: None,
+            provider: None,
+            hourly: Vec::new(),
         }
     }
 
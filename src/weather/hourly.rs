@@ -0,0 +1,164 @@
+use super::http;
+use crate::config::{BaseUrls, Config};
+use crate::errors::RustormyError;
+use crate::models::{ForecastHour, Location, Units};
+use chrono::DateTime;
+#[cfg(test)]
+use chrono::Utc;
+use reqwest::blocking::Client;
+
+const BASE_URL: &str = "https://api.open-meteo.com";
+const FORECAST_API_PATH: &str = "/v1/forecast";
+
+#[derive(Debug, serde::Serialize)]
+struct HourlyRequestParams {
+    latitude: f64,
+    longitude: f64,
+    hourly: &'static str,
+    forecast_hours: u8,
+    timeformat: &'static str,
+    temperature_unit: &'static str,
+    precipitation_unit: &'static str,
+}
+
+impl HourlyRequestParams {
+    pub fn new(location: &Location, hours: u8, units: Units) -> Self {
+        let (temperature_unit, precipitation_unit) = match units {
+            Units::Metric => ("celsius", "mm"),
+            Units::Imperial => ("fahrenheit", "inch"),
+        };
+        Self {
+            latitude: location.latitude,
+            longitude: location.longitude,
+            hourly: "temperature_2m,precipitation_probability,precipitation",
+            forecast_hours: hours,
+            timeformat: "unixtime",
+            temperature_unit,
+            precipitation_unit,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct HourlySeries {
+    time: Vec<i64>,
+    temperature_2m: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<u8>>,
+    precipitation: Vec<Option<f64>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum HourlyResponse {
+    Ok { hourly: HourlySeries },
+    Err { reason: String },
+}
+
+impl HourlyResponse {
+    fn into_hours(self) -> Result<Vec<ForecastHour>, RustormyError> {
+        let series = match self {
+            Self::Ok { hourly } => hourly,
+            Self::Err { reason } => return Err(RustormyError::ApiReturnedError(reason)),
+        };
+        // Hours without a temperature can't be charted
+        Ok(series
+            .time
+            .iter()
+            .zip(&series.temperature_2m)
+            .enumerate()
+            .filter_map(|(i, (&time, &temperature))| {
+                Some(ForecastHour {
+                    time: DateTime::from_timestamp(time, 0)?,
+                    temperature: temperature?,
+                    precipitation: series
+                        .precipitation
+                        .get(i)
+                        .copied()
+                        .flatten()
+                        .unwrap_or(0.0),
+                    precipitation_probability: series
+                        .precipitation_probability
+                        .get(i)
+                        .copied()
+                        .flatten(),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Temperature and precipitation of the next `hours` hours from the Open-Meteo forecast API
+/// (no API key required)
+pub fn get_hourly(
+    client: &Client,
+    config: &Config,
+    location: &Location,
+    hours: u8,
+) -> Result<Vec<ForecastHour>, RustormyError> {
+    let params = HourlyRequestParams::new(location, hours, config.units());
+    let response: HourlyResponse = http::get_json(
+        client
+            .get(BaseUrls::endpoint(
+                config.base_urls().open_meteo.as_deref(),
+                BASE_URL,
+                FORECAST_API_PATH,
+            ))
+            .query(&params),
+        http::Op::hourly_at(location),
+    )?;
+    response.into_hours()
+}
+
+/// Hours from `start` with the given temperatures and chances of precipitation, for tests of
+/// what is drawn from a forecast
+#[cfg(test)]
+pub fn sample_hours(
+    start: DateTime<Utc>,
+    temperatures: &[f64],
+    probabilities: &[Option<u8>],
+) -> Vec<ForecastHour> {
+    temperatures
+        .iter()
+        .zip(probabilities)
+        .enumerate()
+        .map(
+            |(i, (&temperature, &precipitation_probability))| ForecastHour {
+                time: start + chrono::Duration::hours(i as i64),
+                temperature,
+                precipitation: 0.0,
+                precipitation_probability,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_into_hours() {
+        let json = r#"{"latitude":38.72,"longitude":-9.14,"hourly":{"time":[1792324800,1792328400,1792332000],"temperature_2m":[18.4,null,17.9],"precipitation_probability":[10,20,null],"precipitation":[0.0,0.3,0.6]}}"#;
+        let response: HourlyResponse = serde_json::from_str(json).unwrap();
+        let hours = response.into_hours().unwrap();
+
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].time.timestamp(), 1_792_324_800);
+        assert_eq!(hours[0].temperature, 18.4);
+        assert_eq!(hours[0].precipitation_probability, Some(10));
+        assert_eq!(hours[1].temperature, 17.9);
+        assert_eq!(hours[1].precipitation, 0.6);
+        assert_eq!(hours[1].precipitation_probability, None);
+    }
+
+    #[test]
+    fn test_into_hours_error() {
+        let json = r#"{"error":true,"reason":"Parameter 'forecast_hours' must be positive"}"#;
+        let response: HourlyResponse = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            response.into_hours(),
+            Err(RustormyError::ApiReturnedError(_))
+        ));
+    }
+}
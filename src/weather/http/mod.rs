@@ -0,0 +1,543 @@
+mod recording;
+
+use crate::cache::{CachedResponse, cache_response, get_cached_response};
+use crate::config::Config;
+use crate::errors::RustormyError;
+use crate::logging::{Level, level_passes, state};
+use crate::models::{Location, Provider};
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, Request, RequestBuilder, Response};
+use reqwest::header::{EXPIRES, HeaderMap, HeaderName, IF_MODIFIED_SINCE, LAST_MODIFIED};
+use reqwest::{Certificate, NoProxy, Proxy};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub use recording::{HttpMode, init_mode, mode};
+use recording::{RawResponse, redact_url};
+
+const BODY_LOG_LIMIT: usize = 1024;
+const TRUNCATED_MARKER: &str = "…[truncated]";
+
+/// Describes the HTTP call for logging purposes.
+///
+/// Built once per call via the `geocode` / `weather_at` / `weather_for` / `uv_at`
+/// constructors and passed to [`get_json`]. `Display` is only invoked when an
+/// info-level log actually fires, so providers don't allocate label strings at
+/// default verbosity.
+#[derive(Debug, Clone, Copy)]
+pub enum Op<'a> {
+    Geocode {
+        provider: Provider,
+        city: &'a str,
+    },
+    WeatherAtCity {
+        provider: Provider,
+        city: &'a str,
+    },
+    WeatherAtCoords {
+        provider: Provider,
+        lat: f64,
+        lon: f64,
+    },
+    Uv {
+        lat: f64,
+        lon: f64,
+    },
+    AirQuality {
+        lat: f64,
+        lon: f64,
+    },
+    Hourly {
+        lat: f64,
+        lon: f64,
+    },
+}
+
+impl<'a> Op<'a> {
+    pub const fn geocode(provider: Provider, city: &'a str) -> Self {
+        Self::Geocode { provider, city }
+    }
+
+    pub fn weather_for(provider: Provider, config: &'a Config) -> Self {
+        debug_assert!(
+            config.coordinates().is_some() || config.city().is_some(),
+            "Op::weather_for called with neither coordinates nor city in config",
+        );
+        if let Some((lat, lon)) = config.coordinates() {
+            Self::WeatherAtCoords { provider, lat, lon }
+        } else {
+            Self::WeatherAtCity {
+                provider,
+                city: config.city().unwrap_or(""),
+            }
+        }
+    }
+}
+
+impl Op<'static> {
+    pub fn weather_at(provider: Provider, location: &Location) -> Self {
+        Self::WeatherAtCoords {
+            provider,
+            lat: location.latitude,
+            lon: location.longitude,
+        }
+    }
+
+    pub fn uv_at(location: &Location) -> Self {
+        Self::Uv {
+            lat: location.latitude,
+            lon: location.longitude,
+        }
+    }
+
+    pub fn air_quality_at(location: &Location) -> Self {
+        Self::AirQuality {
+            lat: location.latitude,
+            lon: location.longitude,
+        }
+    }
+
+    pub fn hourly_at(location: &Location) -> Self {
+        Self::Hourly {
+            lat: location.latitude,
+            lon: location.longitude,
+        }
+    }
+}
+
+impl fmt::Display for Op<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Geocode { provider, city } => {
+                write!(f, "provider:{provider:?} geocode \"{city}\"")
+            }
+            Self::WeatherAtCity { provider, city } => {
+                write!(f, "provider:{provider:?} weather \"{city}\"")
+            }
+            Self::WeatherAtCoords { provider, lat, lon } => {
+                write!(f, "provider:{provider:?} weather {lat:.2},{lon:.2}")
+            }
+            Self::Uv { lat, lon } => {
+                write!(f, "provider:OpenUV uv {lat:.2},{lon:.2}")
+            }
+            Self::AirQuality { lat, lon } => {
+                write!(f, "provider:OpenMeteo air quality {lat:.2},{lon:.2}")
+            }
+            Self::Hourly { lat, lon } => {
+                write!(f, "provider:OpenMeteo hourly forecast {lat:.2},{lon:.2}")
+            }
+        }
+    }
+}
+
+/// Build the HTTP client from the timeout, proxy and TLS settings in the config
+pub fn build_client(config: &Config) -> Result<Client, RustormyError> {
+    // The blocking timeout limits the wait for the response headers and each read of the body
+    // (see `read_body`), so a slow but steady response is not cut off
+    let mut builder = Client::builder()
+        .user_agent(concat!("rustormy/", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(Duration::from_secs(config.connect_timeout()))
+        .timeout(Duration::from_secs(config.read_timeout()));
+
+    if let Some(url) = config.proxy() {
+        let no_proxy = config
+            .no_proxy()
+            .map_or_else(NoProxy::from_env, NoProxy::from_string);
+        let proxy = Proxy::all(url)
+            .map_err(|source| RustormyError::InvalidProxy {
+                url: url.to_string(),
+                source,
+            })?
+            .no_proxy(no_proxy);
+        builder = builder.proxy(proxy);
+    }
+
+    for path in config.ca_certificates() {
+        builder = builder.tls_certs_merge(load_certificates(path)?);
+    }
+
+    Ok(builder.build()?)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<Certificate>, RustormyError> {
+    let invalid = |reason: String| RustormyError::InvalidCertificate {
+        path: path.display().to_string(),
+        reason,
+    };
+    let pem = std::fs::read(path).map_err(|e| invalid(e.to_string()))?;
+    let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| invalid(e.to_string()))?;
+    if certificates.is_empty() {
+        return Err(invalid("no PEM certificates found".to_string()));
+    }
+    Ok(certificates)
+}
+
+pub fn get_json<T>(request: RequestBuilder, op: Op<'_>) -> Result<T, RustormyError>
+where
+    T: DeserializeOwned,
+{
+    let (client, request) = request.build_split();
+    let response = send(&client, request?, op)?;
+    Ok(serde_json::from_str(&response.body)?)
+}
+
+/// Whether [`get_json_cached`] may reuse responses cached on disk; off for `--no-cache`
+static USE_CACHE: OnceLock<bool> = OnceLock::new();
+
+pub fn init_cache(enabled: bool) {
+    let _ = USE_CACHE.set(enabled);
+}
+
+fn use_cache() -> bool {
+    *USE_CACHE.get_or_init(|| true)
+}
+
+/// Parsed response body together with the time before which it should not be re-fetched
+#[derive(Debug)]
+pub struct Cached<T> {
+    pub data: T,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+/// Like [`get_json`], but honors `Expires` and `Last-Modified` response headers.
+///
+/// The body is cached on disk per URL. While it has not expired no request is made;
+/// after that the request carries `If-Modified-Since` and a `304 Not Modified`
+/// response reuses the cached body. The cache is bypassed with `--no-cache` and when
+/// recording or replaying.
+pub fn get_json_cached<T>(request: RequestBuilder, op: Op<'_>) -> Result<Cached<T>, RustormyError>
+where
+    T: DeserializeOwned,
+{
+    let (client, request) = request.build_split();
+    let mut request = request?;
+
+    if !use_cache() || !matches!(mode(), HttpMode::Live) {
+        let response = send(&client, request, op)?;
+        return Ok(Cached {
+            data: serde_json::from_str(&response.body)?,
+            expires: response.expires.as_deref().and_then(parse_http_date),
+        });
+    }
+
+    let url = request.url().to_string();
+    let cached = get_cached_response(&url).unwrap_or_else(|error| {
+        crate::warn!("Failed to read HTTP cache: {error}");
+        None
+    });
+    if let Some(entry) = cached.as_ref().filter(|entry| entry.is_fresh(Utc::now())) {
+        crate::info!("{op} → cached, not expired");
+        return Ok(Cached {
+            data: serde_json::from_str(&entry.body)?,
+            expires: entry.expires,
+        });
+    }
+    if let Some(value) = cached
+        .as_ref()
+        .and_then(|entry| entry.last_modified.as_deref())
+        .and_then(|last_modified| last_modified.parse().ok())
+    {
+        request.headers_mut().insert(IF_MODIFIED_SINCE, value);
+    }
+
+    let response = send(&client, request, op)?;
+    let expires = response.expires.as_deref().and_then(parse_http_date);
+    let (body, last_modified) = match cached {
+        Some(entry) if response.status == StatusCode::NOT_MODIFIED.as_u16() => {
+            (entry.body, response.last_modified.or(entry.last_modified))
+        }
+        _ => (response.body, response.last_modified),
+    };
+
+    let data = serde_json::from_str(&body)?;
+    if (200..300).contains(&response.status) || response.status == StatusCode::NOT_MODIFIED.as_u16()
+    {
+        let entry = CachedResponse {
+            url,
+            last_modified,
+            expires,
+            body,
+        };
+        if let Err(error) = cache_response(&entry) {
+            crate::warn!("Failed to write HTTP cache: {error}");
+        }
+    }
+    Ok(Cached { data, expires })
+}
+
+/// Send the request, or serve it from recordings in replay mode, and read the whole body
+fn send(client: &Client, request: Request, op: Op<'_>) -> Result<RawResponse, RustormyError> {
+    let level = state().map_or(0, |s| s.level);
+    let url = request.url().clone();
+
+    if let HttpMode::Replay(dir) = mode() {
+        let response = RawResponse::replay(dir, &url)?;
+        crate::info!("{op} → {} (replayed)", response.status);
+        log_body(level, &response.body);
+        return Ok(response);
+    }
+
+    crate::debug!("GET {}", redact_url(&url));
+    let start = level_passes(level, Level::Info).then(Instant::now);
+    let response = client.execute(request)?;
+    let status = response.status().as_u16();
+    let expires = header_string(response.headers(), &EXPIRES);
+    let last_modified = header_string(response.headers(), &LAST_MODIFIED);
+    let body = read_body(response)?;
+
+    if let Some(s) = start {
+        let ms = s.elapsed().as_millis();
+        crate::info!("{op} → {status} in {ms}ms");
+    }
+    log_body(level, &body);
+
+    let response = RawResponse {
+        url: redact_url(&url),
+        status,
+        expires,
+        last_modified,
+        body,
+    };
+    if let HttpMode::Record(dir) = mode()
+        && let Err(error) = response.record(dir)
+    {
+        crate::warn!("Failed to record response: {error}");
+    }
+    Ok(response)
+}
+
+/// Read the body one chunk at a time. Unlike `Response::text`, which limits the whole body to
+/// the client timeout, every read gets the full timeout.
+fn read_body(mut response: Response) -> Result<String, RustormyError> {
+    let mut body = Vec::new();
+    response.read_to_end(&mut body).map_err(|error| {
+        let kind = error.kind();
+        // Timeouts and connection errors are reqwest errors wrapped in an I/O error
+        let Some(inner) = error.into_inner() else {
+            return RustormyError::from(std::io::Error::from(kind));
+        };
+        match inner.downcast::<reqwest::Error>() {
+            Ok(error) => RustormyError::from(*error),
+            Err(inner) => RustormyError::from(std::io::Error::new(kind, inner)),
+        }
+    })?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+fn log_body(level: u8, body: &str) {
+    if level_passes(level, Level::Debug) {
+        crate::debug!("response body: {}", truncate_for_log(body, BODY_LOG_LIMIT));
+    }
+}
+
+fn header_string(headers: &HeaderMap, name: &HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string)
+}
+
+/// HTTP dates use the RFC 2822 layout with a `GMT` zone, e.g. `Tue, 16 Jun 2026 10:00:00 GMT`
+fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+fn truncate_for_log(s: &str, byte_limit: usize) -> String {
+    if s.len() <= byte_limit {
+        return s.to_string();
+    }
+    let mut cut = byte_limit;
+    while cut > 0 && !s.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let mut out = String::with_capacity(cut + TRUNCATED_MARKER.len());
+    out.push_str(&s[..cut]);
+    out.push_str(TRUNCATED_MARKER);
+    out
+}
+
+/// Answer every request on a local port with the concatenated `chunks` of a JSON body, pausing
+/// before each chunk. Returns the URL and the number of requests served so far.
+#[cfg(test)]
+pub fn serve(
+    chunks: &'static [&'static str],
+    pause: Duration,
+) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+    use std::io::Write;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let served = Arc::clone(&requests);
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            served.fetch_add(1, Ordering::SeqCst);
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buffer[..n]),
+                }
+            }
+            let length: usize = chunks.iter().map(|chunk| chunk.len()).sum();
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {length}\r\nConnection: close\r\n\r\n"
+            );
+            for chunk in chunks {
+                std::thread::sleep(pause);
+                let _ = stream.write_all(chunk.as_bytes());
+                let _ = stream.flush();
+            }
+        }
+    });
+    (url, requests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_short_string_is_unchanged() {
+        assert_eq!(truncate_for_log("hello", 1024), "hello");
+    }
+
+    #[test]
+    fn truncate_long_string_marks_truncation() {
+        let s = "x".repeat(2048);
+        let out = truncate_for_log(&s, 1024);
+        assert!(out.starts_with(&"x".repeat(1024)));
+        assert!(out.ends_with("…[truncated]"));
+        assert_eq!(out.len(), 1024 + "…[truncated]".len());
+    }
+
+    #[test]
+    fn truncate_handles_utf8_boundaries() {
+        let s = "абв";
+        let out = truncate_for_log(s, 3);
+        assert!(out.starts_with('а'));
+        assert!(out.ends_with("…[truncated]"));
+    }
+
+    #[test]
+    fn load_certificates_missing_file() {
+        let result = load_certificates(Path::new("/nonexistent/rustormy-ca.pem"));
+        assert!(matches!(
+            result,
+            Err(RustormyError::InvalidCertificate { path, .. }) if path == "/nonexistent/rustormy-ca.pem"
+        ));
+    }
+
+    #[test]
+    fn load_certificates_rejects_non_pem() {
+        let path = std::env::temp_dir().join("rustormy_test_not_a_cert.pem");
+        std::fs::write(&path, "not a certificate").unwrap();
+        let result = load_certificates(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(RustormyError::InvalidCertificate { .. })
+        ));
+    }
+
+    /// Client with a one second `read_timeout`
+    fn client_with_read_timeout() -> Client {
+        let config: Config = toml::from_str(
+            "
+            api_keys = {}
+            read_timeout = 1
+            ",
+        )
+        .unwrap();
+        build_client(&config).unwrap()
+    }
+
+    #[test]
+    fn read_timeout_applies_to_each_read() {
+        // Each chunk arrives within the timeout, the whole body doesn't
+        let pause = Duration::from_millis(600);
+        let (url, _) = serve(&["{\"a\":", "1,\"b\":", "2}"], pause);
+        let client = client_with_read_timeout();
+        let body: serde_json::Value =
+            get_json(client.get(url), Op::Uv { lat: 1.0, lon: 2.0 }).unwrap();
+        assert_eq!(body, serde_json::json!({"a": 1, "b": 2}));
+    }
+
+    #[test]
+    fn read_timeout_stops_a_stalled_response() {
+        let (url, _) = serve(&["{\"a\":", "1}"], Duration::from_millis(1500));
+        let client = client_with_read_timeout();
+        let result: Result<serde_json::Value, _> =
+            get_json(client.get(url), Op::Uv { lat: 1.0, lon: 2.0 });
+        assert!(
+            matches!(result, Err(RustormyError::HttpRequestFailed(ref error)) if error.is_timeout()),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn build_client_rejects_invalid_proxy() {
+        let config: Config = toml::from_str(
+            r#"
+            api_keys = {}
+            proxy = "not a url"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            build_client(&config),
+            Err(RustormyError::InvalidProxy { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_http_date_gmt() {
+        let date = parse_http_date("Tue, 16 Jun 2026 10:00:00 GMT").unwrap();
+        assert_eq!(date.to_rfc3339(), "2026-06-16T10:00:00+00:00");
+    }
+
+    #[test]
+    fn parse_http_date_invalid() {
+        assert!(parse_http_date("0").is_none());
+        assert!(parse_http_date("").is_none());
+    }
+
+    #[test]
+    fn op_display_geocode() {
+        let op = Op::geocode(Provider::OpenMeteo, "Lisbon");
+        assert_eq!(op.to_string(), "provider:OpenMeteo geocode \"Lisbon\"");
+    }
+
+    #[test]
+    fn op_display_weather_at() {
+        let location = Location {
+            name: "Lisbon".to_string(),
+            latitude: 38.7223,
+            longitude: -9.1393,
+        };
+        let op = Op::weather_at(Provider::OpenMeteo, &location);
+        assert_eq!(op.to_string(), "provider:OpenMeteo weather 38.72,-9.14");
+    }
+
+    #[test]
+    fn op_display_uv_at() {
+        let location = Location {
+            name: "Lisbon".to_string(),
+            latitude: 38.7223,
+            longitude: -9.1393,
+        };
+        let op = Op::uv_at(&location);
+        assert_eq!(op.to_string(), "provider:OpenUV uv 38.72,-9.14");
+    }
+}
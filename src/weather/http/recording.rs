@@ -0,0 +1,160 @@
+use crate::cache::stable_hash;
+use crate::errors::RustormyError;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Query parameters that carry API keys and are never written to recordings
+const SECRET_QUERY_PARAMS: &[&str] = &["key", "apikey", "api_key", "appid", "token"];
+const REDACTED: &str = "REDACTED";
+
+/// Whether HTTP requests go to the network, and whether responses are recorded or replayed
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum HttpMode {
+    #[default]
+    Live,
+    /// Send requests and save every response to the directory
+    Record(PathBuf),
+    /// Serve responses from the directory without touching the network
+    Replay(PathBuf),
+}
+
+static MODE: OnceLock<HttpMode> = OnceLock::new();
+
+pub fn init_mode(mode: HttpMode) {
+    let _ = MODE.set(mode);
+}
+
+pub fn mode() -> &'static HttpMode {
+    MODE.get_or_init(HttpMode::default)
+}
+
+/// Replace API keys in the query string so recordings can be shared and matched without them
+pub(super) fn redact_url(url: &Url) -> String {
+    if !url
+        .query_pairs()
+        .any(|(name, _)| SECRET_QUERY_PARAMS.contains(&name.as_ref()))
+    {
+        return url.to_string();
+    }
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            let value = if SECRET_QUERY_PARAMS.contains(&name.as_ref()) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (name.into_owned(), value)
+        })
+        .collect();
+    let mut redacted = url.clone();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted.to_string()
+}
+
+/// A response read to the end, as sent by the server or loaded from a recording
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct RawResponse {
+    /// Request URL with API keys redacted
+    pub url: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// Recordings are named after the host and a hash of the redacted URL,
+/// so the same request always maps to the same file
+fn recording_file_name(redacted_url: &str) -> String {
+    let host = Url::parse(redacted_url)
+        .ok()
+        .and_then(|url| url.host_str().map(ToString::to_string))
+        .unwrap_or_default();
+    format!("{host}_{:016x}.json", stable_hash(redacted_url))
+}
+
+impl RawResponse {
+    pub fn record(&self, dir: &Path) -> Result<(), RustormyError> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(recording_file_name(&self.url));
+        serde_json::to_writer_pretty(File::create(path)?, self)?;
+        Ok(())
+    }
+
+    pub fn replay(dir: &Path, url: &Url) -> Result<Self, RustormyError> {
+        let url = redact_url(url);
+        let path = dir.join(recording_file_name(&url));
+        if !path.exists() {
+            return Err(RustormyError::NoRecordedResponse(url));
+        }
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_url_hides_api_keys() {
+        let url =
+            Url::parse("https://api.weatherapi.com/v1/current.json?key=secret&q=London").unwrap();
+        assert_eq!(
+            redact_url(&url),
+            "https://api.weatherapi.com/v1/current.json?key=REDACTED&q=London"
+        );
+
+        let url =
+            Url::parse("https://api.openweathermap.org/data/2.5/weather?lat=1&lon=2&appid=secret")
+                .unwrap();
+        assert!(!redact_url(&url).contains("secret"));
+    }
+
+    #[test]
+    fn test_redact_url_keeps_urls_without_keys() {
+        let url =
+            Url::parse("https://api.open-meteo.com/v1/forecast?latitude=1.5&longitude=2").unwrap();
+        assert_eq!(redact_url(&url), url.to_string());
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let dir = std::env::temp_dir().join("rustormy_test_recordings");
+        let _ = std::fs::remove_dir_all(&dir);
+        let url = Url::parse("https://api.weatherbit.io/v2.0/current?key=one&city=Paris").unwrap();
+
+        assert!(matches!(
+            RawResponse::replay(&dir, &url),
+            Err(RustormyError::NoRecordedResponse(_))
+        ));
+
+        RawResponse {
+            url: redact_url(&url),
+            status: 200,
+            expires: None,
+            last_modified: None,
+            body: r#"{"data":[]}"#.to_string(),
+        }
+        .record(&dir)
+        .unwrap();
+
+        // A different API key replays the same recording
+        let other_key =
+            Url::parse("https://api.weatherbit.io/v2.0/current?key=two&city=Paris").unwrap();
+        let response = RawResponse::replay(&dir, &other_key).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, r#"{"data":[]}"#);
+
+        let saved = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap();
+        let saved = std::fs::read_to_string(saved.path()).unwrap();
+        assert!(!saved.contains("one"));
+        assert!(saved.contains("api.weatherbit.io"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -38,18 +38,25 @@ pub trait LookUpCity {
         }
         let location = self.lookup_city(client, config)?;
         if config.use_geocoding_cache() {
-            crate::cache::cache_location(city, config.language(), &location)?;
+            crate::cache::cache_location(
+                city,
+                config.language(),
+                &location,
+                config.geocoding_cache_ttl_days(),
+            )?;
         }
         Ok(location)
     }
 }
 
+mod air_quality;
 mod enrich;
+pub(crate) mod hourly;
 pub(crate) mod http;
 mod openuv;
 mod providers;
-mod sun;
+pub(crate) mod sun;
 pub mod tools;
 
-pub use enrich::enrich;
+pub use enrich::{enrich, enrich_hourly};
 pub use providers::GetWeatherProvider;
@@ -1,11 +1,12 @@
 use super::http;
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::errors::RustormyError;
 use crate::models::Location;
 use chrono::{DateTime, Utc};
 use reqwest::blocking::Client;
 
-const OPEN_UV_API_URL: &str = "https://api.openuv.io/api/v1/uv";
+const BASE_URL: &str = "https://api.openuv.io";
+const OPEN_UV_API_PATH: &str = "/api/v1/uv";
 
 #[derive(Debug, serde::Serialize)]
 struct UvRequestParams {
@@ -129,7 +130,11 @@ pub fn get_uv_index(
     let params = UvRequestParams::new(location);
     let response: UvResponse = http::get_json(
         client
-            .get(OPEN_UV_API_URL)
+            .get(BaseUrls::endpoint(
+                config.base_urls().open_uv.as_deref(),
+                BASE_URL,
+                OPEN_UV_API_PATH,
+            ))
             .query(&params)
             .header("x-access-token", &config.api_keys().open_uv),
         http::Op::uv_at(location),
@@ -1,4 +1,4 @@
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::display::translations::ll;
 use crate::errors::RustormyError;
 use crate::models::{Language, Location, Provider, Units, Weather, WeatherConditionIcon};
@@ -6,8 +6,10 @@ use crate::weather::{GetWeather, LookUpCity, http, tools};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 
-const GEO_API_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
-const WEATHER_API_URL: &str = "https://api.open-meteo.com/v1/forecast";
+const GEO_API_BASE_URL: &str = "https://geocoding-api.open-meteo.com";
+const GEO_API_PATH: &str = "/v1/search";
+const WEATHER_API_BASE_URL: &str = "https://api.open-meteo.com";
+const WEATHER_API_PATH: &str = "/v1/forecast";
 
 #[derive(Debug, Default)]
 pub struct OpenMeteo {}
@@ -50,10 +52,15 @@ impl OpenMeteoResponse {
             wind_speed: self.current.wind_speed,
             wind_direction: self.current.wind_direction,
             uv_index: None,
+            aqi: None,
             is_day: Some(self.current.is_day == 1),
             description: self.description(config.language()).to_string(),
             icon: self.icon(),
             location: location.clone(),
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::OpenMeteo),
+            hourly: Vec::new(),
         }
     }
 
@@ -100,11 +107,15 @@ impl OpenMeteoResponse {
             1..=2 => WeatherConditionIcon::PartlyCloudy,
             3 => WeatherConditionIcon::Cloudy,
             45 | 48 => WeatherConditionIcon::Fog,
-            51..=57 | 80 => WeatherConditionIcon::LightShowers,
-            61..=67 | 81 | 82 => WeatherConditionIcon::HeavyShowers,
+            51..=55 => WeatherConditionIcon::Drizzle,
+            56 | 57 | 66 | 67 => WeatherConditionIcon::FreezingRain,
+            80 => WeatherConditionIcon::LightShowers,
+            61..=65 | 81 | 82 => WeatherConditionIcon::HeavyShowers,
             71..=73 => WeatherConditionIcon::LightSnow,
             75 | 77 | 85 | 86 => WeatherConditionIcon::HeavySnow,
-            95 | 96 | 99 => WeatherConditionIcon::Thunderstorm,
+            95 => WeatherConditionIcon::Thunderstorm,
+            // Thunderstorm with hail
+            96 | 99 => WeatherConditionIcon::Hail,
             _ => WeatherConditionIcon::Unknown,
         }
     }
@@ -219,7 +230,13 @@ impl LookUpCity for OpenMeteo {
 
         let request = GeocodingRequest::new(city, config.language());
         let data = http::get_json::<ApiResponse<GeocodingResponse>>(
-            client.get(GEO_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().open_meteo_geocoding.as_deref(),
+                    GEO_API_BASE_URL,
+                    GEO_API_PATH,
+                ))
+                .query(&request),
             http::Op::geocode(Provider::OpenMeteo, city),
         )?
         .into_result()?;
@@ -234,7 +251,13 @@ impl GetWeather for OpenMeteo {
         let location = self.get_location(client, config)?;
         let request = WeatherAPIRequest::new(&location, config);
         let data = http::get_json::<ApiResponse<OpenMeteoResponse>>(
-            client.get(WEATHER_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().open_meteo.as_deref(),
+                    WEATHER_API_BASE_URL,
+                    WEATHER_API_PATH,
+                ))
+                .query(&request),
             http::Op::weather_at(Provider::OpenMeteo, &location),
         )?
         .into_result()?;
@@ -309,7 +332,10 @@ mod tests {
 
     #[test_case(0, WeatherConditionIcon::Clear)]
     #[test_case(1, WeatherConditionIcon::PartlyCloudy)]
+    #[test_case(53, WeatherConditionIcon::Drizzle)]
+    #[test_case(66, WeatherConditionIcon::FreezingRain)]
     #[test_case(95, WeatherConditionIcon::Thunderstorm)]
+    #[test_case(99, WeatherConditionIcon::Hail)]
     #[test_case(100, WeatherConditionIcon::Unknown)]
     fn test_icon(weather_code: u8, expected: WeatherConditionIcon) {
         assert_eq!(make_weather_response(weather_code).icon(), expected);
@@ -1,4 +1,4 @@
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::display::translations::ll;
 use crate::errors::RustormyError;
 use crate::models::{Language, Location, Provider, Units, Weather, WeatherConditionIcon};
@@ -6,8 +6,9 @@ use crate::weather::{GetWeather, LookUpCity, http, tools};
 use capitalize::Capitalize;
 use reqwest::blocking::Client;
 
-const GEO_API_URL: &str = "https://api.openweathermap.org/geo/1.0/direct";
-const WEATHER_API_URL: &str = "https://api.openweathermap.org/data/2.5/weather";
+const BASE_URL: &str = "https://api.openweathermap.org";
+const GEO_API_PATH: &str = "/geo/1.0/direct";
+const WEATHER_API_PATH: &str = "/data/2.5/weather";
 
 #[derive(Debug, Default)]
 pub struct OpenWeatherMap {}
@@ -135,12 +136,17 @@ impl WeatherResponseData {
             wind_speed: self.wind.speed,
             wind_direction: self.wind.deg,
             uv_index: None,
+            aqi: None,
             is_day: self.is_day(),
             description: self
                 .description()
                 .unwrap_or_else(|| ll(config.language(), "Unknown").to_string()),
             icon: self.icon(),
             location,
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::OpenWeatherMap),
+            hourly: Vec::new(),
         }
     }
 }
@@ -199,7 +205,13 @@ impl LookUpCity for OpenWeatherMap {
 
         let request = GeocodingApiRequest::new(city, config);
         let data: GeocodingApiResponse = http::get_json(
-            client.get(GEO_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().open_weather_map.as_deref(),
+                    BASE_URL,
+                    GEO_API_PATH,
+                ))
+                .query(&request),
             http::Op::geocode(Provider::OpenWeatherMap, city),
         )?;
 
@@ -220,7 +232,13 @@ impl GetWeather for OpenWeatherMap {
 
         let request = WeatherAPIRequest::new(&location, config);
         let response: WeatherApiResponse = http::get_json(
-            client.get(WEATHER_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().open_weather_map.as_deref(),
+                    BASE_URL,
+                    WEATHER_API_PATH,
+                ))
+                .query(&request),
             http::Op::weather_at(Provider::OpenWeatherMap, &location),
         )?;
         match response {
@@ -1,11 +1,12 @@
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::display::translations::ll;
 use crate::errors::RustormyError;
 use crate::models::{Language, Location, Provider, Units, Weather, WeatherConditionIcon};
 use crate::weather::{GetWeather, http};
 use reqwest::blocking::Client;
 
-const REALTIME_API_URL: &str = "https://api.tomorrow.io/v4/weather/realtime";
+const BASE_URL: &str = "https://api.tomorrow.io";
+const REALTIME_API_PATH: &str = "/v4/weather/realtime";
 
 #[derive(Debug, Default)]
 pub struct TomorrowIo {}
@@ -86,10 +87,14 @@ impl WeatherValues {
             1100 | 1101 => WeatherConditionIcon::PartlyCloudy,
             1102 | 1001 => WeatherConditionIcon::Cloudy,
             2000 | 2100 => WeatherConditionIcon::Fog,
-            4000 | 4200 | 6000 | 6200 => WeatherConditionIcon::LightShowers,
-            4001 | 4201 | 6001 | 6201 => WeatherConditionIcon::HeavyShowers,
-            5001 | 5100 | 7102 => WeatherConditionIcon::LightSnow,
-            5000 | 5101 | 7000 | 7101 => WeatherConditionIcon::HeavySnow,
+            4000 => WeatherConditionIcon::Drizzle,
+            6000 | 6001 | 6200 | 6201 => WeatherConditionIcon::FreezingRain,
+            // Ice pellets
+            7000 | 7101 | 7102 => WeatherConditionIcon::Sleet,
+            4200 => WeatherConditionIcon::LightShowers,
+            4001 | 4201 => WeatherConditionIcon::HeavyShowers,
+            5001 | 5100 => WeatherConditionIcon::LightSnow,
+            5000 | 5101 => WeatherConditionIcon::HeavySnow,
             8000 => WeatherConditionIcon::Thunderstorm,
             _ => WeatherConditionIcon::Unknown,
         }
@@ -178,10 +183,15 @@ impl WeatherResponse {
                     wind_speed: data.values.wind_speed,
                     wind_direction: data.values.wind_direction,
                     uv_index: Some((data.values.uv_index * 10.0).round() / 10.0),
+                    aqi: None,
                     is_day: None,
                     icon: data.values.icon(),
                     description: data.values.description(config.language()).to_string(),
                     location: location.into(),
+                    stale_since: None,
+                    expires: None,
+                    provider: Some(Provider::TomorrowIo),
+                    hourly: Vec::new(),
                 })
             }
         }
@@ -192,7 +202,13 @@ impl GetWeather for TomorrowIo {
     fn get_weather(&self, client: &Client, config: &Config) -> Result<Weather, RustormyError> {
         let request = WeatherRequestParams::new(config);
         let data: WeatherResponse = http::get_json(
-            client.get(REALTIME_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().tomorrow_io.as_deref(),
+                    BASE_URL,
+                    REALTIME_API_PATH,
+                ))
+                .query(&request),
             http::Op::weather_for(Provider::TomorrowIo, config),
         )?;
 
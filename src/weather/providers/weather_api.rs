@@ -1,10 +1,11 @@
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::errors::RustormyError;
 use crate::models::{Location, Provider, Units, Weather, WeatherConditionIcon};
 use crate::weather::{GetWeather, http, tools};
 use reqwest::blocking::Client;
 
-const WEATHER_API_URL: &str = "https://api.weatherapi.com/v1/current.json";
+const BASE_URL: &str = "https://api.weatherapi.com";
+const WEATHER_API_PATH: &str = "/v1/current.json";
 
 /// Module for interacting with the <https://www.weatherapi.com/> service.
 /// Requires an API key, which can be obtained for free by signing up on their website.
@@ -69,11 +70,16 @@ impl WeatherApiData {
             wind_speed: current.wind_speed(config.units()),
             wind_direction: current.wind_degree,
             uv_index: Some(current.uv_index()),
+            aqi: None,
             is_day,
             dew_point: current.dew_point(config.units()),
             description: current.description().to_string(),
             icon: current.icon(),
             location,
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::WeatherApi),
+            hourly: Vec::new(),
         }
     }
 }
@@ -200,17 +206,21 @@ impl WeatherApiCurrent {
             1006 | 1009 => WeatherConditionIcon::Cloudy,
             // Mist/Fog
             1030 | 1135 | 1147 => WeatherConditionIcon::Fog,
-            // Patchy rain, drizzle, light rain, light sleet
-            1063 | 1150 | 1153 | 1180 | 1183 | 1240 | 1249 | 1252 => {
-                WeatherConditionIcon::LightShowers
-            }
-            // Moderate/heavy rain, heavy sleet, heavy snow showers
+            // Drizzle
+            1150 | 1153 => WeatherConditionIcon::Drizzle,
+            // Freezing drizzle, freezing rain
+            1072 | 1168 | 1171 | 1198 | 1201 => WeatherConditionIcon::FreezingRain,
+            // Sleet, ice pellets
+            1069 | 1204 | 1207 | 1237 | 1249 | 1252 | 1261 | 1264 => WeatherConditionIcon::Sleet,
+            // Patchy rain, light rain
+            1063 | 1180 | 1183 | 1240 => WeatherConditionIcon::LightShowers,
+            // Moderate/heavy rain
             1186 | 1189 | 1192 | 1195 | 1243 | 1246 => WeatherConditionIcon::HeavyShowers,
-            // Patchy/light snow, ice pellets
-            1066 | 1069 | 1072 | 1210 | 1213 | 1216 | 1219 | 1222 | 1225 | 1237 | 1255 => {
+            // Patchy/light snow
+            1066 | 1210 | 1213 | 1216 | 1219 | 1222 | 1225 | 1255 => {
                 WeatherConditionIcon::LightSnow
             }
-            // Blowing/heavy snow, heavy ice pellets
+            // Blowing/heavy snow
             1114 | 1117 | 1228 | 1231 | 1258 => WeatherConditionIcon::HeavySnow,
             // Thundery outbreaks, thunderstorms
             1087 | 1273 | 1276 | 1279 | 1282 => WeatherConditionIcon::Thunderstorm,
@@ -230,7 +240,13 @@ impl GetWeather for WeatherApi {
     fn get_weather(&self, client: &Client, config: &Config) -> Result<Weather, RustormyError> {
         let request = WeatherApiRequest::new(config);
         let data: WeatherApiResponse = http::get_json(
-            client.get(WEATHER_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().weather_api.as_deref(),
+                    BASE_URL,
+                    WEATHER_API_PATH,
+                ))
+                .query(&request),
             http::Op::weather_for(Provider::WeatherApi, config),
         )?;
         match data {
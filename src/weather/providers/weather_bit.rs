@@ -1,11 +1,12 @@
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::errors::RustormyError;
 use crate::models::{Location, Provider, Units, Weather, WeatherConditionIcon};
 use crate::weather::{GetWeather, LookUpCity, http, tools};
 use reqwest::blocking::Client;
 
-const GEOCODING_API_URL: &str = "https://api.weatherbit.io/v2.0/geocode";
-const WEATHER_API_URL: &str = "https://api.weatherbit.io/v2.0/current";
+const BASE_URL: &str = "https://api.weatherbit.io";
+const GEOCODING_API_PATH: &str = "/v2.0/geocode";
+const WEATHER_API_PATH: &str = "/v2.0/current";
 
 #[derive(Debug, Default)]
 pub struct WeatherBit {}
@@ -59,7 +60,13 @@ impl LookUpCity for WeatherBit {
     ) -> Result<Location, RustormyError> {
         let request = GeocodingApiRequest::new(config)?;
         let data: GeocodingApiResponse = http::get_json(
-            client.get(GEOCODING_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().weather_bit.as_deref(),
+                    BASE_URL,
+                    GEOCODING_API_PATH,
+                ))
+                .query(&request),
             http::Op::geocode(Provider::WeatherBit, config.city().unwrap_or("")),
         )?;
         match data {
@@ -166,10 +173,15 @@ impl WeatherData {
             wind_speed: self.wind_spd,
             wind_direction: self.wind_dir,
             uv_index: Some(self.uv_index()),
+            aqi: None,
             is_day,
             icon: self.weather.icon(),
             description: self.weather.description,
             location: Location::new(self.city_name, self.lat, self.lon),
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::WeatherBit),
+            hourly: Vec::new(),
         }
     }
 }
@@ -183,7 +195,14 @@ struct WeatherDescription {
 
 impl WeatherDescription {
     fn icon(&self) -> WeatherConditionIcon {
-        tools::owm_code_to_icon(self.code)
+        // Weatherbit codes follow OpenWeatherMap, except for these
+        match self.code {
+            233 => WeatherConditionIcon::Hail,
+            610 => WeatherConditionIcon::Sleet,
+            623 => WeatherConditionIcon::LightSnow,
+            700 | 751 => WeatherConditionIcon::Fog,
+            code => tools::owm_code_to_icon(code),
+        }
     }
 }
 
@@ -192,7 +211,13 @@ impl GetWeather for WeatherBit {
         let location = self.get_location(client, config)?;
         let request = WeatherAPIRequest::new(&location, config);
         let data: WeatherApiResponse = http::get_json(
-            client.get(WEATHER_API_URL).query(&request),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().weather_bit.as_deref(),
+                    BASE_URL,
+                    WEATHER_API_PATH,
+                ))
+                .query(&request),
             http::Op::weather_at(Provider::WeatherBit, &location),
         )?;
         match data {
@@ -1,10 +1,11 @@
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::errors::RustormyError;
 use crate::models::{Language, Location, Provider, Units, Weather, WeatherConditionIcon};
 use crate::weather::{GetWeather, http, tools};
 use reqwest::blocking::Client;
 
-const WWO_API_URL: &str = "https://api.worldweatheronline.com/premium/v1/weather.ashx";
+const BASE_URL: &str = "https://api.worldweatheronline.com";
+const WWO_API_PATH: &str = "/premium/v1/weather.ashx";
 
 #[derive(Debug, serde::Serialize)]
 struct WwoRequestParams<'a> {
@@ -65,10 +66,15 @@ impl WwoWeatherData {
             wind_speed: condition.wind_speed(config.units())?,
             wind_direction: condition.wind_direction()?,
             uv_index: condition.uv_index()?,
+            aqi: None,
             is_day: None,
             description: condition.desc(config.language())?.to_string(),
             icon: condition.icon()?,
             location,
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::WorldWeatherOnline),
+            hourly: Vec::new(),
         })
     }
 
@@ -243,11 +249,18 @@ impl WwoCurrentCondition {
             119 | 122 => WeatherConditionIcon::Cloudy,
             // Mist/Fog
             143 | 248 | 260 => WeatherConditionIcon::Fog,
+            // Drizzle
+            263 | 266 => WeatherConditionIcon::Drizzle,
+            // Freezing drizzle, freezing rain
+            185 | 281 | 284 | 311 | 314 => WeatherConditionIcon::FreezingRain,
+            // Sleet, ice pellets
+            182 | 317 | 320 | 350 | 362 | 365 | 374 | 377 => WeatherConditionIcon::Sleet,
             // Light Rain Showers
-            176 | 263 | 266 | 281 | 284 => WeatherConditionIcon::LightShowers,
+            176 => WeatherConditionIcon::LightShowers,
             // Heavy Rain Showers
-            293 | 296 | 299 | 302 | 308 | 305 | 353 | 356 | 359 | 311 | 314 | 317 | 320 | 362
-            | 365 | 374 | 377 => WeatherConditionIcon::HeavyShowers,
+            293 | 296 | 299 | 302 | 308 | 305 | 353 | 356 | 359 => {
+                WeatherConditionIcon::HeavyShowers
+            }
             // Light Snow/Blowing Snow
             179 | 227 | 326 | 368 | 323 => WeatherConditionIcon::LightSnow,
             // Heavy Snow/Snow Showers
@@ -292,7 +305,13 @@ impl GetWeather for WorldWeatherOnline {
     fn get_weather(&self, client: &Client, config: &Config) -> Result<Weather, RustormyError> {
         let params = WwoRequestParams::new(config);
         let response: WwoResponse = http::get_json(
-            client.get(WWO_API_URL).query(&params),
+            client
+                .get(BaseUrls::endpoint(
+                    config.base_urls().world_weather_online.as_deref(),
+                    BASE_URL,
+                    WWO_API_PATH,
+                ))
+                .query(&params),
             http::Op::weather_for(Provider::WorldWeatherOnline, config),
         )?;
 
@@ -1,5 +1,5 @@
 use super::open_meteo::OpenMeteo;
-use crate::config::Config;
+use crate::config::{BaseUrls, Config};
 use crate::display::translations::ll;
 use crate::errors::RustormyError;
 use crate::models::{Language, Location, Provider, Units, Weather, WeatherConditionIcon};
@@ -8,7 +8,8 @@ use crate::weather::{GetWeather, LookUpCity, http};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 
-const YR_API_URL: &str = "https://api.met.no/weatherapi/locationforecast/2.0/compact";
+const BASE_URL: &str = "https://api.met.no";
+const YR_API_PATH: &str = "/weatherapi/locationforecast/2.0/compact";
 const YR_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
 #[derive(Debug, Default)]
@@ -144,6 +145,7 @@ impl YrResponse {
                 ))?
                 .round() as u16,
             uv_index: None,
+            aqi: None,
             is_day,
             description,
             icon,
@@ -153,6 +155,10 @@ impl YrResponse {
             feels_like,
             precipitation,
             location: location.clone(),
+            stale_since: None,
+            expires: None,
+            provider: Some(Provider::Yr),
+            hourly: Vec::new(),
         })
     }
 
@@ -165,14 +171,21 @@ impl YrResponse {
 impl GetWeather for Yr {
     fn get_weather(&self, client: &Client, config: &Config) -> Result<Weather, RustormyError> {
         let location = get_location(client, config)?;
-        let data: YrResponse = http::get_json(
+        // met.no terms of service require honoring `Expires` and using `If-Modified-Since`
+        let response: http::Cached<YrResponse> = http::get_json_cached(
             client
-                .get(YR_API_URL)
+                .get(BaseUrls::endpoint(
+                    config.base_urls().yr.as_deref(),
+                    BASE_URL,
+                    YR_API_PATH,
+                ))
                 .query(&YrRequest::new(&location))
                 .header("User-Agent", YR_USER_AGENT),
             http::Op::weather_at(Provider::Yr, &location),
         )?;
-        data.into_weather(config, &location)
+        let mut weather = response.data.into_weather(config, &location)?;
+        weather.expires = response.expires;
+        Ok(weather)
     }
 }
 
@@ -277,18 +290,16 @@ impl YrWeatherCode {
             Self::LightRainShowers | Self::RainShowers | Self::LightRain | Self::Rain => {
                 WeatherConditionIcon::LightShowers
             }
-            Self::HeavyRainShowers
-            | Self::HeavyRain
-            | Self::HeavySleetShowers
-            | Self::HeavySleet => WeatherConditionIcon::HeavyShowers,
+            Self::HeavyRainShowers | Self::HeavyRain => WeatherConditionIcon::HeavyShowers,
             Self::LightSleetShowers
             | Self::SleetShowers
+            | Self::HeavySleetShowers
             | Self::LightSleet
             | Self::Sleet
-            | Self::LightSnowShowers
-            | Self::SnowShowers
-            | Self::LightSnow
-            | Self::Snow => WeatherConditionIcon::LightSnow,
+            | Self::HeavySleet => WeatherConditionIcon::Sleet,
+            Self::LightSnowShowers | Self::SnowShowers | Self::LightSnow | Self::Snow => {
+                WeatherConditionIcon::LightSnow
+            }
             Self::HeavySnowShowers | Self::HeavySnow => WeatherConditionIcon::HeavySnow,
             Self::Fog => WeatherConditionIcon::Fog,
             Self::LightRainShowersAndThunder
@@ -479,6 +490,18 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_sleet_icon() {
+        assert_eq!(
+            symbol_code_to_icon("heavysleetshowers_day"),
+            WeatherConditionIcon::Sleet
+        );
+        assert_eq!(
+            symbol_code_to_icon("lightsleet"),
+            WeatherConditionIcon::Sleet
+        );
+    }
+
     #[test]
     fn test_unknown_code_fallback() {
         assert_eq!(
@@ -1,25 +1,45 @@
 use crate::models::Location;
-use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
 
 pub fn is_daytime(location: &Location, now: DateTime<Utc>) -> bool {
     solar_altitude_deg(location.latitude, location.longitude, now) > 0.0
 }
 
-/// Calculates the solar altitude (the angle of the sun above the horizon) in degrees for a given location and time.
-fn solar_altitude_deg(lat_deg: f64, lon_deg: f64, now: DateTime<Utc>) -> f64 {
-    let lat = lat_deg.to_radians();
+/// Sunrise and sunset on the date at the location at `now`, or `None` during polar day or
+/// polar night. The local date follows the mean solar time of the location's longitude.
+pub fn sun_times(
+    location: &Location,
+    now: DateTime<Utc>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    // The sun crosses a meridian 4 minutes later per degree to the west
+    let date = (now + Duration::seconds((location.longitude * 240.0).round() as i64)).date_naive();
+    let (eqtime, decl) = solar_position(date.year(), date.ordinal(), 12.0);
+    let lat = location.latitude.to_radians();
+
+    // 90.833° accounts for atmospheric refraction and the size of the solar disk
+    let cos_ha = 90.833_f64.to_radians().cos() / (lat.cos() * decl.cos()) - lat.tan() * decl.tan();
+    if !(-1.0..=1.0).contains(&cos_ha) {
+        return None;
+    }
+    let ha_deg = cos_ha.acos().to_degrees();
+
+    let midnight = date.and_hms_opt(0, 0, 0)?.and_utc();
+    let at_minutes = |minutes: f64| midnight + Duration::seconds((minutes * 60.0).round() as i64);
+    let sunrise = 720.0 - 4.0 * (location.longitude + ha_deg) - eqtime;
+    let sunset = 720.0 - 4.0 * (location.longitude - ha_deg) - eqtime;
+    Some((at_minutes(sunrise), at_minutes(sunset)))
+}
 
-    let day_of_year = f64::from(now.ordinal());
-    let hours =
-        f64::from(now.hour()) + f64::from(now.minute()) / 60.0 + f64::from(now.second()) / 3600.0;
-    let year = now.year();
+/// Equation of time (in minutes) and solar declination (in radians) at `hours` UTC of a given day
+fn solar_position(year: i32, day_of_year: u32, hours: f64) -> (f64, f64) {
     let days_in_year = if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 {
         366.0
     } else {
         365.0
     };
 
-    let gamma = std::f64::consts::TAU / days_in_year * (day_of_year - 1.0 + (hours - 12.0) / 24.0);
+    let gamma = std::f64::consts::TAU / days_in_year
+        * (f64::from(day_of_year) - 1.0 + (hours - 12.0) / 24.0);
 
     let eqtime = 229.18
         * (0.000_075 + 0.001_868 * gamma.cos()
@@ -33,6 +53,17 @@ fn solar_altitude_deg(lat_deg: f64, lon_deg: f64, now: DateTime<Utc>) -> f64 {
         - 0.002_697 * (3.0 * gamma).cos()
         + 0.001_48 * (3.0 * gamma).sin();
 
+    (eqtime, decl)
+}
+
+/// Calculates the solar altitude (the angle of the sun above the horizon) in degrees for a given location and time.
+fn solar_altitude_deg(lat_deg: f64, lon_deg: f64, now: DateTime<Utc>) -> f64 {
+    let lat = lat_deg.to_radians();
+
+    let hours =
+        f64::from(now.hour()) + f64::from(now.minute()) / 60.0 + f64::from(now.second()) / 3600.0;
+    let (eqtime, decl) = solar_position(now.year(), now.ordinal(), hours);
+
     let time_offset = eqtime + 4.0 * lon_deg;
     let tst_minutes = hours * 60.0 + time_offset;
 
@@ -72,6 +103,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn london_sun_times_at_summer_solstice() {
+        let (sunrise, sunset) = sun_times(&loc(51.5074, -0.1278), dt(2024, 6, 21, 12, 0)).unwrap();
+        // Published times are 03:43 and 20:21 UTC, the approximation is within a couple of minutes
+        assert!((sunrise - dt(2024, 6, 21, 3, 43)).num_minutes().abs() <= 2);
+        assert!((sunset - dt(2024, 6, 21, 20, 21)).num_minutes().abs() <= 2);
+    }
+
+    #[test]
+    fn tokyo_sun_times_on_local_date_before_utc_midnight() {
+        // 05:00 on June 21 in Tokyo is still June 20 in UTC
+        let (sunrise, sunset) = sun_times(&loc(35.68, 139.69), dt(2024, 6, 20, 20, 0)).unwrap();
+        // Published times are 04:25 and 19:00 JST, i.e. 19:25 and 10:00 UTC
+        assert!((sunrise - dt(2024, 6, 20, 19, 25)).num_minutes().abs() <= 2);
+        assert!((sunset - dt(2024, 6, 21, 10, 0)).num_minutes().abs() <= 2);
+    }
+
+    #[test]
+    fn polar_day_has_no_sun_times() {
+        assert!(sun_times(&loc(78.22, 15.65), dt(2024, 7, 1, 0, 0)).is_none());
+        assert!(sun_times(&loc(78.22, 15.65), dt(2024, 12, 21, 12, 0)).is_none());
+    }
+
     #[test]
     fn longyearbyen_polar_day() {
         assert!(is_daytime(&loc(78.22, 15.65), dt(2024, 7, 1, 0, 0)));
@@ -61,10 +61,18 @@ pub fn mm_to_inch(mm: f64) -> f64 {
 pub fn owm_code_to_icon(code: u32) -> WeatherConditionIcon {
     match code {
         200..=232 => WeatherConditionIcon::Thunderstorm,
-        300..=321 | 500 | 520 => WeatherConditionIcon::LightShowers,
+        300..=321 => WeatherConditionIcon::Drizzle,
+        511 => WeatherConditionIcon::FreezingRain,
+        500 | 520 => WeatherConditionIcon::LightShowers,
         500..=531 => WeatherConditionIcon::HeavyShowers,
-        600 | 612 | 615 | 620 => WeatherConditionIcon::LightSnow,
+        611..=616 => WeatherConditionIcon::Sleet,
+        600 | 620 => WeatherConditionIcon::LightSnow,
         601..=622 => WeatherConditionIcon::HeavySnow,
+        711 | 762 => WeatherConditionIcon::Smoke,
+        721 => WeatherConditionIcon::Haze,
+        731 | 751 | 761 => WeatherConditionIcon::Dust,
+        771 => WeatherConditionIcon::Windy,
+        781 => WeatherConditionIcon::Tornado,
         701..=781 => WeatherConditionIcon::Fog,
         800 => WeatherConditionIcon::Clear,
         801 | 802 => WeatherConditionIcon::PartlyCloudy,
@@ -95,6 +103,25 @@ pub fn shorten_location_name(name: String) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
+
+    #[test_case(202, WeatherConditionIcon::Thunderstorm ; "thunderstorm")]
+    #[test_case(301, WeatherConditionIcon::Drizzle ; "drizzle")]
+    #[test_case(500, WeatherConditionIcon::LightShowers ; "light rain")]
+    #[test_case(511, WeatherConditionIcon::FreezingRain ; "freezing rain")]
+    #[test_case(502, WeatherConditionIcon::HeavyShowers ; "heavy rain")]
+    #[test_case(611, WeatherConditionIcon::Sleet ; "sleet")]
+    #[test_case(616, WeatherConditionIcon::Sleet ; "rain and snow")]
+    #[test_case(602, WeatherConditionIcon::HeavySnow ; "heavy snow")]
+    #[test_case(711, WeatherConditionIcon::Smoke ; "smoke")]
+    #[test_case(721, WeatherConditionIcon::Haze ; "haze")]
+    #[test_case(761, WeatherConditionIcon::Dust ; "dust")]
+    #[test_case(771, WeatherConditionIcon::Windy ; "squalls")]
+    #[test_case(781, WeatherConditionIcon::Tornado ; "tornado")]
+    #[test_case(741, WeatherConditionIcon::Fog ; "fog")]
+    fn test_owm_code_to_icon(code: u32, expected: WeatherConditionIcon) {
+        assert_eq!(owm_code_to_icon(code), expected);
+    }
 
     #[test]
     fn shorten_passthrough_for_single_segment() {